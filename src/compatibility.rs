@@ -0,0 +1,107 @@
+use semver::{Version, VersionReq};
+
+/// Solana and Rust versions known to work with a range of Anchor versions.
+pub struct Compatibility {
+    /// Anchor versions this entry applies to
+    pub anchor: &'static str,
+    /// Solana versions that work with these Anchor versions
+    pub solana: &'static str,
+    /// Minimum Rust toolchain required to build programs with these Anchor versions
+    pub rust: &'static str,
+}
+
+/// Known-compatible toolchain combinations, newest Anchor first.
+pub const COMPATIBILITY_MATRIX: &[Compatibility] = &[
+    Compatibility {
+        anchor: ">=0.30.0, <0.31.0",
+        solana: ">=1.18.8, <2.0.0",
+        rust: ">=1.75.0",
+    },
+    Compatibility {
+        anchor: ">=0.29.0, <0.30.0",
+        solana: ">=1.16.0, <1.19.0",
+        rust: ">=1.68.0",
+    },
+    Compatibility {
+        anchor: ">=0.28.0, <0.29.0",
+        solana: ">=1.14.0, <1.17.0",
+        rust: ">=1.64.0",
+    },
+];
+
+/// Parse a version as printed by the CLIs, e.g. `v1.18.16` or `1.18.16`.
+pub fn parse_version(version: &str) -> Option<Version> {
+    Version::parse(version.trim().trim_start_matches('v')).ok()
+}
+
+/// Check the given toolchain against the compatibility matrix and return a warning for each
+/// known-incompatible pairing. Versions that can't be parsed or that aren't covered by the
+/// matrix produce no warnings.
+pub fn check(anchor: &str, solana: Option<&str>, rust: Option<&str>) -> Vec<String> {
+    let Some(anchor_version) = parse_version(anchor) else {
+        return vec![];
+    };
+    let Some(entry) = COMPATIBILITY_MATRIX.iter().find(|entry| {
+        VersionReq::parse(entry.anchor)
+            .map(|req| req.matches(&anchor_version))
+            .unwrap_or(false)
+    }) else {
+        return vec![];
+    };
+
+    let mut warnings = vec![];
+    let mut check_tool = |tool: &str, version: Option<&str>, requirement: &str| {
+        let Some(version) = version.and_then(parse_version) else {
+            return;
+        };
+        let Ok(req) = VersionReq::parse(requirement) else {
+            return;
+        };
+        if !req.matches(&version) {
+            warnings.push(format!(
+                "Anchor {anchor_version} is not known to work with {tool} {version} (expected {requirement})"
+            ));
+        }
+    };
+    check_tool("Solana", solana, entry.solana);
+    check_tool("Rust", rust, entry.rust);
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_accepts_a_v_prefix() {
+        assert_eq!(parse_version("v1.18.16"), Version::parse("1.18.16").ok());
+        assert_eq!(parse_version(" 0.30.0\n"), Version::parse("0.30.0").ok());
+        assert_eq!(parse_version("latest"), None);
+    }
+
+    #[test]
+    fn check_accepts_compatible_toolchains() {
+        assert!(check("0.30.0", Some("1.18.16"), Some("1.79.0")).is_empty());
+        assert!(check("0.29.0", Some("1.17.0"), None).is_empty());
+    }
+
+    #[test]
+    fn check_warns_about_each_incompatible_tool() {
+        let warnings = check("0.30.0", Some("1.17.0"), Some("1.70.0"));
+        assert_eq!(
+            warnings,
+            [
+                "Anchor 0.30.0 is not known to work with Solana 1.17.0 (expected >=1.18.8, <2.0.0)",
+                "Anchor 0.30.0 is not known to work with Rust 1.70.0 (expected >=1.75.0)",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_ignores_unknown_versions() {
+        assert!(check("0.1.0", Some("1.0.0"), Some("1.0.0")).is_empty());
+        assert!(check("latest", Some("1.0.0"), None).is_empty());
+        assert!(check("0.30.0", Some("nightly"), None).is_empty());
+    }
+}
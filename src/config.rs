@@ -0,0 +1,121 @@
+//! Defaults of the `init` options, read from `df-sol.toml` in the current directory or from
+//! `$XDG_CONFIG_HOME/df-sol/config.toml`. Flags passed on the command line take precedence over
+//! `df-sol.toml`, which takes precedence over the user config file.
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Config file of the current directory
+pub const CONFIG_FILE: &str = "df-sol.toml";
+
+/// Defaults of the `init` options, every field is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// Rust program template, e.g. counter
+    pub template: Option<String>,
+    /// Package manager, e.g. pnpm
+    pub package_manager: Option<String>,
+    pub anchor_version: Option<String>,
+    pub solana_version: Option<String>,
+    pub node_version: Option<String>,
+}
+
+impl Config {
+    /// Load `df-sol.toml` and the user config file, the fields of the former override the latter.
+    pub fn load() -> Result<Self> {
+        let local = Self::read(Path::new(CONFIG_FILE))?;
+        let user = match user_config_path() {
+            Some(path) => Self::read(&path)?,
+            None => Self::default(),
+        };
+        Ok(local.or(user))
+    }
+
+    /// Read a config file, the default config if it doesn't exist.
+    fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {e}", path.display()))?;
+        toml::from_str(&content).map_err(|e| anyhow!("Invalid config {}: {e}", path.display()))
+    }
+
+    /// Templates passed on the command line, else the template of the config. `None` when neither
+    /// sets one, for `init` to use its default template.
+    pub fn templates(&self, cli: &[String]) -> Option<Vec<String>> {
+        if !cli.is_empty() {
+            return Some(cli.to_vec());
+        }
+        self.template.clone().map(|template| vec![template])
+    }
+
+    /// Fields of `self`, falling back to those of `other`.
+    fn or(self, other: Self) -> Self {
+        Self {
+            template: self.template.or(other.template),
+            package_manager: self.package_manager.or(other.package_manager),
+            anchor_version: self.anchor_version.or(other.anchor_version),
+            solana_version: self.solana_version.or(other.solana_version),
+            node_version: self.node_version.or(other.node_version),
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/df-sol/config.toml`, with `XDG_CONFIG_HOME` defaulting to `~/.config`.
+fn user_config_path() -> Option<PathBuf> {
+    let config_home = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => dirs::home_dir()?.join(".config"),
+    };
+    Some(config_home.join("df-sol").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(template: Option<&str>) -> Config {
+        Config {
+            template: template.map(Into::into),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cli_templates_take_precedence_over_the_config() {
+        let cli = ["basic".to_string(), "mint-token".to_string()];
+        assert_eq!(config(Some("counter")).templates(&cli), Some(cli.to_vec()));
+    }
+
+    #[test]
+    fn config_template_applies_without_cli_templates() {
+        assert_eq!(
+            config(Some("counter")).templates(&[]),
+            Some(vec!["counter".to_string()])
+        );
+    }
+
+    #[test]
+    fn default_template_applies_without_cli_templates_or_config() {
+        assert_eq!(config(None).templates(&[]), None);
+    }
+
+    #[test]
+    fn local_config_takes_precedence_over_the_user_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join(CONFIG_FILE);
+        fs::write(&local, "template = \"counter\"\n").unwrap();
+        let user = dir.path().join("config.toml");
+        fs::write(&user, "template = \"escrow\"\npackage-manager = \"pnpm\"\n").unwrap();
+
+        let config = Config::read(&local)
+            .unwrap()
+            .or(Config::read(&user).unwrap());
+        assert_eq!(config.template.as_deref(), Some("counter"));
+        assert_eq!(config.package_manager.as_deref(), Some("pnpm"));
+    }
+}
@@ -1,167 +1,2550 @@
-use crate::rust_template::{create_anchor_toml, ProgramTemplate};
+use crate::config::Config;
+use crate::rust_template::{
+    create_anchor_toml, Commitment, DeployLoader, PackageManager, PdaSeed, ProgramTemplate,
+};
 use anyhow::{anyhow, Result};
-use clap::Parser;
-use heck::{ToKebabCase, ToSnakeCase};
-use solana_sdk::signature::Keypair;
-use std::fs::{self, File};
+use clap::{Args, FromArgMatches, Parser, ValueEnum};
+use heck::{ToKebabCase, ToPascalCase, ToSnakeCase};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair};
+use solana_sdk::signer::Signer;
+use std::collections::HashSet;
+use std::fs;
 use std::io::prelude::*;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::string::ToString;
+use std::time::Duration;
 
+pub mod compatibility;
+pub mod config;
+pub mod manifest;
 pub mod rust_template;
+pub mod style;
+pub mod template_dir;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 #[derive(Debug, Parser)]
 #[clap(version = VERSION)]
 pub struct Opts {
+    /// When to color the output, `auto` colors it in a terminal unless `NO_COLOR` is set
+    #[clap(long, global = true, value_enum, default_value_t)]
+    pub color: style::ColorChoice,
     #[clap(subcommand)]
     pub command: Command,
 }
 
 #[derive(Debug, Parser)]
+#[allow(clippy::large_enum_variant)]
 pub enum Command {
+    /// Create a new Anchor workspace
     Init {
-        /// Workspace name
+        #[clap(flatten)]
+        opts: InitOptions,
+    },
+    /// Check the installed toolchain against known-compatible versions
+    Doctor,
+    /// Build the workspace's programs with `anchor build`
+    Build {
+        /// Fail the build on any warning
+        #[clap(long)]
+        deny_warnings: bool,
+    },
+    /// Run the workspace's tests with `anchor test`
+    Test {
+        /// Test against programs already deployed to the cluster
+        #[clap(long)]
+        skip_deploy: bool,
+        /// Use an already running local validator instead of starting one
+        #[clap(long)]
+        skip_local_validator: bool,
+        /// Cluster to test against, overriding the Anchor.toml provider
+        #[clap(long)]
+        cluster: Option<String>,
+    },
+    /// Deploy the workspace's programs with `anchor deploy`
+    Deploy {
+        /// Cluster to deploy to, the Anchor.toml provider cluster by default
+        #[clap(long)]
+        cluster: Option<String>,
+        /// Keypair of the program to deploy instead of the one in target/deploy
+        #[clap(long)]
+        program_keypair: Option<PathBuf>,
+    },
+    /// Add another program to the workspace
+    Add {
+        /// Program name
+        name: String,
+        /// Rust program template to use, e.g. basic, counter or mint-token
+        #[clap(short, long, default_value = "basic")]
+        template: String,
+        /// Re-run on an existing program, keeping its files and adding the missing ones
+        #[clap(long)]
+        skip_existing_program_check: bool,
+    },
+    /// Write the program and test files of a built-in template to a directory, with placeholders
+    /// for the name and program ID, to customize and use with `init --template-dir`
+    NewTemplate {
+        /// Built-in template to start from, e.g. basic, counter or mint-token
+        template: String,
+        /// Directory to write the template to
+        out: PathBuf,
+    },
+    /// Start a local validator with the built programs of the workspace loaded
+    Localnet {
+        /// Wipe the test-ledger directory before starting
+        #[clap(long)]
+        reset: bool,
+    },
+    /// Format the programs with `cargo fmt` and the tests with the package.json prettier scripts
+    Fmt {
+        /// Only check the formatting, failing if any file would change
+        #[clap(long)]
+        check: bool,
+    },
+    /// Remove the build artifacts and the test ledger of the workspace
+    Clean {
+        /// Also remove node_modules
+        #[clap(long)]
+        node_modules: bool,
+    },
+    /// Manage the program keypairs of the workspace
+    Keys {
+        #[clap(subcommand)]
+        action: KeysAction,
+    },
+    /// Add an `#[account]` struct to a program of the workspace
+    NewAccount {
+        /// Program to add the account to
+        program: String,
+        /// Name of the account struct
         name: String,
-        /// Don't install JavaScript dependencies
+        /// Fields as `name:type`, with a max length for strings and vectors, e.g. `title:String:32`
+        fields: Vec<String>,
+        /// Keep a `.bak` copy of the program's lib.rs before modifying it
         #[clap(long)]
-        no_install: bool,
-        /// Don't initialize git
+        backup: bool,
+    },
+    /// Rewrite Anchor.toml from an older Anchor version's format
+    #[clap(name = "migrate-anchor-toml")]
+    MigrateToml {
+        /// Anchor version the Anchor.toml was written for
+        #[clap(long, default_value = "0.29.0")]
+        from: String,
+        /// Anchor version to migrate the Anchor.toml to
+        #[clap(long, default_value = rust_template::ANCHOR_VERSION)]
+        to: String,
+        /// Keep a `.bak` copy of Anchor.toml before rewriting it
         #[clap(long)]
-        no_git: bool,
-        /// Rust program template to use
-        #[clap(value_enum, short, long, default_value = "basic")]
-        template: ProgramTemplate,
-        /// Initialize even if there are files
-        #[clap(long, action)]
-        force: bool,
+        backup: bool,
+    },
+    /// Copy the built IDLs and TypeScript types to another directory
+    CopyIdl {
+        /// Directory to copy the IDL and type files to
+        out_dir: PathBuf,
+    },
+    /// Replace a program's ID in its `declare_id!` and the Anchor.toml program entries
+    SetProgramId {
+        /// Program to update
+        name: String,
+        /// New program ID
+        id: Pubkey,
+    },
+    /// Append a Usage section generated from the program's built IDL to README.md
+    ReadmeUsage {
+        /// Program to document
+        name: String,
+    },
+    /// Check that a deployed program matches the local build in target/deploy
+    Verify {
+        /// ID of the deployed program
+        program_id: Pubkey,
+        /// Cluster to fetch the program from, e.g. localnet, devnet or an RPC URL
+        #[clap(long, default_value = "localnet")]
+        cluster: String,
     },
+    /// Reproduce a workspace from the .df-sol/manifest.json written by `init --write-manifest`
+    Regenerate {
+        /// Manifest to read the template and options from
+        #[clap(long, default_value = ".df-sol/manifest.json")]
+        manifest: PathBuf,
+        /// Directory to create the workspace in, the current directory by default
+        out_dir: Option<PathBuf>,
+    },
+    /// Write every template's generated files with fixed names and IDs, for golden-file comparison
+    #[clap(hide = true)]
+    SnapshotTemplates {
+        /// Directory to write the snapshots to, one subdirectory per template
+        out_dir: PathBuf,
+    },
+}
+
+#[derive(Debug, Parser)]
+pub enum KeysAction {
+    /// Set every program's `declare_id!` and Anchor.toml entries to its keypair in target/deploy
+    Sync,
+}
+
+/// Options of `init_workspace`, the library counterpart of `df-sol init`, parsed from its flags.
+/// Options left out use their defaults, which `df-sol init` takes from the config files first.
+#[derive(Debug, Clone, Args, Serialize, Deserialize)]
+pub struct InitOptions {
+    /// Workspace name
+    pub name: String,
+    /// Don't install JavaScript dependencies
+    #[clap(long)]
+    pub no_install: bool,
+    /// Don't initialize git
+    #[clap(long)]
+    pub no_git: bool,
+    /// Rust program template to use, e.g. basic, counter or mint-token. Picked from a menu
+    /// when omitted in a terminal, basic otherwise. Repeat it or pass a comma separated list to
+    /// generate more programs, each named after its template
+    #[clap(short, long, value_delimiter = ',')]
+    pub template: Vec<String>,
+    /// Reinitialize an existing Anchor workspace, regenerating its files and default program
+    #[clap(long, action)]
+    pub force: bool,
+    /// Mint to this recipient's associated token account in the generated test
+    #[clap(long)]
+    #[serde(with = "option_pubkey")]
+    pub mint_recipient: Option<Pubkey>,
+    /// Don't generate wallet.json, use the default Solana CLI keypair instead
+    #[clap(long)]
+    pub no_wallet: bool,
+    /// Copy this keypair into the workspace as wallet.json instead of generating one
+    #[clap(long, conflicts_with = "no_wallet")]
+    pub wallet: Option<PathBuf>,
+    /// Remove node_modules and lockfiles before installing JavaScript dependencies
+    #[clap(long, conflicts_with = "no_install")]
+    pub force_reinstall: bool,
+    /// Import the program types in tests through a tsconfig.json path alias
+    #[clap(long)]
+    pub with_path_alias: bool,
+    /// Number of attempts for installing JavaScript dependencies
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub install_retries: u32,
+    /// Generate VS Code settings and recommended extensions
+    #[clap(long)]
+    pub with_vscode: bool,
+    /// Only generate the Rust program, skipping all TypeScript/JavaScript files
+    #[clap(long, conflicts_with_all = ["with_path_alias", "mint_recipient", "force_reinstall"])]
+    pub rust_only: bool,
+    /// Merge the TOML sections of this file into the generated Anchor.toml
+    #[clap(long)]
+    pub anchor_toml_append: Option<PathBuf>,
+    /// Also print the program ID declared in the generated lib.rs once initialized
+    #[clap(long)]
+    pub show_program_id: bool,
+    /// Write the generated wallet's public key to this file
+    #[clap(long, conflicts_with = "no_wallet")]
+    pub write_pubkey: Option<PathBuf>,
+    /// Cluster the generated tests run against, overriding the provider cluster for tests only
+    #[clap(long)]
+    pub test_cluster: Option<String>,
+    /// Derive the template's PDA from this account in addition to a fixed seed
+    #[clap(value_enum, long)]
+    pub pda_from: Option<PdaSeed>,
+    /// Generate scripts/deploy.sh for deploying the program
+    #[clap(long)]
+    pub with_deploy_script: bool,
+    /// Loader used by the generated deploy script
+    #[clap(
+        value_enum,
+        long,
+        default_value = "upgradeable",
+        requires = "with_deploy_script"
+    )]
+    pub loader: DeployLoader,
+    /// Generate a .anchorversion file so avm switches to the pinned Anchor version
+    #[clap(long)]
+    pub with_anchorversion: bool,
+    /// Generate scripts/loadtest.ts measuring the throughput of the main instruction
+    #[clap(long, conflicts_with = "rust_only")]
+    pub with_loadtest: bool,
+    /// Commitment used by the generated tests when sending and confirming transactions
+    #[clap(value_enum, long)]
+    pub commitment: Option<Commitment>,
+    /// Generate app/client.ts wrapping each instruction in a typed function
+    #[clap(long, conflicts_with = "rust_only")]
+    pub with_client: bool,
+    /// Don't warn when the tests need `seeds = true` but Anchor.toml disables it
+    #[clap(long)]
+    pub disable_seeds_feature_warn: bool,
+    /// Clone the Metaplex metadata program into the local test validator and test on localnet
+    /// (mint-token and nft templates)
+    #[clap(long)]
+    pub preload_metaplex: bool,
+    /// Airdrop SOL to the provider wallet before the tests when running on localnet
+    #[clap(long, conflicts_with = "rust_only")]
+    pub fund_wallet: bool,
+    /// Generate a Makefile with build, test, deploy, localnet and clean targets
+    #[clap(long)]
+    pub with_makefile: bool,
+    /// Embed a security.txt in the program with the `solana-security-txt` crate
+    #[clap(long)]
+    pub with_security_txt: bool,
+    /// Security contact email of the security.txt
+    #[clap(long, requires = "with_security_txt")]
+    pub contact: Option<String>,
+    /// Number of mints managed by the program, each derived from its index (mint-token template)
+    #[clap(long, value_parser = clap::value_parser!(u8).range(2..), conflicts_with_all = ["with_client", "with_loadtest"])]
+    pub mints: Option<u8>,
+    /// Run the mocha test files in parallel
+    #[clap(long, conflicts_with = "rust_only")]
+    pub parallel_tests: bool,
+    /// First code of the custom errors, Anchor numbers them from 6000 by default
+    #[clap(long, value_parser = clap::value_parser!(u32).range(6000..))]
+    pub error_base: Option<u32>,
+    /// Add build, test, deploy and localnet scripts wrapping anchor to package.json
+    #[clap(long, conflicts_with = "rust_only")]
+    pub with_npm_scripts: bool,
+    /// Generate a GitHub Actions workflow running the tests against `--ci-clusters` with the
+    /// selected Solana and Anchor versions
+    #[clap(long, conflicts_with = "rust_only")]
+    pub with_ci: bool,
+    /// Clusters the CI workflow tests against, e.g. `localnet,devnet`
+    #[clap(
+        long,
+        value_delimiter = ',',
+        default_value = "localnet",
+        requires = "with_ci"
+    )]
+    pub ci_clusters: Vec<String>,
+    /// Package manager installing the node modules and running the tests [default: yarn]
+    #[clap(value_enum, long)]
+    pub package_manager: Option<PackageManager>,
+    /// Generate a .cargo/config.toml denying warnings in every build
+    #[clap(long)]
+    pub strict_warnings: bool,
+    /// Amount of tokens minted by the generated test (mint-token template)
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..=rust_template::MAX_MINT_AMOUNT))]
+    pub demo_mint_amount: Option<u64>,
+    /// Record the generated files and the options used in .df-sol/manifest.json
+    #[clap(long)]
+    pub write_manifest: bool,
+    /// Copy the generated program keypair to this file, e.g. to store it in a secret store
+    #[clap(long)]
+    pub export_program_keypair: Option<PathBuf>,
+    /// Also generate a Rust integration test of the program running in LiteSVM
+    #[clap(long)]
+    pub rust_tests: bool,
+    /// Print the files that would be generated with their sizes without writing anything
+    #[clap(long)]
+    pub dry_run: bool,
+    /// Anchor version of the generated crates, npm package and toolchain, e.g. 0.29.0
+    /// [default: 0.30.0]
+    #[clap(long)]
+    pub anchor_version: Option<String>,
+    /// Solana release installed by the generated devbox.json [default: 1.18.16]
+    #[clap(long)]
+    pub solana_version: Option<String>,
+    /// Node.js version of the generated devbox.json [default: 18]
+    #[clap(long)]
+    pub node_version: Option<String>,
+    /// Generate JavaScript tests instead of TypeScript, without a tsconfig.json
+    #[clap(long, conflicts_with_all = ["with_client", "with_loadtest", "with_path_alias"])]
+    pub javascript: bool,
+    /// Generate a justfile with build, test, deploy and lint recipes
+    #[clap(long, conflicts_with = "rust_only")]
+    pub justfile: bool,
+    /// Use this program keypair instead of generating one, to keep the program ID across re-inits
+    #[clap(long)]
+    pub program_keypair: Option<PathBuf>,
+    /// Skip generating devbox.json
+    #[clap(long)]
+    pub no_devbox: bool,
+    /// Generate a GitHub Actions workflow building and testing the workspace on localnet with
+    /// the selected Solana and Anchor versions, `--with-ci` tests against several clusters
+    #[clap(long, conflicts_with_all = ["rust_only", "with_ci"])]
+    pub github_actions: bool,
+    /// Provider cluster of Anchor.toml, one of localnet, devnet, testnet, mainnet-beta or an RPC
+    /// URL [default: devnet for the mint-token and nft templates, localnet otherwise]
+    #[clap(long)]
+    pub cluster: Option<String>,
+    /// Generate a Dockerfile building and testing the workspace with the selected Solana and
+    /// Anchor versions, and its .dockerignore
+    #[clap(long, conflicts_with = "rust_only")]
+    pub dockerfile: bool,
+    /// Render the program from this directory instead of the built-in template. Its files
+    /// may use the {{name}}, {{snake_name}}, {{pascal_name}}, {{program_id}} and
+    /// {{anchor_version}} placeholders, in their contents and paths
+    #[clap(
+        long,
+        conflicts_with_all = ["template", "with_security_txt", "rust_tests", "with_client", "with_loadtest"]
+    )]
+    pub template_dir: Option<PathBuf>,
+    /// Build the workspace with `anchor build` once initialized, to check the templates against
+    /// the installed toolchain
+    #[clap(long, conflicts_with = "dry_run")]
+    pub verify: bool,
+    /// Clusters listing the program in Anchor.toml, e.g. `localnet,devnet` [default: localnet
+    /// and devnet for the mint-token and nft templates, localnet otherwise]
+    #[clap(long, value_delimiter = ',')]
+    pub clusters: Vec<String>,
+    /// Use a release profile building faster, without LTO and with more codegen units, instead
+    /// of the optimized one
+    #[clap(long)]
+    pub fast_build: bool,
+    /// License of the generated package.json, npm's `init-license` when unset
+    #[clap(skip)]
+    pub license: Option<String>,
+}
+
+impl InitOptions {
+    /// Options of `df-sol init <name>` without any flag.
+    pub fn new(name: impl Into<String>) -> Self {
+        // Parsed from a placeholder name, which can't be taken for a flag
+        let command = Self::augment_args(clap::Command::new("init"));
+        let matches = command.get_matches_from(["init", "name"]);
+        let mut opts = Self::from_arg_matches(&matches).expect("Flags have valid defaults");
+        opts.name = name.into();
+        opts
+    }
+}
+
+/// (De)serialize an optional public key as its base58 string.
+mod option_pubkey {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn serialize<S: Serializer>(
+        pubkey: &Option<Pubkey>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        pubkey
+            .map(|pubkey| pubkey.to_string())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Pubkey>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|pubkey| pubkey.parse().map_err(de::Error::custom))
+            .transpose()
+    }
+}
+
+pub fn entry(opts: Opts) -> Result<()> {
+    style::init(opts.color);
+    process_command(opts)
+}
+
+fn process_command(opts: Opts) -> Result<()> {
+    match opts.command {
+        Command::Init { mut opts } => {
+            // Flags left out fall back to df-sol.toml and the user config file
+            let config = Config::load()?;
+            let templates = match config.templates(&opts.template) {
+                Some(templates) => templates
+                    .iter()
+                    .map(|template| resolve_template(template))
+                    .collect::<Result<Vec<_>>>()?,
+                None => vec![default_template()?],
+            };
+            opts.template = templates.iter().map(ToString::to_string).collect();
+            if opts.package_manager.is_none() {
+                if let Some(name) = config.package_manager {
+                    opts.package_manager = Some(
+                        <PackageManager as ValueEnum>::from_str(&name, true).map_err(|e| {
+                            anyhow!("Invalid package manager `{name}` in the config: {e}")
+                        })?,
+                    );
+                }
+            }
+            opts.anchor_version = opts.anchor_version.or(config.anchor_version);
+            opts.solana_version = opts.solana_version.or(config.solana_version);
+            opts.node_version = opts.node_version.or(config.node_version);
+            init_workspace(opts)
+        }
+        Command::Doctor => doctor(),
+        Command::Build { deny_warnings } => build(deny_warnings),
+        Command::Test {
+            skip_deploy,
+            skip_local_validator,
+            cluster,
+        } => test(skip_deploy, skip_local_validator, cluster.as_deref()),
+        Command::Deploy {
+            cluster,
+            program_keypair,
+        } => deploy(cluster, program_keypair.as_deref()),
+        Command::Add {
+            name,
+            template,
+            skip_existing_program_check,
+        } => add(
+            &name,
+            resolve_template(&template)?,
+            skip_existing_program_check,
+        ),
+        Command::NewTemplate { template, out } => new_template(resolve_template(&template)?, &out),
+        Command::Localnet { reset } => localnet(reset),
+        Command::Fmt { check } => fmt(check),
+        Command::Clean { node_modules } => clean(node_modules),
+        Command::Keys { action } => match action {
+            KeysAction::Sync => keys_sync(),
+        },
+        Command::NewAccount {
+            program,
+            name,
+            fields,
+            backup,
+        } => new_account(&program, &name, &fields, backup),
+        Command::MigrateToml { from, to, backup } => migrate_toml(&from, &to, backup),
+        Command::CopyIdl { out_dir } => copy_idl(&out_dir),
+        Command::SetProgramId { name, id } => set_program_id(&name, id),
+        Command::ReadmeUsage { name } => readme_usage(&name),
+        Command::Verify {
+            program_id,
+            cluster,
+        } => verify(&program_id, &cluster),
+        Command::Regenerate { manifest, out_dir } => regenerate(&manifest, out_dir.as_deref()),
+        Command::SnapshotTemplates { out_dir } => snapshot_templates(&out_dir),
+    }
+}
+
+/// Create a new Anchor workspace, as `df-sol init` does.
+pub fn init_workspace(opts: InitOptions) -> Result<()> {
+    // Recorded as resolved, with the picked template and the config defaults applied
+    let manifest_options = opts
+        .write_manifest
+        .then(|| serde_json::to_value(&opts))
+        .transpose()?;
+    let InitOptions {
+        name,
+        no_install,
+        no_git,
+        template: templates,
+        force,
+        mint_recipient,
+        no_wallet,
+        wallet: existing_wallet,
+        force_reinstall,
+        with_path_alias,
+        install_retries,
+        with_vscode,
+        rust_only,
+        anchor_toml_append,
+        show_program_id,
+        write_pubkey,
+        test_cluster,
+        pda_from,
+        with_deploy_script,
+        loader,
+        with_anchorversion,
+        with_loadtest,
+        commitment,
+        with_client,
+        disable_seeds_feature_warn,
+        preload_metaplex,
+        fund_wallet,
+        with_makefile,
+        with_security_txt,
+        contact,
+        mints,
+        parallel_tests,
+        error_base,
+        with_npm_scripts,
+        with_ci,
+        ci_clusters,
+        package_manager,
+        strict_warnings,
+        demo_mint_amount,
+        write_manifest: _,
+        export_program_keypair,
+        rust_tests,
+        dry_run,
+        anchor_version,
+        solana_version,
+        node_version,
+        javascript,
+        justfile,
+        program_keypair,
+        no_devbox,
+        github_actions,
+        cluster,
+        dockerfile,
+        template_dir,
+        verify,
+        clusters,
+        fast_build,
+        license,
+    } = opts;
+    // The first template is the workspace's program, the others are named after their templates
+    let mut templates = templates
+        .iter()
+        .map(|template| template.parse::<ProgramTemplate>())
+        .collect::<Result<Vec<_>>>()?
+        .into_iter();
+    let template = templates.next().unwrap_or_default();
+    let package_manager = package_manager.unwrap_or_default();
+    let anchor_version = anchor_version.unwrap_or_else(|| rust_template::ANCHOR_VERSION.into());
+    let solana_version = solana_version.unwrap_or_else(|| rust_template::SOLANA_VERSION.into());
+    let node_version = node_version.unwrap_or_else(|| rust_template::NODE_VERSION.into());
+
+    // We need to format different cases for the dir and the name
+    let rust_name = name.to_snake_case();
+    let project_name = if name == rust_name {
+        rust_name.clone()
+    } else {
+        name.to_kebab_case()
+    };
+
+    if !is_valid_program_name(&rust_name) {
+        return Err(anyhow!(
+            "Anchor workspace name must be a valid Rust identifier. It may not be a Rust reserved word, start with a digit, or include certain disallowed characters. See https://doc.rust-lang.org/reference/identifiers.html for more detail.",
+        ));
+    }
+
+    // The other programs are named after their templates, e.g. programs/mint-token
+    let mut extra_programs: Vec<(String, ProgramTemplate)> = vec![];
+    for extra_template in templates {
+        let program_name = extra_template.to_string();
+        if program_name.to_snake_case() == rust_name
+            || extra_programs.iter().any(|(name, _)| *name == program_name)
+        {
+            return Err(anyhow!(
+                "Program `{program_name}` is generated twice, each template after the first one generates a program named after it"
+            ));
+        }
+        extra_programs.push((program_name, extra_template));
+    }
+
+    if mint_recipient.is_some() && template != ProgramTemplate::MintToken {
+        return Err(anyhow!(
+            "`--mint-recipient` is only supported by the mint-token template"
+        ));
+    }
+
+    if demo_mint_amount.is_some() && template != ProgramTemplate::MintToken {
+        return Err(anyhow!(
+            "`--demo-mint-amount` is only supported by the mint-token template"
+        ));
+    }
+
+    if mints.is_some() && template != ProgramTemplate::MintToken {
+        return Err(anyhow!(
+            "`--mints` is only supported by the mint-token template"
+        ));
+    }
+
+    // Only these templates define custom errors
+    let has_errors = template == ProgramTemplate::Pausable
+        || template == ProgramTemplate::Vesting
+        || template == ProgramTemplate::Staking
+        || template == ProgramTemplate::Multisig
+        || template == ProgramTemplate::Crud
+        || (template == ProgramTemplate::MintToken && mints.is_some());
+    if error_base.is_some() && !has_errors {
+        return Err(anyhow!(
+            "`--error-base` is only supported by the pausable, vesting, staking, multisig and crud templates and the mint-token template with `--mints`"
+        ));
+    }
+
+    if semver::Version::parse(&anchor_version).is_err() {
+        return Err(anyhow!(
+            "Invalid Anchor version `{anchor_version}`, expected a version like {}",
+            rust_template::ANCHOR_VERSION
+        ));
+    }
+
+    if semver::Version::parse(&solana_version).is_err() {
+        return Err(anyhow!(
+            "Invalid Solana version `{solana_version}`, expected a version like {}",
+            rust_template::SOLANA_VERSION
+        ));
+    }
+
+    if let Some(cluster) = &cluster {
+        let named = ["localnet", "devnet", "testnet", "mainnet-beta"];
+        let is_url = cluster.starts_with("http://") || cluster.starts_with("https://");
+        if !is_url && !named.contains(&cluster.to_lowercase().as_str()) {
+            return Err(anyhow!(
+                "Invalid cluster `{cluster}`, expected one of {} or an http(s) URL",
+                named.join(", ")
+            ));
+        }
+    }
+
+    // Anchor.toml lists programs under these cluster names
+    let program_clusters = ["localnet", "devnet", "testnet", "mainnet"];
+    if let Some(invalid) = clusters
+        .iter()
+        .find(|cluster| !program_clusters.contains(&cluster.as_str()))
+    {
+        return Err(anyhow!(
+            "Invalid cluster `{invalid}` in `--clusters`, expected one of {}",
+            program_clusters.join(", ")
+        ));
+    }
+
+    for warning in compatibility::check(&anchor_version, Some(&solana_version), None) {
+        eprintln!("{} {warning}", style::warning("Warning:"));
+    }
+
+    // Read before changing into the workspace so that relative paths resolve as expected
+    let extra_toml = match &anchor_toml_append {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {e}", path.display()))?,
+        None => String::new(),
+    };
+
+    // Resolve before changing into the workspace directory
+    let write_pubkey = match write_pubkey {
+        Some(path) => Some(std::env::current_dir()?.join(path)),
+        None => None,
+    };
+    let export_program_keypair = match export_program_keypair {
+        Some(path) => Some(std::env::current_dir()?.join(path)),
+        None => None,
+    };
+    let template_dir = match template_dir {
+        Some(dir) if dir.is_dir() => Some(std::env::current_dir()?.join(dir)),
+        Some(dir) => {
+            return Err(anyhow!(
+                "Template directory {} doesn't exist",
+                dir.display()
+            ))
+        }
+        None => None,
+    };
+    let program_keypair = match program_keypair {
+        Some(path) => Some(
+            read_keypair_file(&path)
+                .map_err(|e| anyhow!("Invalid program keypair {}: {e}", path.display()))?,
+        ),
+        None => None,
+    };
+    let existing_wallet = match existing_wallet {
+        Some(path) => Some(
+            read_keypair_file(&path)
+                .map_err(|e| anyhow!("Invalid wallet {}: {e}", path.display()))?,
+        ),
+        None => None,
+    };
+
+    if preload_metaplex
+        && template != ProgramTemplate::MintToken
+        && template != ProgramTemplate::Nft
+    {
+        return Err(anyhow!(
+            "`--preload-metaplex` is only supported by the mint-token and nft templates"
+        ));
+    }
+    // Anchor only starts the validator it clones the Metaplex program into on localnet
+    let cluster = match cluster {
+        None if preload_metaplex => Some("localnet".to_string()),
+        Some(cluster) if preload_metaplex && !cluster.eq_ignore_ascii_case("localnet") => {
+            return Err(anyhow!(
+                "`--preload-metaplex` clones the Metaplex program into the local validator, it can't be used with `--cluster {cluster}`"
+            ));
+        }
+        cluster => cluster,
+    };
+
+    if pda_from.is_some() && template != ProgramTemplate::Counter {
+        return Err(anyhow!(
+            "`--pda-from` is only supported by the counter template"
+        ));
+    }
+
+    if github_actions && with_ci {
+        return Err(anyhow!(
+            "`--github-actions` and `--with-ci` both generate a GitHub Actions workflow, pass only one of them"
+        ));
+    }
+
+    // Checked in dry runs too so that they fail where the actual run would
+    let path = Path::new(&project_name);
+    if path.exists() && !is_empty_dir(path) {
+        if !force {
+            return Err(anyhow!(
+                "`{project_name}` already exists and is not an empty directory. Pass `--force` to initialize it anyway or choose another name"
+            ));
+        }
+        // `--force` reinitializes an existing workspace, never an unrelated directory
+        if !is_anchor_workspace(path) {
+            return Err(anyhow!(
+                "`{project_name}` is not an Anchor workspace, it has no Anchor.toml and programs directory. `--force` only reinitializes existing workspaces"
+            ));
+        }
+    }
+    // Generated from within the workspace, the caller's directory is restored on return
+    let _current_dir = if dry_run {
+        None
+    } else {
+        fs::create_dir_all(&project_name)
+            .map_err(|e| anyhow!("Failed to create {project_name}: {e}"))?;
+        Some(CurrentDir::enter(&project_name)?)
+    };
+
+    let test_script = if parallel_tests {
+        eprintln!(
+            "{} test files run in parallel, tests touching the same on-chain accounts may conflict",
+            style::warning("Warning:")
+        );
+        rust_template::get_parallel_test_script(package_manager, javascript)
+    } else {
+        rust_template::get_test_script(package_manager, javascript)
+    };
+    // `anchor test` exports the provider cluster to the test script, override it for tests only
+    // with the cluster of `[test.provider]`
+    let test_script = match &test_cluster {
+        Some(cluster) => format!(
+            "ANCHOR_PROVIDER_URL={} {test_script}",
+            rust_template::cluster_url(cluster),
+        ),
+        None => test_script,
+    };
+    // A dry run must not write the program keypair, its program ID is only a placeholder
+    let program_id = match &program_keypair {
+        Some(keypair) if dry_run => keypair.pubkey(),
+        // Copied into place before generating the files so they all use its program ID
+        Some(keypair) => {
+            let keypair_dir = Path::new("target").join("deploy");
+            fs::create_dir_all(&keypair_dir)?;
+            let keypair_path = keypair_dir.join(format!("{rust_name}-keypair.json"));
+            solana_sdk::signature::write_keypair_file(keypair, &keypair_path)
+                .map_err(|e| anyhow!("Failed to write {}: {e}", keypair_path.display()))?;
+            keypair.pubkey()
+        }
+        None if dry_run => Keypair::new().pubkey(),
+        None => rust_template::get_or_create_program_id(&rust_name),
+    };
+    let extra_programs = extra_programs
+        .into_iter()
+        .map(|(name, template)| {
+            let program_id = if dry_run {
+                Keypair::new().pubkey()
+            } else {
+                rust_template::get_or_create_program_id(&name)
+            };
+            (name, template, program_id)
+        })
+        .collect::<Vec<_>>();
+    let wallet = if no_wallet {
+        rust_template::DEFAULT_WALLET_PATH
+    } else {
+        rust_template::WALLET_PATH
+    };
+    let mut toml = create_anchor_toml(
+        &rust_name,
+        program_id.to_string(),
+        test_script,
+        wallet,
+        template,
+        cluster.as_deref(),
+        &clusters,
+    );
+    for (name, _, program_id) in &extra_programs {
+        toml = add_anchor_toml_program(&toml, name, program_id)?;
+    }
+    // Every other file targeting a cluster follows the provider cluster
+    let cluster = cluster.as_deref().unwrap_or(template.default_cluster());
+    if let Some(test_cluster) = &test_cluster {
+        toml = merge_toml(&toml, &rust_template::test_provider(test_cluster))?;
+    }
+    if preload_metaplex {
+        toml = merge_toml(&toml, &rust_template::test_validator_clone_metaplex())?;
+    }
+    if anchor_toml_append.is_some() {
+        toml = merge_toml(&toml, &extra_toml)?;
+    }
+
+    // Files of the workspace, overwritten if they already exist
+    let mut files: Files = vec![
+        ("Anchor.toml".into(), toml),
+        (".gitignore".into(), rust_template::git_ignore().into()),
+    ];
+    if !rust_only {
+        files.push((
+            ".prettierignore".into(),
+            rust_template::prettier_ignore().into(),
+        ));
+    }
+    let wallet_keypair = if no_wallet {
+        None
+    } else {
+        // An existing wallet is copied to the same path, so Anchor.toml doesn't change
+        let keypair = existing_wallet.unwrap_or_else(Keypair::new);
+        files.push((
+            rust_template::WALLET_PATH.into(),
+            serialize_keypair(&keypair)?,
+        ));
+        Some(keypair)
+    };
+    files.push(("README.md".into(), rust_template::readme(template)));
+    files.push((
+        ".env.example".into(),
+        rust_template::env_example(cluster, wallet),
+    ));
+    if !no_devbox {
+        files.push((
+            "devbox.json".into(),
+            rust_template::devbox_json(&anchor_version, &solana_version, &node_version),
+        ));
+    }
+
+    if with_anchorversion {
+        files.push((
+            ".anchorversion".into(),
+            rust_template::anchorversion_file(&anchor_version),
+        ));
+    }
+
+    let deploy_script = Path::new("scripts").join("deploy.sh");
+    if with_deploy_script {
+        files.push((
+            deploy_script.clone(),
+            rust_template::deploy_script(&rust_name, loader),
+        ));
+    }
+
+    if with_loadtest {
+        files.push((
+            "scripts/loadtest.ts".into(),
+            rust_template::loadtest_script(&project_name, template),
+        ));
+    }
+
+    if with_client {
+        files.push((
+            "app/client.ts".into(),
+            rust_template::ts_client(&project_name, template),
+        ));
+    }
+
+    if with_makefile {
+        files.push((
+            "Makefile".into(),
+            rust_template::makefile(template, cluster),
+        ));
+    }
+
+    if justfile {
+        files.push((
+            "justfile".into(),
+            rust_template::justfile(template, package_manager, cluster),
+        ));
+    }
+
+    // Fail every cargo build of the workspace on warnings
+    if strict_warnings {
+        files.push((
+            ".cargo/config.toml".into(),
+            rust_template::cargo_config_deny_warnings(),
+        ));
+    }
+
+    if with_ci {
+        files.push((
+            ".github/workflows/test.yml".into(),
+            rust_template::ci_workflow(
+                &ci_clusters,
+                wallet,
+                package_manager,
+                &solana_version,
+                &anchor_version,
+            ),
+        ));
+    }
+
+    if github_actions {
+        files.push((
+            ".github/workflows/ci.yml".into(),
+            rust_template::github_ci(wallet, package_manager, &solana_version, &anchor_version),
+        ));
+    }
+
+    if dockerfile {
+        files.push((
+            "Dockerfile".into(),
+            rust_template::dockerfile(&solana_version, &anchor_version, package_manager),
+        ));
+        files.push((
+            ".dockerignore".into(),
+            rust_template::docker_ignore().into(),
+        ));
+    }
+
+    // Mocha config for parallel runs
+    if parallel_tests {
+        files.push((
+            ".mocharc.json".into(),
+            rust_template::mocharc_parallel(javascript).into(),
+        ));
+    }
+
+    if with_vscode {
+        files.push((
+            ".vscode/settings.json".into(),
+            rust_template::vscode_settings().into(),
+        ));
+        files.push((
+            ".vscode/extensions.json".into(),
+            rust_template::vscode_extensions().into(),
+        ));
+    }
+
+    if !rust_only {
+        let license = match license {
+            Some(license) => license,
+            None => get_npm_init_license()?,
+        };
+        let extension = rust_template::test_extension(javascript);
+        if !javascript {
+            files.push((
+                "tsconfig.json".into(),
+                rust_template::ts_config(&project_name, with_path_alias),
+            ));
+        }
+        // The tests of the other programs may need other dependencies
+        let templates = std::iter::once(template)
+            .chain(extra_programs.iter().map(|(_, template, _)| *template))
+            .collect::<Vec<_>>();
+        let package_json = rust_template::ts_package_json(
+            license,
+            &templates,
+            with_npm_scripts,
+            cluster,
+            &anchor_version,
+            javascript,
+        );
+        files.push(("package.json".into(), package_json));
+        // The deploy script is plain JavaScript either way
+        files.push((
+            Path::new("migrations").join(format!("deploy.{extension}")),
+            rust_template::ts_deploy_script().into(),
+        ));
+        let test = if javascript {
+            rust_template::js_mocha(
+                &project_name,
+                template,
+                mint_recipient,
+                pda_from,
+                mints,
+                demo_mint_amount,
+                commitment,
+            )
+        } else {
+            rust_template::ts_mocha(
+                &project_name,
+                template,
+                mint_recipient,
+                with_path_alias.then_some(rust_template::TYPES_PATH_ALIAS),
+                pda_from,
+                mints,
+                demo_mint_amount,
+                commitment,
+            )
+        };
+        files.push((
+            Path::new("tests").join(format!("{project_name}.{extension}")),
+            test,
+        ));
+        for (name, extra_template, _) in &extra_programs {
+            let test = if javascript {
+                rust_template::js_mocha(name, *extra_template, None, None, None, None, commitment)
+            } else {
+                // The `@program` alias only resolves to the workspace's own program
+                let path_alias = rust_template::program_types_path_alias(name);
+                rust_template::ts_mocha(
+                    name,
+                    *extra_template,
+                    None,
+                    with_path_alias.then_some(path_alias.as_str()),
+                    None,
+                    None,
+                    None,
+                    commitment,
+                )
+            };
+            files.push((Path::new("tests").join(format!("{name}.{extension}")), test));
+        }
+        if fund_wallet {
+            let hook = if javascript {
+                rust_template::js_fund_wallet_hook()
+            } else {
+                rust_template::ts_fund_wallet_hook().into()
+            };
+            files.push((
+                Path::new("tests").join(format!("fund-wallet.{extension}")),
+                hook,
+            ));
+        }
+    }
+
+    // Files of the program, existing ones are kept
+    let mut program_files = rust_template::program_files(
+        &project_name,
+        template,
+        program_id,
+        &anchor_version,
+        pda_from,
+        mints,
+        error_base,
+        fast_build,
+    );
+    if with_security_txt {
+        rust_template::add_security_txt(&mut program_files, &project_name, contact.as_deref())?;
+    }
+    if rust_tests {
+        rust_template::add_rust_tests(&mut program_files, &project_name, template, pda_from)?;
+    }
+    for (name, extra_template, program_id) in &extra_programs {
+        let program_path = Path::new("programs").join(name);
+        // The workspace Cargo.toml is already part of the first program's files
+        let mut extra_files = rust_template::program_files(
+            name,
+            *extra_template,
+            *program_id,
+            &anchor_version,
+            None,
+            None,
+            None,
+            false,
+        );
+        extra_files.retain(|(path, _)| path.starts_with(&program_path));
+        if with_security_txt {
+            rust_template::add_security_txt(&mut extra_files, name, contact.as_deref())?;
+        }
+        if rust_tests {
+            rust_template::add_rust_tests(&mut extra_files, name, *extra_template, None)?;
+        }
+        program_files.extend(extra_files);
+    }
+    // Files of the template directory, written as they are since `create_files` would create the
+    // paths without an extension, e.g. a Makefile, as directories
+    let mut template_files = vec![];
+    let mut binary_files = vec![];
+    if let Some(dir) = &template_dir {
+        // The rendered files replace the built-in program and generated files of the same path
+        let (rendered, binary) =
+            template_dir::render(dir, &project_name, program_id, &anchor_version)?;
+        let program_path = Path::new("programs").join(&project_name);
+        let replaced = |path: &PathBuf| {
+            path.starts_with(&program_path)
+                || rendered.iter().any(|(p, _)| p == path)
+                || binary.iter().any(|(p, _)| p == path)
+        };
+        files.retain(|(path, _)| !replaced(path));
+        program_files.retain(|(path, _)| !replaced(path));
+        template_files = rendered;
+        binary_files = binary;
+    }
+
+    if !disable_seeds_feature_warn {
+        let file = |path: &Path| {
+            files
+                .iter()
+                .chain(&program_files)
+                .chain(&template_files)
+                .find(|(p, _)| p == path)
+                .map(|(_, content)| content.as_str())
+        };
+        let extension = rust_template::test_extension(javascript);
+        let names = std::iter::once(&project_name).chain(extra_programs.iter().map(|(n, ..)| n));
+        for name in names {
+            let program = Path::new("programs").join(name).join("src").join("lib.rs");
+            let test = Path::new("tests").join(format!("{name}.{extension}"));
+            let (Some(anchor_toml), Some(program), Some(test)) =
+                (file(Path::new("Anchor.toml")), file(&program), file(&test))
+            else {
+                continue;
+            };
+            if let Some(warning) = rust_template::seeds_feature_warning(anchor_toml, program, test)
+            {
+                eprintln!("{} {name}: {warning}", style::warning("Warning:"));
+            }
+        }
+    }
+
+    if dry_run {
+        let sizes = files
+            .iter()
+            .chain(&program_files)
+            .chain(&template_files)
+            .map(|(path, content)| (path, content.len()))
+            .chain(
+                binary_files
+                    .iter()
+                    .map(|(path, content)| (path, content.len())),
+            );
+        for (path, size) in sizes {
+            println!(
+                "{} ({size} bytes)",
+                Path::new(&project_name).join(path).display(),
+            );
+        }
+        println!(
+            "{}",
+            style::warning("Dry run, nothing was written, the program ID is a placeholder")
+        );
+        return Ok(());
+    }
+
+    if !rust_only {
+        fs::create_dir_all("app")?;
+    }
+    let mut created = write_files(&files)?;
+    if with_deploy_script {
+        make_executable(&deploy_script)?;
+    }
+
+    if let Some(path) = &write_pubkey {
+        let pubkey = match &wallet_keypair {
+            Some(keypair) => keypair.pubkey(),
+            None => read_keypair_file(rust_template::WALLET_PATH)
+                .map_err(|e| anyhow!("Failed to read {}: {e}", rust_template::WALLET_PATH))?
+                .pubkey(),
+        };
+        fs::write(path, pubkey.to_string())?;
+    }
+
+    // Remove the programs generated again if `--force` is passed, other programs are kept
+    if force {
+        let program_path = Path::new("programs").join(&project_name);
+        if program_path.exists() {
+            fs::remove_dir_all(program_path)?;
+        }
+        for (name, _, _) in &extra_programs {
+            let program_path = Path::new("programs").join(name);
+            if program_path.exists() {
+                fs::remove_dir_all(program_path)?;
+            }
+        }
+    }
+
+    // Build the program.
+    created += create_files(&program_files)?.len();
+    created += write_files(&template_files)?;
+    created += write_files(&binary_files)?;
+    if let Some(path) = &export_program_keypair {
+        let keypair_path = Path::new("target")
+            .join("deploy")
+            .join(format!("{rust_name}-keypair.json"));
+        fs::copy(&keypair_path, path).map_err(|e| {
+            anyhow!(
+                "Failed to export the program keypair to {}: {e}",
+                path.display()
+            )
+        })?;
+        make_private(path)?;
+    }
+
+    if !no_install && !rust_only {
+        if force_reinstall {
+            remove_node_modules()?;
+        }
+
+        let cmd = package_manager.command();
+        let result = install_node_modules_with_retries(cmd, install_retries)?;
+        if !result.status.success() && package_manager == PackageManager::Yarn {
+            println!(
+                "{}",
+                style::warning("Failed yarn install will attempt to npm install")
+            );
+            install_node_modules_with_retries("npm", install_retries)?;
+        }
+    }
+
+    if let Some(options) = manifest_options {
+        write_generation_manifest(template, options)?;
+    }
+
+    if !no_git {
+        // Start on `main` whatever the user's `init.defaultBranch`
+        let initial_branch = git_supports_initial_branch();
+        let git_result = std::process::Command::new("git")
+            .arg("init")
+            .args(initial_branch.then_some("--initial-branch=main"))
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|e| anyhow::format_err!("git init failed: {}", e.to_string()))?;
+        if !git_result.status.success() {
+            eprintln!(
+                "{}",
+                style::error("Failed to automatically initialize a new git repository")
+            );
+        } else {
+            if !initial_branch && !rename_unborn_branch("main")? {
+                eprintln!(
+                    "{} failed to name the initial branch `main`",
+                    style::warning("Warning:")
+                );
+            }
+            if !initial_commit()? {
+                // Usually `user.name` or `user.email` isn't configured, keep the workspace anyway
+                eprintln!(
+                    "{} failed to create the initial commit. Check that git `user.name` and `user.email` are set, then run `git add -A && git commit`",
+                    style::warning("Warning:")
+                );
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        style::success(format!(
+            "{project_name} initialized, {created} files created"
+        ))
+    );
+
+    // Summary of the addresses, so they don't have to be looked up in the generated files
+    println!("Program ID: {}", style::bold(program_id));
+    for (name, _, program_id) in &extra_programs {
+        println!("Program ID of {name}: {}", style::bold(program_id));
+    }
+    match &wallet_keypair {
+        Some(keypair) => println!("Wallet: {} ({wallet})", style::bold(keypair.pubkey())),
+        None => println!("Wallet: {wallet}"),
+    }
+    if let Some(keypair) = wallet_keypair
+        .as_ref()
+        .filter(|_| cluster.eq_ignore_ascii_case("devnet"))
+    {
+        println!(
+            "{} the workspace targets devnet, airdrop some SOL to the wallet with:\n  solana airdrop 2 {} --url devnet",
+            style::warning("Note:"),
+            keypair.pubkey()
+        );
+    }
+
+    if show_program_id {
+        let lib_rs = Path::new("programs")
+            .join(&project_name)
+            .join("src")
+            .join("lib.rs");
+        // A program rendered from a template directory may keep its lib.rs elsewhere
+        if let Some(declared_id) = fs::read_to_string(lib_rs)
+            .ok()
+            .and_then(|lib_rs| declared_program_id(&lib_rs))
+        {
+            println!("declare_id!: {}", style::bold(declared_id));
+        }
+    }
+
+    // Catches a skew between the Anchor version of the templates and the installed toolchain
+    if verify {
+        println!("Building the workspace to verify it...");
+        let status = run_anchor(&["build"], &[])?;
+        if !status.success() {
+            return Err(anyhow!(
+                "`anchor build` of the generated workspace failed ({status}), check that the installed Anchor matches {anchor_version} with `df-sol doctor`"
+            ));
+        }
+        println!("{}", style::success("Workspace built successfully"));
+    }
+
+    Ok(())
+}
+
+/// Directory of the generation manifest, relative to the workspace root
+const MANIFEST_DIR: &str = ".df-sol";
+
+/// Write `.df-sol/manifest.json` listing every generated file of the workspace with its size,
+/// along with the template and the options used.
+fn write_generation_manifest(template: ProgramTemplate, options: serde_json::Value) -> Result<()> {
+    let mut files = vec![];
+    let walker = walkdir::WalkDir::new(".")
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            !matches!(
+                entry.file_name().to_str(),
+                Some("node_modules" | ".git" | MANIFEST_DIR)
+            )
+        });
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path().strip_prefix(".")?;
+        let path = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.push(serde_json::json!({ "path": path, "size": entry.metadata()?.len() }));
+    }
+
+    let manifest = serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "template": template.name(),
+        "options": options,
+        "files": files,
+    });
+    fs::create_dir_all(MANIFEST_DIR)?;
+    fs::write(
+        Path::new(MANIFEST_DIR).join("manifest.json"),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    Ok(())
+}
+
+/// Run `init` again with the options recorded in a generation manifest. The program keypair and
+/// the wallet are new, everything else is generated the same way as the original workspace.
+fn regenerate(manifest_path: &Path, out_dir: Option<&Path>) -> Result<()> {
+    let manifest: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(manifest_path)
+            .map_err(|e| anyhow!("Can't read manifest {}: {e}", manifest_path.display()))?,
+    )?;
+
+    let version = manifest["version"].as_str().unwrap_or_default();
+    if version != VERSION {
+        return Err(anyhow!(
+            "Manifest was written by df-sol {version}, which generates different files than df-sol {VERSION}. Run `cargo install df-sol --version {version}` to regenerate it"
+        ));
+    }
+
+    let options = serde_json::from_value::<InitOptions>(manifest["options"].clone())
+        .map_err(|e| anyhow!("Manifest has no valid `options`: {e}"))?;
+
+    let _current_dir = match out_dir {
+        Some(out_dir) => {
+            fs::create_dir_all(out_dir)?;
+            Some(CurrentDir::enter(out_dir)?)
+        }
+        None => None,
+    };
+    init_workspace(options)
+}
+
+/// Current directory changed by [`CurrentDir::enter`], set back to the previous one when dropped.
+struct CurrentDir(PathBuf);
+
+impl CurrentDir {
+    fn enter(dir: impl AsRef<Path>) -> Result<Self> {
+        let previous = std::env::current_dir()?;
+        std::env::set_current_dir(dir)?;
+        Ok(Self(previous))
+    }
+}
+
+impl Drop for CurrentDir {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.0);
+    }
+}
+
+fn doctor() -> Result<()> {
+    let anchor = installed_version("anchor");
+    let solana = installed_version("solana");
+    let rust = installed_version("rustc");
+
+    for (tool, version) in [("anchor", &anchor), ("solana", &solana), ("rustc", &rust)] {
+        match version {
+            Some(version) => println!("{tool}: {}", style::bold(version)),
+            None => println!("{tool}: {}", style::error("not found")),
+        }
+    }
+
+    // Generated workspaces pin Anchor, so check against that when the CLI isn't installed
+    let anchor = anchor.as_deref().unwrap_or(rust_template::ANCHOR_VERSION);
+    let warnings = compatibility::check(anchor, solana.as_deref(), rust.as_deref());
+    if warnings.is_empty() {
+        println!("{}", style::success("No known incompatibilities found"));
+    }
+    for warning in warnings {
+        println!("{} {warning}", style::warning("Warning:"));
+    }
+
+    Ok(())
+}
+
+fn build(deny_warnings: bool) -> Result<()> {
+    let mut envs = vec![];
+    if deny_warnings {
+        let rustflags = std::env::var("RUSTFLAGS").unwrap_or_default();
+        envs.push((
+            "RUSTFLAGS",
+            format!("{rustflags} -D warnings").trim_start().to_string(),
+        ));
+    }
+
+    let status = run_anchor(&["build"], &envs)?;
+    if !status.success() {
+        return Err(anyhow!("`anchor build` failed"));
+    }
+
+    Ok(())
+}
+
+fn test(skip_deploy: bool, skip_local_validator: bool, cluster: Option<&str>) -> Result<()> {
+    let mut args = vec!["test"];
+    if skip_deploy {
+        args.push("--skip-deploy");
+    }
+    if skip_local_validator {
+        args.push("--skip-local-validator");
+    }
+    if let Some(cluster) = cluster {
+        args.extend(["--provider.cluster", cluster]);
+    }
+
+    let status = run_anchor(&args, &[])?;
+    if !status.success() {
+        // Exit with the code of `anchor test` so that scripts and CI see the same result
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
+
+fn deploy(cluster: Option<String>, program_keypair: Option<&Path>) -> Result<()> {
+    ensure_workspace()?;
+    let anchor_toml = fs::read_to_string("Anchor.toml")?.parse::<toml::Table>()?;
+    let provider = |key: &str| {
+        anchor_toml
+            .get("provider")
+            .and_then(|provider| provider.get(key))
+            .and_then(|value| value.as_str())
+            .map(ToString::to_string)
+    };
+    let cluster = cluster
+        .or_else(|| provider("cluster"))
+        .ok_or_else(|| anyhow!("No cluster given and no `[provider] cluster` in Anchor.toml"))?;
+
+    if cluster.eq_ignore_ascii_case("devnet") {
+        let wallet = provider("wallet").unwrap_or_else(|| rust_template::WALLET_PATH.into());
+        println!(
+            "{} deploying to devnet needs 2-3 SOL in the wallet. Airdrop some with:\n  solana airdrop 2 $(solana address --keypair {wallet}) --url devnet",
+            style::warning("Note:")
+        );
+    }
+
+    let program_keypair = program_keypair.map(|path| path.display().to_string());
+    let mut args = vec!["deploy", "--provider.cluster", &cluster];
+    if let Some(program_keypair) = &program_keypair {
+        args.extend(["--program-keypair", program_keypair]);
+    }
+
+    let status = run_anchor(&args, &[])?;
+    if !status.success() {
+        return Err(anyhow!("`anchor deploy` failed"));
+    }
+
+    Ok(())
+}
+
+fn add(name: &str, template: ProgramTemplate, skip_existing_program_check: bool) -> Result<()> {
+    ensure_workspace()?;
+
+    // Same names as `init` uses for the program directory and the Anchor.toml key
+    let rust_name = name.to_snake_case();
+    let program_name = if name == rust_name {
+        rust_name.clone()
+    } else {
+        name.to_kebab_case()
+    };
+    if !is_valid_program_name(&rust_name) {
+        return Err(anyhow!(
+            "Program name must be a valid Rust identifier. It may not be a Rust reserved word, start with a digit, or include certain disallowed characters. See https://doc.rust-lang.org/reference/identifiers.html for more detail.",
+        ));
+    }
+    let program_path = Path::new("programs").join(&program_name);
+    if program_path.exists() && !skip_existing_program_check {
+        return Err(anyhow!(
+            "{} already exists. Choose another name, or pass --skip-existing-program-check to keep its files and add the missing ones",
+            program_path.display()
+        ));
+    }
+
+    // The new program depends on the Anchor version the workspace is pinned to
+    let anchor_version =
+        workspace_anchor_version()?.unwrap_or_else(|| rust_template::ANCHOR_VERSION.into());
+    rust_template::create_program(&program_name, template, &anchor_version, None, None, None)?;
+    let program_id = rust_template::get_or_create_program_id(&program_name);
+
+    let mut anchor_toml = fs::read_to_string("Anchor.toml")?.parse::<toml::Table>()?;
+    let programs = anchor_toml
+        .entry("programs")
+        .or_insert_with(|| toml::Table::new().into())
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("`programs` in Anchor.toml is not a table"))?;
+    programs
+        .entry("localnet")
+        .or_insert_with(|| toml::Table::new().into())
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("`programs.localnet` in Anchor.toml is not a table"))?
+        .insert(rust_name, program_id.to_string().into());
+    fs::write("Anchor.toml", toml::to_string(&anchor_toml)?)?;
+
+    // Workspaces created with `--rust-only` have no tests, those created with `--javascript` have
+    // a package.json but no tsconfig.json
+    if let Ok(ts_config) = fs::read_to_string("tsconfig.json") {
+        // `@program` resolves to the workspace's own program, the added one is imported through
+        // `@program/<name>` when tsconfig.json maps it and relatively otherwise
+        let path_alias = ts_config
+            .contains(rust_template::PROGRAM_TYPES_PATH_ALIAS)
+            .then(|| rust_template::program_types_path_alias(&program_name));
+        rust_template::create_test_files(
+            &program_name,
+            template,
+            None,
+            path_alias.as_deref(),
+            None,
+            None,
+            None,
+            None,
+            false,
+        )?;
+    } else if Path::new("package.json").exists() {
+        rust_template::create_test_files(
+            &program_name,
+            template,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            true,
+        )?;
+    }
+
+    println!(
+        "{}",
+        style::success(format!("Added program {program_name} ({program_id})"))
+    );
+
+    Ok(())
+}
+
+fn localnet(reset: bool) -> Result<()> {
+    ensure_workspace()?;
+
+    let anchor_toml = fs::read_to_string("Anchor.toml")?.parse::<toml::Table>()?;
+    let programs = anchor_toml
+        .get("programs")
+        .and_then(|programs| programs.get("localnet"))
+        .and_then(|programs| programs.as_table())
+        .ok_or_else(|| anyhow!("No `[programs.localnet]` in Anchor.toml"))?;
+
+    let mut args = vec![];
+    for (name, id) in programs {
+        let Some(id) = id.as_str() else {
+            continue;
+        };
+        let binary = Path::new("target")
+            .join("deploy")
+            .join(format!("{name}.so"));
+        if !binary.exists() {
+            eprintln!(
+                "{} {} not found, run `anchor build` to load {name}",
+                style::warning("Warning:"),
+                binary.display()
+            );
+            continue;
+        }
+        args.extend([
+            "--bpf-program".to_string(),
+            id.to_string(),
+            binary.display().to_string(),
+        ]);
+    }
+
+    // The programs of the mint-token and nft templates call Token Metadata, cloned from devnet
+    if uses_token_metadata()? {
+        args.extend([
+            "--url".to_string(),
+            "devnet".to_string(),
+            "--clone-upgradeable-program".to_string(),
+            rust_template::TOKEN_METADATA_PROGRAM_ID.to_string(),
+        ]);
+    }
+    if reset {
+        args.push("--reset".to_string());
+    }
+
+    let status = std::process::Command::new("solana-test-validator")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| anyhow!("Failed to run `solana-test-validator`: {e}"))?;
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
+
+/// Whether a program of the workspace enables the `metadata` feature of anchor-spl.
+fn uses_token_metadata() -> Result<bool> {
+    for entry in fs::read_dir("programs")? {
+        let cargo_toml = entry?.path().join("Cargo.toml");
+        let Ok(manifest) = fs::read_to_string(&cargo_toml) else {
+            continue;
+        };
+        let manifest = manifest.parse::<toml::Table>()?;
+        let features = manifest
+            .get("dependencies")
+            .and_then(|dependencies| dependencies.get("anchor-spl"))
+            .and_then(|anchor_spl| anchor_spl.get("features"))
+            .and_then(|features| features.as_array());
+        if features.is_some_and(|features| features.iter().any(|f| f.as_str() == Some("metadata")))
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+fn fmt(check: bool) -> Result<()> {
+    ensure_workspace()?;
+
+    let mut cargo_args = vec!["fmt", "--all"];
+    if check {
+        cargo_args.extend(["--", "--check"]);
+    }
+    let mut commands = vec![("cargo".to_string(), cargo_args)];
+    // The lint scripts run prettier, which reads the .prettierignore of the workspace
+    if Path::new("package.json").exists() {
+        let script = if check { "lint" } else { "lint:fix" };
+        let package_manager = detect_package_manager().command();
+        commands.push((package_manager.to_string(), vec!["run", script]));
+    }
+
+    // Run every formatter even if one fails so that all the issues are reported at once
+    let mut failed = vec![];
+    for (program, args) in &commands {
+        let command = format!("{program} {}", args.join(" "));
+        let status = std::process::Command::new(program)
+            .args(args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .map_err(|e| anyhow!("Failed to run `{command}`: {e}"))?;
+        if !status.success() {
+            failed.push(format!("`{command}`"));
+        }
+    }
+    if !failed.is_empty() {
+        return Err(anyhow!("{} failed", failed.join(" and ")));
+    }
+
+    Ok(())
+}
+
+/// Package manager of the workspace, guessed from its lockfile and yarn if there is none.
+fn detect_package_manager() -> PackageManager {
+    if Path::new("pnpm-lock.yaml").exists() {
+        PackageManager::Pnpm
+    } else if Path::new("bun.lockb").exists() || Path::new("bun.lock").exists() {
+        PackageManager::Bun
+    } else if Path::new("package-lock.json").exists() {
+        PackageManager::Npm
+    } else {
+        PackageManager::Yarn
+    }
+}
+
+fn clean(node_modules: bool) -> Result<()> {
+    ensure_workspace()?;
+
+    let mut removed = vec![];
+    // Like `anchor clean`, keep the program keypairs so that the program IDs don't change
+    let target = Path::new("target");
+    if target.exists() {
+        for entry in fs::read_dir(target)? {
+            let path = entry?.path();
+            if path.file_name() == Some("deploy".as_ref()) {
+                for entry in fs::read_dir(&path)? {
+                    let path = entry?.path();
+                    let is_keypair = path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.ends_with("-keypair.json"));
+                    if !is_keypair {
+                        remove_path(&path)?;
+                        removed.push(path);
+                    }
+                }
+            } else {
+                remove_path(&path)?;
+                removed.push(path);
+            }
+        }
+    }
+
+    let mut dirs = vec![".anchor", "test-ledger"];
+    if node_modules {
+        dirs.push("node_modules");
+    }
+    for dir in dirs {
+        let path = PathBuf::from(dir);
+        if path.exists() {
+            remove_path(&path)?;
+            removed.push(path);
+        }
+    }
+
+    removed.sort();
+    for path in &removed {
+        println!("{}", style::success(format!("Removed {}", path.display())));
+    }
+    if removed.is_empty() {
+        println!("Nothing to clean");
+    }
+
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+/// Check that the current directory is the root of an Anchor workspace.
+fn ensure_workspace() -> Result<()> {
+    if !Path::new("Anchor.toml").exists() {
+        return Err(anyhow!(
+            "No Anchor.toml found, run this command in the root of an Anchor workspace"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether Anchor accepts the snake case name as a program name.
+fn is_valid_program_name(rust_name: &str) -> bool {
+    // Additional keywords that have not been added to the `syn` crate as reserved words
+    // https://github.com/dtolnay/syn/pull/1098
+    let extra_keywords = ["async", "await", "try"];
+    // Anchor converts to snake case before writing the program name
+    syn::parse_str::<syn::Ident>(rust_name).is_ok() && !extra_keywords.contains(&rust_name)
+}
+
+/// Run an `anchor` subcommand in the workspace with its output shown to the user.
+fn run_anchor(args: &[&str], envs: &[(&str, String)]) -> Result<std::process::ExitStatus> {
+    ensure_workspace()?;
+
+    std::process::Command::new("anchor")
+        .args(args)
+        .envs(envs.iter().map(|(key, value)| (key, value)))
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| anyhow!("Failed to run `anchor {}`: {e}", args.join(" ")))
+}
+
+fn new_account(program: &str, name: &str, fields: &[String], backup: bool) -> Result<()> {
+    let lib_rs = program_lib_rs(program)?;
+    let source = fs::read_to_string(&lib_rs)?;
+
+    let name = name.to_pascal_case();
+    let file = syn::parse_file(&source)?;
+    let exists = file
+        .items
+        .iter()
+        .any(|item| matches!(item, syn::Item::Struct(item) if item.ident == name));
+    if exists {
+        return Err(anyhow!("`{name}` already exists in {}", lib_rs.display()));
+    }
+
+    let account = account_struct(&name, fields)?;
+    override_or_create_files(
+        &vec![(lib_rs.clone(), format!("{source}\n{account}"))],
+        backup,
+    )?;
+
+    println!(
+        "{}",
+        style::success(format!("Added account `{name}` to {}", lib_rs.display()))
+    );
+
+    Ok(())
+}
+
+fn migrate_toml(from: &str, to: &str, backup: bool) -> Result<()> {
+    let path = Path::new("Anchor.toml");
+    if !path.exists() {
+        return Err(anyhow!(
+            "Anchor.toml not found, run this from the workspace root"
+        ));
+    }
+
+    let toml = migrate_anchor_toml(&fs::read_to_string(path)?, from, to)?;
+    override_or_create_files(&vec![(path.into(), toml)], backup)?;
+    println!(
+        "{}",
+        style::success(format!("Migrated Anchor.toml from {from} to {to}"))
+    );
+
+    Ok(())
+}
+
+/// Rewrite an Anchor.toml written for Anchor `from` to the format expected by Anchor `to`.
+/// Program IDs, scripts and any other sections are preserved.
+pub fn migrate_anchor_toml(toml: &str, from: &str, to: &str) -> Result<String> {
+    let parse = |version: &str| {
+        compatibility::parse_version(version)
+            .ok_or_else(|| anyhow!("Invalid Anchor version `{version}`"))
+    };
+    let (from, to) = (parse(from)?, parse(to)?);
+    if (from.major, from.minor, to.major, to.minor) != (0, 29, 0, 30) {
+        return Err(anyhow!(
+            "Unsupported migration from {from} to {to}, only 0.29 to 0.30 is supported"
+        ));
+    }
+
+    let mut table = toml.parse::<toml::Table>()?;
+
+    // 0.30 expects a `[toolchain]` table at the top of the file
+    if !table.contains_key("toolchain") {
+        let mut migrated = toml::Table::new();
+        migrated.insert("toolchain".into(), toml::Value::Table(toml::Table::new()));
+        migrated.extend(table);
+        table = migrated;
+    }
+
+    // The `seeds` feature was replaced by `resolution`, which is enabled by default
+    if let Some(toml::Value::Table(features)) = table.get_mut("features") {
+        if let Some(seeds) = features.remove("seeds") {
+            features.insert("resolution".into(), seeds);
+        }
+    }
+
+    Ok(toml::to_string(&table)?)
+}
+
+fn copy_idl(out_dir: &Path) -> Result<()> {
+    let mut files = vec![];
+    for (dir, extension) in [("idl", "json"), ("types", "ts")] {
+        let dir = Path::new("target").join(dir);
+        if !dir.exists() {
+            return Err(anyhow!(
+                "{} not found, run `anchor build` first",
+                dir.display()
+            ));
+        }
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == extension) {
+                files.push(path);
+            }
+        }
+    }
+    if files.is_empty() {
+        return Err(anyhow!("No IDL files found, run `anchor build` first"));
+    }
+
+    fs::create_dir_all(out_dir)?;
+    for path in files {
+        let dest = out_dir.join(path.file_name().unwrap());
+        fs::copy(&path, &dest)?;
+        println!(
+            "{}",
+            style::success(format!("Copied {} to {}", path.display(), dest.display()))
+        );
+    }
+
+    Ok(())
+}
+
+fn set_program_id(name: &str, id: Pubkey) -> Result<()> {
+    let lib_rs = program_lib_rs(name)?;
+    let source = fs::read_to_string(&lib_rs)?;
+    if declared_program_id(&source).is_none() {
+        return Err(anyhow!("No `declare_id!` found in {}", lib_rs.display()));
+    }
+    let source = replace_declared_program_id(&source, &id);
+
+    let anchor_toml = Path::new("Anchor.toml");
+    let toml = set_anchor_toml_program_id(&fs::read_to_string(anchor_toml)?, name, &id)?;
+
+    override_or_create_files(&vec![(lib_rs, source), (anchor_toml.into(), toml)], false)?;
+    println!(
+        "{}",
+        style::success(format!("Set the program ID of {name} to {id}"))
+    );
+
+    Ok(())
+}
+
+/// Replace the program ID of the `declare_id!` in a program's source.
+fn replace_declared_program_id(source: &str, id: &Pubkey) -> String {
+    let re = Regex::new(r#"declare_id!\("[1-9A-HJ-NP-Za-km-z]+"\)"#).unwrap();
+    re.replace(source, format!(r#"declare_id!("{id}")"#))
+        .into_owned()
+}
+
+fn keys_sync() -> Result<()> {
+    ensure_workspace()?;
+
+    let anchor_toml_path = Path::new("Anchor.toml");
+    let mut anchor_toml = fs::read_to_string(anchor_toml_path)?;
+    let mut files = vec![];
+    let mut programs = fs::read_dir("programs")?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<_>>>()?;
+    programs.sort();
+    for program_path in programs {
+        let lib_rs = program_path.join("src").join("lib.rs");
+        let Some(name) = program_path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !lib_rs.exists() {
+            continue;
+        }
+
+        let keypair_path = Path::new("target")
+            .join("deploy")
+            .join(format!("{}-keypair.json", name.to_snake_case()));
+        let id = match read_keypair_file(&keypair_path) {
+            Ok(keypair) => keypair.pubkey(),
+            Err(_) => {
+                eprintln!(
+                    "{} no keypair for {name} at {}, skipping it",
+                    style::warning("Warning:"),
+                    keypair_path.display()
+                );
+                continue;
+            }
+        };
+
+        let source = fs::read_to_string(&lib_rs)?;
+        if declared_program_id(&source).is_none() {
+            return Err(anyhow!("No `declare_id!` found in {}", lib_rs.display()));
+        }
+        let synced = replace_declared_program_id(&source, &id);
+        let toml = match set_anchor_toml_program_id(&anchor_toml, name, &id) {
+            Ok(toml) => toml,
+            Err(e) => {
+                eprintln!("{} {e}", style::warning("Warning:"));
+                anchor_toml.clone()
+            }
+        };
+        if synced == source && toml.parse::<toml::Table>()? == anchor_toml.parse::<toml::Table>()? {
+            println!("{name}: {id}");
+            continue;
+        }
+
+        println!("{}", style::success(format!("{name}: synced to {id}")));
+        files.push((lib_rs, synced));
+        anchor_toml = toml;
+    }
+
+    if !files.is_empty() {
+        files.push((anchor_toml_path.into(), anchor_toml));
+        override_or_create_files(&files, false)?;
+    }
+
+    Ok(())
+}
+
+/// Anchor version the programs of the workspace depend on, read from the `anchor-lang`
+/// dependency of the first one that has it.
+fn workspace_anchor_version() -> Result<Option<String>> {
+    let Ok(entries) = fs::read_dir("programs") else {
+        return Ok(None);
+    };
+    let mut manifests = entries
+        .map(|entry| Ok(entry?.path().join("Cargo.toml")))
+        .collect::<Result<Vec<_>>>()?;
+    manifests.sort();
+    for manifest in manifests {
+        let Ok(content) = fs::read_to_string(&manifest) else {
+            continue;
+        };
+        let manifest = content
+            .parse::<toml::Table>()
+            .map_err(|e| anyhow!("Invalid {}: {e}", manifest.display()))?;
+        let anchor_lang = manifest
+            .get("dependencies")
+            .and_then(|dependencies| dependencies.get("anchor-lang"));
+        // Either `anchor-lang = "0.30.0"` or `anchor-lang = { version = "0.30.0", ... }`
+        let version = match anchor_lang {
+            Some(toml::Value::String(version)) => Some(version.as_str()),
+            Some(toml::Value::Table(dependency)) => dependency
+                .get("version")
+                .and_then(|version| version.as_str()),
+            _ => None,
+        };
+        if let Some(version) = version {
+            return Ok(Some(version.to_string()));
+        }
+    }
+
+    Ok(None)
 }
 
-pub fn entry(opts: Opts) -> Result<()> {
-    let result = process_command(opts);
+/// Add a program to every `[programs.<cluster>]` table of an Anchor.toml.
+fn add_anchor_toml_program(toml: &str, name: &str, id: &Pubkey) -> Result<String> {
+    let mut table = toml.parse::<toml::Table>()?;
+    if let Some(toml::Value::Table(programs)) = table.get_mut("programs") {
+        for (_, cluster) in programs.iter_mut() {
+            if let Some(cluster) = cluster.as_table_mut() {
+                cluster.insert(name.to_snake_case(), id.to_string().into());
+            }
+        }
+    }
 
-    result
+    Ok(toml::to_string(&table)?)
 }
 
-fn process_command(opts: Opts) -> Result<()> {
-    match opts.command {
-        Command::Init {
-            name,
-            no_install,
-            no_git,
-            template,
-            force,
-        } => init(name, no_install, no_git, template, force),
+/// Set the ID of the given program in every `[programs.<cluster>]` table of an Anchor.toml.
+pub fn set_anchor_toml_program_id(toml: &str, name: &str, id: &Pubkey) -> Result<String> {
+    let mut table = toml.parse::<toml::Table>()?;
+    let key = name.to_snake_case();
+
+    let mut updated = false;
+    if let Some(toml::Value::Table(programs)) = table.get_mut("programs") {
+        for (_, cluster) in programs.iter_mut() {
+            if let Some(program_id) = cluster.as_table_mut().and_then(|c| c.get_mut(&key)) {
+                *program_id = toml::Value::String(id.to_string());
+                updated = true;
+            }
+        }
+    }
+    if !updated {
+        return Err(anyhow!("Program `{key}` not found in Anchor.toml"));
     }
+
+    Ok(toml::to_string(&table)?)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn init(
-    name: String,
-    no_install: bool,
-    no_git: bool,
-    template: ProgramTemplate,
-    force: bool,
-) -> Result<()> {
-    // We need to format different cases for the dir and the name
-    let rust_name = name.to_snake_case();
-    let project_name = if name == rust_name {
-        rust_name.clone()
-    } else {
-        name.to_kebab_case()
+fn readme_usage(name: &str) -> Result<()> {
+    let idl_path = Path::new("target")
+        .join("idl")
+        .join(format!("{}.json", name.to_snake_case()));
+    if !idl_path.exists() {
+        return Err(anyhow!(
+            "{} not found, run `anchor build` first",
+            idl_path.display()
+        ));
+    }
+    let idl = serde_json::from_str(&fs::read_to_string(&idl_path)?)?;
+    let usage = idl_usage(&idl)?;
+
+    let readme = Path::new("README.md");
+    let content = match fs::read_to_string(readme) {
+        Ok(content) => format!("{}\n\n{usage}", content.trim_end()),
+        Err(_) => usage,
     };
+    fs::write(readme, content)?;
+    println!(
+        "{}",
+        style::success(format!("Added the {name} usage to README.md"))
+    );
 
-    // Additional keywords that have not been added to the `syn` crate as reserved words
-    // https://github.com/dtolnay/syn/pull/1098
-    let extra_keywords = ["async", "await", "try"];
-    // Anchor converts to snake case before writing the program name
-    if syn::parse_str::<syn::Ident>(&rust_name).is_err()
-        || extra_keywords.contains(&rust_name.as_str())
-    {
+    Ok(())
+}
+
+/// Generate a README "Usage" section listing the instructions of an IDL with their
+/// arguments and accounts.
+pub fn idl_usage(idl: &serde_json::Value) -> Result<String> {
+    let instructions = idl["instructions"]
+        .as_array()
+        .ok_or_else(|| anyhow!("The IDL has no instructions"))?;
+
+    let mut usage = String::from("## Usage\n");
+    for ix in instructions {
+        usage.push_str(&format!("\n### `{}`\n", ix["name"].as_str().unwrap_or("?")));
+
+        let args = ix["args"].as_array().cloned().unwrap_or_default();
+        if !args.is_empty() {
+            usage.push_str("\n**Arguments**\n\n");
+            for arg in &args {
+                usage.push_str(&format!(
+                    "- `{}`: `{}`\n",
+                    arg["name"].as_str().unwrap_or("?"),
+                    idl_type(&arg["type"])
+                ));
+            }
+        }
+
+        let accounts = ix["accounts"].as_array().cloned().unwrap_or_default();
+        if !accounts.is_empty() {
+            usage.push_str("\n**Accounts**\n\n");
+            idl_accounts(&accounts, "", &mut usage);
+        }
+    }
+
+    Ok(usage)
+}
+
+fn idl_accounts(accounts: &[serde_json::Value], indent: &str, usage: &mut String) {
+    for account in accounts {
+        let name = account["name"].as_str().unwrap_or("?");
+        // Composite accounts list their own accounts
+        if let Some(nested) = account["accounts"].as_array() {
+            usage.push_str(&format!("{indent}- `{name}`\n"));
+            idl_accounts(nested, &format!("{indent}  "), usage);
+            continue;
+        }
+
+        let flags = [("writable", "writable"), ("signer", "signer")]
+            .iter()
+            .filter(|(key, _)| account[key].as_bool().unwrap_or(false))
+            .map(|(_, flag)| *flag)
+            .collect::<Vec<_>>();
+        if flags.is_empty() {
+            usage.push_str(&format!("{indent}- `{name}`\n"));
+        } else {
+            usage.push_str(&format!("{indent}- `{name}` ({})\n", flags.join(", ")));
+        }
+    }
+}
+
+/// Format an IDL type the way it is written in Rust.
+fn idl_type(ty: &serde_json::Value) -> String {
+    if let Some(ty) = ty.as_str() {
+        return ty.to_string();
+    }
+    if let Some(defined) = ty.get("defined") {
+        return defined["name"]
+            .as_str()
+            .or_else(|| defined.as_str())
+            .unwrap_or("?")
+            .to_string();
+    }
+    if let Some(inner) = ty.get("vec") {
+        return format!("Vec<{}>", idl_type(inner));
+    }
+    if let Some(inner) = ty.get("option") {
+        return format!("Option<{}>", idl_type(inner));
+    }
+    if let Some(array) = ty.get("array") {
+        return format!("[{}; {}]", idl_type(&array[0]), array[1]);
+    }
+    ty.to_string()
+}
+
+fn verify(program_id: &Pubkey, cluster: &str) -> Result<()> {
+    let deploy_dir = Path::new("target").join("deploy");
+    verify_program(&deploy_dir, program_id, cluster, dump_program)
+}
+
+/// Compare the program fetched from `cluster` by `fetch` with its build in `deploy_dir`.
+fn verify_program(
+    deploy_dir: &Path,
+    program_id: &Pubkey,
+    cluster: &str,
+    fetch: impl FnOnce(&Pubkey, &str) -> Result<Vec<u8>>,
+) -> Result<()> {
+    let local_path = local_program_binary(deploy_dir, program_id)?;
+    let local = fs::read(&local_path)?;
+    let deployed = fetch(program_id, cluster)?;
+
+    let (deployed_hash, local_hash) = (program_hash(&deployed), program_hash(&local));
+    if deployed_hash != local_hash {
         return Err(anyhow!(
-            "Anchor workspace name must be a valid Rust identifier. It may not be a Rust reserved word, start with a digit, or include certain disallowed characters. See https://doc.rust-lang.org/reference/identifiers.html for more detail.",
+            "{program_id} does not match {}\n  deployed: {deployed_hash}\n  local:    {local_hash}",
+            local_path.display()
         ));
     }
+    println!(
+        "{}",
+        style::success(format!(
+            "{program_id} matches {} ({local_hash})",
+            local_path.display()
+        ))
+    );
 
-    if force {
-        fs::create_dir_all(&project_name)?;
-    } else {
-        fs::create_dir(&project_name)?;
+    Ok(())
+}
+
+/// Fetch a deployed program with `solana program dump`, which strips the upgradeable loader's
+/// program data header.
+fn dump_program(program_id: &Pubkey, cluster: &str) -> Result<Vec<u8>> {
+    let dump_path = std::env::temp_dir().join(format!("{program_id}.so"));
+    let output = std::process::Command::new("solana")
+        .args(["program", "dump", "--url"])
+        .arg(rust_template::cluster_url(cluster))
+        .arg(program_id.to_string())
+        .arg(&dump_path)
+        .output()
+        .map_err(|e| anyhow!("Failed to run `solana program dump`: {e}"))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Failed to fetch {program_id}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
     }
-    std::env::set_current_dir(&project_name)?;
-    fs::create_dir_all("app")?;
+    let deployed = fs::read(&dump_path)?;
+    let _ = fs::remove_file(&dump_path);
 
-    let test_script = rust_template::get_test_script();
-    let program_id = rust_template::get_or_create_program_id(&rust_name);
-    let toml = create_anchor_toml(program_id.to_string(), test_script.to_string(), template);
-    fs::write("Anchor.toml", toml)?;
+    Ok(deployed)
+}
 
-    // Initialize .gitignore file
-    fs::write(".gitignore", rust_template::git_ignore())?;
+/// Hash of a program binary, ignoring the zero padding of program data accounts
+/// deployed with extra space (`--max-len`).
+pub fn program_hash(program: &[u8]) -> solana_sdk::hash::Hash {
+    let len = program
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+    solana_sdk::hash::hash(&program[..len])
+}
 
-    // Initialize .prettierignore file
-    fs::write(".prettierignore", rust_template::prettier_ignore())?;
+/// Find the built binary in `deploy_dir` whose program keypair has the given ID.
+fn local_program_binary(deploy_dir: &Path, program_id: &Pubkey) -> Result<PathBuf> {
+    if deploy_dir.exists() {
+        for entry in fs::read_dir(deploy_dir)? {
+            let path = entry?.path();
+            let Some(name) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix("-keypair.json"))
+            else {
+                continue;
+            };
+            let matches = read_keypair_file(&path).is_ok_and(|kp| kp.pubkey() == *program_id);
+            let binary = deploy_dir.join(format!("{name}.so"));
+            if matches && binary.exists() {
+                return Ok(binary);
+            }
+        }
+    }
 
-    // Initialize wallet.json
-    fs::write("wallet.json", create_keypair())?;
+    Err(anyhow!(
+        "No build of {program_id} found in {}, run `anchor build` first",
+        deploy_dir.display()
+    ))
+}
 
-    // Initialize README.md
-    fs::write("README.md", rust_template::readme(template))?;
+/// Write the files of a built-in template with their placeholders to `out`.
+fn new_template(template: ProgramTemplate, out: &Path) -> Result<()> {
+    if out.exists() && !is_empty_dir(out) {
+        return Err(anyhow!("{} already exists and is not empty", out.display()));
+    }
 
-    // Initialize devbox.json
-    fs::write("devbox.json", rust_template::devbox_json())?;
+    let files = template_dir::from_built_in(template)
+        .into_iter()
+        .map(|(path, content)| (out.join(path), content))
+        .collect::<Files>();
+    create_files(&files)?;
 
-    // Remove the default program if `--force` is passed
-    if force {
-        fs::remove_dir_all(
-            std::env::current_dir()?
-                .join("programs")
-                .join(&project_name),
-        )?;
-    }
+    println!(
+        "{}",
+        style::success(format!(
+            "{template} template written to {}, use it with `df-sol init <name> --template-dir {}`",
+            out.display(),
+            out.display()
+        ))
+    );
+    Ok(())
+}
 
-    // Build the program.
-    rust_template::create_program(&project_name, template)?;
+/// Name of the program generated in template snapshots
+const SNAPSHOT_NAME: &str = "snapshot";
 
-    // Build the migrations directory.
-    fs::create_dir_all("migrations")?;
+fn snapshot_templates(out_dir: &Path) -> Result<()> {
+    fs::create_dir_all(out_dir)?;
+    let out_dir = out_dir.canonicalize()?;
+    let _current_dir = CurrentDir::enter(&out_dir)?;
 
-    let license = get_npm_init_license()?;
+    // A fixed program keypair keeps the generated program IDs stable across runs
+    let program_keypair = out_dir.join(format!(".{SNAPSHOT_NAME}-keypair.json"));
+    solana_sdk::signature::write_keypair_file(
+        &solana_sdk::signer::keypair::keypair_from_seed(&[0; 32]).map_err(|e| anyhow!("{e}"))?,
+        &program_keypair,
+    )
+    .map_err(|e| anyhow!("{e}"))?;
 
-    // Build typescript config
-    let mut ts_config = File::create("tsconfig.json")?;
-    ts_config.write_all(rust_template::ts_config().as_bytes())?;
+    for template in ProgramTemplate::value_variants() {
+        let dir = out_dir.join(template.name());
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+
+        init_workspace(InitOptions {
+            template: vec![template.to_string()],
+            no_install: true,
+            no_git: true,
+            program_keypair: Some(program_keypair.clone()),
+            license: Some("MIT".into()),
+            ..InitOptions::new(SNAPSHOT_NAME)
+        })?;
+
+        // Only keep the generated template files, without the wallet and program keypairs
+        let workspace = out_dir.join(SNAPSHOT_NAME);
+        fs::remove_file(workspace.join(rust_template::WALLET_PATH))?;
+        fs::remove_dir_all(workspace.join("target"))?;
+        fs::remove_dir(workspace.join("app"))?;
+        fs::rename(&workspace, &dir)?;
+        println!(
+            "{}",
+            style::success(format!(
+                "Wrote {} snapshot to {}",
+                template.name(),
+                dir.display()
+            ))
+        );
+    }
+    fs::remove_file(program_keypair)?;
 
-    let mut ts_package_json = File::create("package.json")?;
-    ts_package_json.write_all(rust_template::ts_package_json(license, template).as_bytes())?;
+    Ok(())
+}
 
-    let mut deploy = File::create("migrations/deploy.ts")?;
-    deploy.write_all(rust_template::ts_deploy_script().as_bytes())?;
+/// Find the `lib.rs` of the given program of the workspace.
+fn program_lib_rs(program: &str) -> Result<PathBuf> {
+    [
+        program.to_string(),
+        program.to_kebab_case(),
+        program.to_snake_case(),
+    ]
+    .iter()
+    .map(|dir| Path::new("programs").join(dir).join("src").join("lib.rs"))
+    .find(|path| path.exists())
+    .ok_or_else(|| anyhow!("Program `{program}` not found in the programs directory"))
+}
 
-    rust_template::create_test_files(&project_name, template)?;
+/// Generate an `#[account]` struct from `name:type[:max_len]` field specs.
+fn account_struct(name: &str, fields: &[String]) -> Result<String> {
+    let mut body = String::new();
+    for field in fields {
+        let mut parts = field.splitn(3, ':');
+        let (Some(field_name), Some(ty)) = (parts.next(), parts.next()) else {
+            return Err(anyhow!("Invalid field `{field}`, expected `name:type`"));
+        };
+        let field_name = field_name.to_snake_case();
+        let ty = match ty {
+            "pubkey" | "Pubkey" => "Pubkey".to_string(),
+            "string" | "String" => "String".to_string(),
+            ty => ty.to_string(),
+        };
 
-    if !no_install {
-        let yarn_result = install_node_modules("yarn")?;
-        if !yarn_result.status.success() {
-            println!("Failed yarn install will attempt to npm install");
-            install_node_modules("npm")?;
+        let needs_max_len = ty == "String" || ty.starts_with("Vec<");
+        match parts.next() {
+            Some(max_len) if needs_max_len => {
+                let max_len: usize = max_len
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid max length in `{field}`"))?;
+                body.push_str(&format!("    #[max_len({max_len})]\n"));
+            }
+            Some(_) => {
+                return Err(anyhow!(
+                    "Only strings and vectors take a max length: `{field}`"
+                ))
+            }
+            None if needs_max_len => {
+                return Err(anyhow!(
+                    "`{field}` needs a max length, e.g. `{field_name}:{ty}:32`"
+                ))
+            }
+            None => {}
         }
+        body.push_str(&format!("    pub {field_name}: {ty},\n"));
     }
 
-    if !no_git {
-        let git_result = std::process::Command::new("git")
-            .arg("init")
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map_err(|e| anyhow::format_err!("git init failed: {}", e.to_string()))?;
-        if !git_result.status.success() {
-            eprintln!("Failed to automatically initialize a new git repository");
+    let account = format!("#[account]\n#[derive(InitSpace)]\npub struct {name} {{\n{body}}}\n");
+    syn::parse_str::<syn::ItemStruct>(&account)
+        .map_err(|e| anyhow!("Invalid account `{name}`: {e}"))?;
+
+    Ok(account)
+}
+
+/// Get the version reported by `<cmd> --version`, e.g. `1.18.16` from `solana-cli 1.18.16 (...)`.
+fn installed_version(cmd: &str) -> Option<String> {
+    let output = std::process::Command::new(cmd)
+        .arg("--version")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()?
+        .split_whitespace()
+        .nth(1)
+        .map(ToString::to_string)
+}
+
+/// Extract the program ID from the `declare_id!` macro of a program's source.
+fn declared_program_id(source: &str) -> Option<String> {
+    let re = Regex::new(r#"declare_id!\("([1-9A-HJ-NP-Za-km-z]+)"\)"#).ok()?;
+    re.captures(source).map(|captures| captures[1].to_string())
+}
+
+/// Parse the `--template` value, falling back to an interactive menu when it is unknown.
+fn resolve_template(template: &str) -> Result<ProgramTemplate> {
+    match template.parse() {
+        Ok(template) => Ok(template),
+        Err(e) if !std::io::stdin().is_terminal() => Err(e),
+        Err(_) => {
+            println!("Unknown template `{template}`.");
+            rust_template::prompt_template(std::io::stdin().lock(), std::io::stdout())
         }
     }
+}
+
+/// Template of a bare `df-sol init`, picked by the user in a terminal and basic otherwise so that
+/// scripts and CI are never prompted.
+fn default_template() -> Result<ProgramTemplate> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(ProgramTemplate::default());
+    }
+    pick_template()
+}
+
+#[cfg(feature = "interactive")]
+fn pick_template() -> Result<ProgramTemplate> {
+    let templates = ProgramTemplate::value_variants();
+    let items = templates
+        .iter()
+        .map(|template| format!("{:<12} {}", template.name(), template.description()))
+        .collect::<Vec<_>>();
+    let selection = dialoguer::Select::new()
+        .with_prompt("Template")
+        .items(&items)
+        .default(0)
+        .interact()?;
+    Ok(templates[selection])
+}
+
+#[cfg(not(feature = "interactive"))]
+fn pick_template() -> Result<ProgramTemplate> {
+    rust_template::prompt_template(std::io::stdin().lock(), std::io::stdout())
+}
 
-    println!("{project_name} initialized");
+/// Deep-merge the tables of `extra` into `base`, failing if both set a key to different values.
+pub fn merge_toml(base: &str, extra: &str) -> Result<String> {
+    let mut base = base.parse::<toml::Table>()?;
+    let extra = extra.parse::<toml::Table>()?;
+    merge_toml_tables(&mut base, extra, "")?;
+
+    Ok(toml::to_string(&base)?)
+}
+
+fn merge_toml_tables(base: &mut toml::Table, extra: toml::Table, path: &str) -> Result<()> {
+    for (key, value) in extra {
+        let key_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+
+        match (base.get_mut(&key), value) {
+            (None, value) => {
+                base.insert(key, value);
+            }
+            (Some(toml::Value::Table(base)), toml::Value::Table(extra)) => {
+                merge_toml_tables(base, extra, &key_path)?;
+            }
+            (Some(existing), value) if *existing == value => {}
+            (Some(_), _) => {
+                return Err(anyhow!(
+                    "Conflicting value for `{key_path}` in the appended Anchor.toml"
+                ))
+            }
+        }
+    }
 
     Ok(())
 }
@@ -169,43 +2552,88 @@ fn init(
 /// Array of (path, content) tuple.
 pub type Files = Vec<(PathBuf, String)>;
 
-/// Create files from the given (path, content) tuple array.
+/// Create files from the given (path, content) tuple array and return the paths of the files
+/// created. Existing paths are skipped, paths without an extension are created as directories.
 ///
 /// # Example
 ///
 /// ```ignore
 /// crate_files(vec![("programs/my_program/src/lib.rs".into(), "// Content".into())])?;
 /// ```
-pub fn create_files(files: &Files) -> Result<()> {
+pub fn create_files(files: &Files) -> Result<Vec<PathBuf>> {
+    // Create the directories first, then write the files concurrently
+    let mut seen = HashSet::new();
+    let mut writes = vec![];
     for (path, content) in files {
-        let path = Path::new(path);
-        if path.exists() {
+        if path.exists() || !seen.insert(path) {
             continue;
         }
 
         match path.extension() {
             Some(_) => {
                 fs::create_dir_all(path.parent().unwrap())?;
-                fs::write(path, content)?;
+                writes.push((path, content));
             }
             None => fs::create_dir_all(path)?,
         }
     }
 
-    Ok(())
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = writes.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let writers = writes
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .try_for_each(|(path, content)| fs::write(path, content))
+                })
+            })
+            .collect::<Vec<_>>();
+        writers
+            .into_iter()
+            .try_for_each(|writer| writer.join().expect("Unable to write files"))
+    })?;
+
+    Ok(writes.into_iter().map(|(path, _)| path.clone()).collect())
+}
+
+/// Write the files of the given (path, content) tuple array along with their parent directories,
+/// overwriting existing ones, and return how many didn't exist.
+fn write_files<C: AsRef<[u8]>>(files: &[(PathBuf, C)]) -> Result<usize> {
+    let mut created = 0;
+    for (path, content) in files {
+        if !path.exists() {
+            created += 1;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+
+    Ok(created)
 }
 
-/// Override or create files from the given (path, content) tuple array.
+/// Override or create files from the given (path, content) tuple array. With `backup`, existing
+/// files are copied to `<path>.bak` before being overwritten.
 ///
 /// # Example
 ///
 /// ```ignore
-/// override_or_create_files(vec![("programs/my_program/src/lib.rs".into(), "// Content".into())])?;
+/// override_or_create_files(vec![("programs/my_program/src/lib.rs".into(), "// Content".into())], false)?;
 /// ```
-pub fn override_or_create_files(files: &Files) -> Result<()> {
+pub fn override_or_create_files(files: &Files, backup: bool) -> Result<()> {
     for (path, content) in files {
         let path = Path::new(path);
         if path.exists() {
+            if backup {
+                let mut backup_path = path.as_os_str().to_owned();
+                backup_path.push(".bak");
+                fs::copy(path, backup_path)?;
+            }
+
             let mut f = fs::OpenOptions::new()
                 .write(true)
                 .truncate(true)
@@ -239,30 +2667,503 @@ fn install_node_modules(cmd: &str) -> Result<std::process::Output> {
     }
 }
 
+/// Install node modules, retrying failed installs up to `attempts` times with a short backoff.
+fn install_node_modules_with_retries(cmd: &str, attempts: u32) -> Result<std::process::Output> {
+    let mut attempt = 1;
+    loop {
+        let output = install_node_modules(cmd)?;
+        if output.status.success() || attempt >= attempts {
+            return Ok(output);
+        }
+
+        println!(
+            "{}",
+            style::warning(format!(
+                "{cmd} install failed, retrying ({attempt}/{attempts})"
+            ))
+        );
+        std::thread::sleep(Duration::from_secs(2u64.pow(attempt - 1)));
+        attempt += 1;
+    }
+}
+
+/// Remove `node_modules` and any lockfiles so that the next install starts from scratch.
+fn remove_node_modules() -> Result<()> {
+    if Path::new("node_modules").exists() {
+        fs::remove_dir_all("node_modules")?;
+    }
+    for lockfile in [
+        "yarn.lock",
+        "package-lock.json",
+        "pnpm-lock.yaml",
+        "bun.lockb",
+        "bun.lock",
+    ] {
+        if Path::new(lockfile).exists() {
+            fs::remove_file(lockfile)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(path, permissions)?;
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Make a file containing a secret key readable by its owner only.
+#[cfg(unix)]
+fn make_private(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_private(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
 /// Get the system's default license - what 'npm init' would use.
 fn get_npm_init_license() -> Result<String> {
-    let npm_init_license_output = std::process::Command::new("npm")
+    npm_init_license("npm")
+}
+
+/// License npm would use, read with `npm`. Falls back to npm's default when npm is missing or
+/// fails, e.g. for users with only pnpm or bun installed.
+fn npm_init_license(npm: &str) -> Result<String> {
+    const DEFAULT_LICENSE: &str = "ISC";
+
+    let output = match std::process::Command::new(npm)
         .arg("config")
         .arg("get")
         .arg("init-license")
-        .output()?;
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(_) | Err(_) => {
+            eprintln!(
+                "{} failed to get the npm init license, using {DEFAULT_LICENSE}",
+                style::warning("Warning:")
+            );
+            return Ok(DEFAULT_LICENSE.to_string());
+        }
+    };
+
+    let license = String::from_utf8(output.stdout)?;
+    match license.trim() {
+        "" => Ok(DEFAULT_LICENSE.to_string()),
+        license => Ok(license.to_string()),
+    }
+}
+
+/// Whether the installed git supports `git init --initial-branch`, added in git 2.28.
+fn git_supports_initial_branch() -> bool {
+    let Ok(output) = std::process::Command::new("git").arg("--version").output() else {
+        return false;
+    };
+    // e.g. `git version 2.39.5` or `git version 2.39.1.windows.1`
+    let version = String::from_utf8_lossy(&output.stdout);
+    let mut parts = version
+        .split_whitespace()
+        .nth(2)
+        .unwrap_or_default()
+        .split('.')
+        .map(str::parse::<u64>);
+    matches!(
+        (parts.next(), parts.next()),
+        (Some(Ok(major)), Some(Ok(minor))) if (major, minor) >= (2, 28)
+    )
+}
+
+/// Point HEAD of a repository without commits to `branch`, returning whether git succeeded.
+fn rename_unborn_branch(branch: &str) -> Result<bool> {
+    let status = std::process::Command::new("git")
+        .args(["symbolic-ref", "HEAD", &format!("refs/heads/{branch}")])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| anyhow!("git symbolic-ref failed: {e}"))?;
+    Ok(status.success())
+}
 
-    if !npm_init_license_output.status.success() {
-        return Err(anyhow!("Failed to get npm init license"));
+/// Commit the whole workspace, returning whether git succeeded.
+fn initial_commit() -> Result<bool> {
+    for args in [
+        &["add", "-A"][..],
+        &["commit", "-m", "Initial commit from df-sol"],
+    ] {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .map_err(|e| anyhow!("git {} failed: {e}", args[0]))?;
+        if !status.success() {
+            return Ok(false);
+        }
     }
 
-    let license = String::from_utf8(npm_init_license_output.stdout)?;
-    Ok(license.trim().to_string())
+    Ok(true)
+}
+
+/// Whether the directory looks like an Anchor workspace, with an Anchor.toml and programs.
+fn is_anchor_workspace(path: &Path) -> bool {
+    path.join("Anchor.toml").is_file() && path.join("programs").is_dir()
+}
+
+fn is_empty_dir(path: impl AsRef<Path>) -> bool {
+    fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_none())
 }
 
-fn create_keypair() -> String {
-    let keypair = Keypair::new();
-    let keypair_bytes = keypair.to_bytes();
-    // Convert keypair to base58 strings
-    let serialized = serde_json::to_string(&keypair_bytes.to_vec());
+/// JSON encoding of a keypair, the byte array format of the Solana CLI.
+fn serialize_keypair(keypair: &Keypair) -> Result<String> {
+    serde_json::to_string(&keypair.to_bytes().to_vec())
+        .map_err(|e| anyhow!("Failed to serialize the keypair: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_toml_merges_nested_tables() {
+        let merged = merge_toml(
+            "[programs.localnet]\ncounter = \"A\"\n",
+            "[programs.localnet]\nvault = \"B\"\n\n[test]\nstartup_wait = 5000\n",
+        )
+        .unwrap();
+        let merged = merged.parse::<toml::Table>().unwrap();
+
+        assert_eq!(
+            merged["programs"]["localnet"]["counter"].as_str(),
+            Some("A")
+        );
+        assert_eq!(merged["programs"]["localnet"]["vault"].as_str(), Some("B"));
+        assert_eq!(merged["test"]["startup_wait"].as_integer(), Some(5000));
+    }
+
+    #[test]
+    fn merge_toml_accepts_equal_values_and_rejects_conflicts() {
+        let base = "[provider]\ncluster = \"Localnet\"\n";
+        assert!(merge_toml(base, base).is_ok());
+
+        let err = merge_toml(base, "[provider]\ncluster = \"Devnet\"\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Conflicting value for `provider.cluster` in the appended Anchor.toml"
+        );
+    }
+
+    #[test]
+    fn migrate_anchor_toml_from_0_29_to_0_30() {
+        let toml = "[features]\nseeds = true\n\n[programs.localnet]\ncounter = \"A\"\n";
+        let migrated = migrate_anchor_toml(toml, "0.29.0", "v0.30.1").unwrap();
+
+        assert!(migrated.starts_with("[toolchain]\n"));
+        let migrated = migrated.parse::<toml::Table>().unwrap();
+        assert_eq!(migrated["features"]["resolution"].as_bool(), Some(true));
+        assert!(migrated["features"].get("seeds").is_none());
+        assert_eq!(
+            migrated["programs"]["localnet"]["counter"].as_str(),
+            Some("A")
+        );
+    }
+
+    #[test]
+    fn migrate_anchor_toml_rejects_other_versions() {
+        assert!(migrate_anchor_toml("", "0.28.0", "0.30.0").is_err());
+        assert!(migrate_anchor_toml("", "latest", "0.30.0").is_err());
+    }
+
+    #[test]
+    fn idl_usage_lists_arguments_and_accounts() {
+        let idl = serde_json::json!({
+            "instructions": [{
+                "name": "deposit",
+                "args": [
+                    { "name": "amount", "type": "u64" },
+                    { "name": "memo", "type": { "option": { "vec": "u8" } } },
+                ],
+                "accounts": [
+                    { "name": "vault", "writable": true },
+                    { "name": "user", "writable": true, "signer": true },
+                    { "name": "common", "accounts": [{ "name": "system_program" }] },
+                ],
+            }],
+        });
+
+        assert_eq!(
+            idl_usage(&idl).unwrap(),
+            "## Usage\n\n### `deposit`\n\n**Arguments**\n\n- `amount`: `u64`\n- `memo`: `Option<Vec<u8>>`\n\n**Accounts**\n\n- `vault` (writable)\n- `user` (writable, signer)\n- `common`\n  - `system_program`\n"
+        );
+        assert!(idl_usage(&serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn account_struct_from_field_specs() {
+        let fields = ["owner:pubkey".to_string(), "title:string:32".to_string()];
+        assert_eq!(
+            account_struct("Entry", &fields).unwrap(),
+            "#[account]\n#[derive(InitSpace)]\npub struct Entry {\n    pub owner: Pubkey,\n    #[max_len(32)]\n    pub title: String,\n}\n"
+        );
+    }
+
+    #[test]
+    fn account_struct_rejects_invalid_fields() {
+        let invalid = |field: &str| account_struct("Entry", &[field.to_string()]).is_err();
+        assert!(invalid("owner"));
+        assert!(invalid("title:string"));
+        assert!(invalid("count:u64:8"));
+        assert!(invalid("title:string:long"));
+        assert!(invalid("count:not a type"));
+    }
+
+    #[test]
+    fn program_hash_ignores_trailing_zero_padding() {
+        let program = [1, 2, 0, 3];
+        let mut padded = program.to_vec();
+        padded.resize(64, 0);
+
+        assert_eq!(program_hash(&padded), program_hash(&program));
+        assert_ne!(program_hash(&[1, 2, 0]), program_hash(&program));
+    }
+
+    /// target/deploy with a build of a new program, returning its ID.
+    fn deploy_dir_with_program(deploy_dir: &Path, program: &[u8]) -> Pubkey {
+        let keypair = Keypair::new();
+        solana_sdk::signature::write_keypair_file(
+            &keypair,
+            deploy_dir.join("my_program-keypair.json"),
+        )
+        .unwrap();
+        fs::write(deploy_dir.join("my_program.so"), program).unwrap();
+        keypair.pubkey()
+    }
+
+    #[test]
+    fn verify_accepts_the_deployed_build_with_its_padding() {
+        let deploy_dir = tempfile::tempdir().unwrap();
+        let program_id = deploy_dir_with_program(deploy_dir.path(), &[1, 2, 3]);
+
+        let fetch = |id: &Pubkey, cluster: &str| {
+            assert_eq!((*id, cluster), (program_id, "devnet"));
+            Ok(vec![1, 2, 3, 0, 0, 0])
+        };
+        verify_program(deploy_dir.path(), &program_id, "devnet", fetch).unwrap();
+    }
+
+    #[test]
+    fn verify_rejects_another_deployed_build() {
+        let deploy_dir = tempfile::tempdir().unwrap();
+        let program_id = deploy_dir_with_program(deploy_dir.path(), &[1, 2, 3]);
+
+        let fetch = |_: &Pubkey, _: &str| Ok(vec![1, 2, 4]);
+        let error = verify_program(deploy_dir.path(), &program_id, "devnet", fetch).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with(&format!("{program_id} does not match")),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn set_anchor_toml_program_id_in_every_cluster() {
+        let id = Pubkey::new_unique();
+        let toml = "[programs.localnet]\nmy_program = \"A\"\nother = \"B\"\n\n[programs.devnet]\nmy_program = \"A\"\n";
+        let updated = set_anchor_toml_program_id(toml, "my-program", &id)
+            .unwrap()
+            .parse::<toml::Table>()
+            .unwrap();
+
+        let id = id.to_string();
+        assert_eq!(
+            updated["programs"]["localnet"]["my_program"].as_str(),
+            Some(id.as_str())
+        );
+        assert_eq!(
+            updated["programs"]["devnet"]["my_program"].as_str(),
+            Some(id.as_str())
+        );
+        assert_eq!(updated["programs"]["localnet"]["other"].as_str(), Some("B"));
+    }
+
+    #[test]
+    fn replace_declared_program_id_keeps_the_rest_of_the_source() {
+        let id = Pubkey::new_unique();
+        let source =
+            "use anchor_lang::prelude::*;\n\ndeclare_id!(\"11111111111111111111111111111111\");\n";
+
+        let replaced = replace_declared_program_id(source, &id);
+        assert_eq!(
+            replaced,
+            format!("use anchor_lang::prelude::*;\n\ndeclare_id!(\"{id}\");\n")
+        );
+        assert_eq!(declared_program_id(&replaced), Some(id.to_string()));
+    }
+
+    #[test]
+    fn set_anchor_toml_program_id_of_a_missing_program() {
+        let err =
+            set_anchor_toml_program_id("[programs.localnet]\n", "missing", &Pubkey::default())
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Program `missing` not found in Anchor.toml"
+        );
+    }
+
+    #[test]
+    fn init_options_round_trip_through_the_manifest() {
+        let recipient = Pubkey::new_unique();
+        let options = InitOptions {
+            template: vec!["token-2022".into(), "counter".into()],
+            mint_recipient: Some(recipient),
+            package_manager: Some(PackageManager::Pnpm),
+            pda_from: Some(PdaSeed::User),
+            ..InitOptions::new("my-program")
+        };
+
+        let value = serde_json::to_value(&options).unwrap();
+        assert_eq!(
+            value["template"],
+            serde_json::json!(["token-2022", "counter"])
+        );
+        assert_eq!(value["mint_recipient"], recipient.to_string());
+        assert_eq!(value["package_manager"], "pnpm");
+
+        let parsed = serde_json::from_value::<InitOptions>(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+    }
+
+    #[test]
+    fn init_options_default_to_the_flag_defaults() {
+        let options = InitOptions::new("--my-program");
+        assert_eq!(options.name, "--my-program");
+        assert!(options.template.is_empty());
+        assert_eq!(options.install_retries, 1);
+        assert_eq!(options.loader, DeployLoader::Upgradeable);
+        assert_eq!(options.ci_clusters, ["localnet"]);
+        assert_eq!(options.anchor_version, None);
+    }
+
+    #[test]
+    fn npm_init_license_without_npm() {
+        assert_eq!(npm_init_license("df-sol-missing-bin").unwrap(), "ISC");
+    }
+
+    #[test]
+    fn create_files_writes_hundreds_of_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("programs/existing.rs");
+        fs::create_dir_all(existing.parent().unwrap()).unwrap();
+        fs::write(&existing, "// Kept").unwrap();
+
+        let mut files = (0..300)
+            .map(|i| {
+                let path = dir
+                    .path()
+                    .join(format!("programs/p{}/src/file{i}.rs", i % 7));
+                (path, format!("// File {i}\n").repeat(i + 1))
+            })
+            .collect::<Files>();
+        // Later duplicates and existing files are skipped
+        files.push((files[0].0.clone(), "// Duplicate".into()));
+        files.push((existing.clone(), "// Overwritten".into()));
+
+        let created = create_files(&files).unwrap();
+        assert_eq!(created.len(), 300);
+        for (i, (path, content)) in files.iter().take(300).enumerate() {
+            assert_eq!(created[i], *path);
+            assert_eq!(fs::read_to_string(path).unwrap(), *content);
+        }
+        assert_eq!(fs::read_to_string(existing).unwrap(), "// Kept");
+    }
+
+    #[test]
+    fn create_files_creates_paths_without_an_extension_as_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Files = vec![
+            (dir.path().join("programs/my-program/src"), String::new()),
+            (
+                dir.path().join("programs/my-program/src/lib.rs"),
+                "// Program".into(),
+            ),
+        ];
+
+        let created = create_files(&files).unwrap();
+        assert_eq!(created, [dir.path().join("programs/my-program/src/lib.rs")]);
+        assert!(dir.path().join("programs/my-program/src").is_dir());
+    }
+
+    #[test]
+    fn write_files_writes_paths_without_an_extension_as_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let makefile = dir.path().join("Makefile");
+        let files = vec![
+            (makefile.clone(), "build:\n"),
+            (dir.path().join(".cargo/config.toml"), "[build]\n"),
+        ];
+        assert_eq!(write_files(&files).unwrap(), 2);
+        assert_eq!(fs::read_to_string(&makefile).unwrap(), "build:\n");
+
+        // Existing files are overwritten and not counted
+        assert_eq!(write_files(&[(makefile.clone(), "test:\n")]).unwrap(), 0);
+        assert_eq!(fs::read_to_string(&makefile).unwrap(), "test:\n");
+    }
+
+    #[test]
+    fn override_or_create_files_backs_up_overwritten_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib_rs = dir.path().join("lib.rs");
+        let anchor_toml = dir.path().join("Anchor.toml");
+        fs::write(&lib_rs, "// Old").unwrap();
+        let files = vec![
+            (lib_rs.clone(), "// New".to_string()),
+            (anchor_toml.clone(), "[provider]".to_string()),
+        ];
+
+        override_or_create_files(&files, true).unwrap();
+        assert_eq!(fs::read_to_string(&lib_rs).unwrap(), "// New");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("lib.rs.bak")).unwrap(),
+            "// Old"
+        );
+        assert!(!dir.path().join("Anchor.toml.bak").exists());
+
+        fs::remove_file(dir.path().join("lib.rs.bak")).unwrap();
+        override_or_create_files(&files, false).unwrap();
+        assert!(!dir.path().join("lib.rs.bak").exists());
+    }
+
+    #[test]
+    fn serialized_keypair_round_trips() {
+        let keypair = Keypair::new();
+        let json = serialize_keypair(&keypair).unwrap();
+
+        let bytes = serde_json::from_str::<Vec<u8>>(&json).unwrap();
+        let parsed = Keypair::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.to_bytes(), keypair.to_bytes());
 
-    match serialized {
-        Ok(v) => return v,
-        Err(_e) => return "".parse().unwrap(),
+        // Readable by the Solana CLI tools, as wallet.json is
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wallet.json");
+        fs::write(&path, json).unwrap();
+        assert_eq!(read_keypair_file(&path).unwrap().pubkey(), keypair.pubkey());
     }
 }
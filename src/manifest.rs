@@ -0,0 +1,130 @@
+//! TOML files generated in the workspace. They are built as typed structs and serialized with
+//! the `toml` crate, so the output is always valid and consistently formatted.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Anchor.toml
+#[derive(Debug, Serialize)]
+pub struct AnchorToml {
+    pub toolchain: Toolchain,
+    pub features: AnchorFeatures,
+    /// Program IDs by cluster and program name
+    pub programs: BTreeMap<String, BTreeMap<String, String>>,
+    pub registry: Registry,
+    pub provider: Provider,
+    pub scripts: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Toolchain {}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AnchorFeatures {
+    pub seeds: bool,
+    pub skip_lint: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Registry {
+    pub url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Provider {
+    pub cluster: String,
+    pub wallet: String,
+}
+
+/// Cargo.toml at the root of the workspace
+#[derive(Debug, Serialize)]
+pub struct WorkspaceManifest {
+    pub workspace: Workspace,
+    pub profile: Profiles,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Workspace {
+    pub members: Vec<String>,
+    pub resolver: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Profiles {
+    pub release: ReleaseProfile,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReleaseProfile {
+    pub overflow_checks: bool,
+    pub lto: Lto,
+    pub codegen_units: u32,
+    pub build_override: BuildOverride,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Lto {
+    Enabled(bool),
+    Mode(String),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BuildOverride {
+    pub opt_level: u32,
+    pub incremental: bool,
+    pub codegen_units: u32,
+}
+
+/// Cargo.toml of a program
+#[derive(Debug, Serialize)]
+pub struct ProgramManifest {
+    pub package: Package,
+    pub lib: Lib,
+    pub features: BTreeMap<String, Vec<String>>,
+    pub dependencies: BTreeMap<String, Dependency>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub edition: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Lib {
+    pub crate_type: Vec<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Dependency {
+    Version(String),
+    Detailed {
+        version: String,
+        features: Vec<String>,
+    },
+}
+
+/// .cargo/config.toml
+#[derive(Debug, Serialize)]
+pub struct CargoConfig {
+    pub build: BuildConfig,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BuildConfig {
+    pub rustflags: Vec<String>,
+}
+
+/// Serialize a generated TOML file.
+pub fn to_toml(value: &impl Serialize) -> String {
+    toml::to_string(value).expect("Generated TOML files are always serializable")
+}
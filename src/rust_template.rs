@@ -1,50 +1,464 @@
+use crate::manifest::{
+    to_toml, AnchorFeatures, AnchorToml, BuildConfig, BuildOverride, CargoConfig, Dependency, Lib,
+    Lto, Package, Profiles, ProgramManifest, Provider, Registry, ReleaseProfile, Toolchain,
+    Workspace, WorkspaceManifest,
+};
 use crate::{create_files, Files};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::{Parser, ValueEnum};
-use heck::{ToPascalCase, ToSnakeCase};
+use fs2::FileExt;
+use heck::{ToLowerCamelCase, ToPascalCase, ToSnakeCase};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use solana_sdk::{
     pubkey::Pubkey,
     signature::{read_keypair_file, write_keypair_file, Keypair},
     signer::Signer,
 };
+use std::collections::BTreeSet;
+use std::fmt;
 use std::fs::File;
-use std::io::Write;
-use std::{fs, path::Path};
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const ANCHOR_VERSION: &str = "0.30.0";
+
+/// Solana release installed by the generated devbox.json
+pub const SOLANA_VERSION: &str = "1.18.16";
+
+/// Node.js version of the generated devbox.json
+pub const NODE_VERSION: &str = "18";
+
+/// tsconfig.json path alias for the program's generated types
+pub const TYPES_PATH_ALIAS: &str = "@program";
+
+/// tsconfig.json path alias for the generated types of any program of the workspace, imported
+/// as `@program/<name>`
+pub const PROGRAM_TYPES_PATH_ALIAS: &str = "@program/*";
+
+/// Wallet generated alongside the workspace
+pub const WALLET_PATH: &str = "wallet.json";
 
-const ANCHOR_VERSION: &str = "0.30.0";
+/// Default keypair path of the Solana CLI, used when the wallet is provided externally
+pub const DEFAULT_WALLET_PATH: &str = "~/.config/solana/id.json";
 
-/// Program initialization template
+/// Program initialization template. The names are stable, see the `FromStr` and `Display`
+/// implementations.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Parser, ValueEnum, Copy)]
 pub enum ProgramTemplate {
     /// Program with a basic template
     #[default]
+    #[clap(name = "basic")]
     Basic,
     /// Program with a counter template
+    #[clap(name = "counter")]
     Counter,
     /// Program with a mint token template
+    #[clap(name = "mint-token")]
     MintToken,
+    /// Program with a pausable template
+    #[clap(name = "pausable")]
+    Pausable,
+    /// Program with a PDA signer template
+    #[clap(name = "pda-signer")]
+    PdaSigner,
+    /// Program with a two-party token escrow template
+    #[clap(name = "escrow")]
+    Escrow,
+    /// Program with a linear token vesting template
+    #[clap(name = "vesting")]
+    Vesting,
+    /// Program minting a single NFT with Token Metadata template
+    #[clap(name = "nft")]
+    Nft,
+    /// Program with a token staking rewards template
+    #[clap(name = "staking")]
+    Staking,
+    /// Program with an M-of-N multisig template
+    #[clap(name = "multisig")]
+    Multisig,
+    /// Program with a Token-2022 mint template
+    #[clap(name = "token-2022")]
+    Token2022,
+    /// Program with a Token-2022 transfer hook template
+    #[clap(name = "transfer-hook")]
+    TransferHook,
+    /// Program with a per-user PDA create, read, update and delete template
+    #[clap(name = "crud")]
+    Crud,
+}
+
+impl fmt::Display for ProgramTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name())
+    }
+}
+
+/// Serialized by name, e.g. in the generation manifest
+impl Serialize for ProgramTemplate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProgramTemplate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for ProgramTemplate {
+    type Err = anyhow::Error;
+
+    /// Parse a template name, ignoring case and accepting `_` for `-`, e.g. `mint_token`.
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        Self::value_variants()
+            .iter()
+            .find(|template| template.name() == name)
+            .copied()
+            .ok_or_else(|| {
+                let names = Self::value_variants()
+                    .iter()
+                    .map(Self::name)
+                    .collect::<Vec<_>>();
+                anyhow!(
+                    "Invalid template `{s}`. Possible values: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Account whose public key is added to the template's PDA seeds
+#[derive(Clone, Debug, Eq, PartialEq, Parser, ValueEnum, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PdaSeed {
+    /// Derive one PDA per user from the signer's public key
+    User,
+}
+
+/// BPF loader used by the generated deploy script
+#[derive(Clone, Debug, Default, Eq, PartialEq, Parser, ValueEnum, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeployLoader {
+    /// Deploy with the upgradeable loader
+    #[default]
+    Upgradeable,
+    /// Deploy as final, the program can't be upgraded afterwards
+    Final,
+}
+
+/// Package manager installing the node modules and running the tests
+#[derive(Clone, Debug, Default, Eq, PartialEq, Parser, ValueEnum, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PackageManager {
+    /// Install with yarn, falling back to npm if it fails
+    #[default]
+    Yarn,
+    Npm,
+    Pnpm,
+    /// Bun runs TypeScript natively, so the tests use mocha instead of ts-mocha
+    Bun,
+}
+
+impl PackageManager {
+    /// Executable of the package manager.
+    pub fn command(&self) -> &'static str {
+        match self {
+            PackageManager::Yarn => "yarn",
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    /// Command running the mocha tests with TypeScript support.
+    fn test_runner(&self) -> &'static str {
+        match self {
+            PackageManager::Yarn => "yarn run ts-mocha -p ./tsconfig.json",
+            PackageManager::Npm => "npx ts-mocha -p ./tsconfig.json",
+            PackageManager::Pnpm => "pnpm exec ts-mocha -p ./tsconfig.json",
+            // Bun loads the TypeScript files and tsconfig.json itself
+            PackageManager::Bun => "bunx --bun mocha",
+        }
+    }
+
+    /// Command running the mocha tests of a JavaScript workspace.
+    fn js_test_runner(&self) -> &'static str {
+        match self {
+            PackageManager::Yarn => "yarn run mocha",
+            PackageManager::Npm => "npx mocha",
+            PackageManager::Pnpm => "pnpm exec mocha",
+            PackageManager::Bun => "bunx --bun mocha",
+        }
+    }
+
+    fn runner(&self, javascript: bool) -> &'static str {
+        if javascript {
+            self.js_test_runner()
+        } else {
+            self.test_runner()
+        }
+    }
+}
+
+/// Commitment level used by the generated tests
+#[derive(Clone, Debug, Eq, PartialEq, Parser, ValueEnum, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Commitment {
+    /// Query the most recent block, which may still be skipped
+    Processed,
+    /// Query the most recent block voted on by a supermajority
+    Confirmed,
+    /// Query the most recent block that has been finalized
+    Finalized,
+}
+
+impl std::fmt::Display for Commitment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Commitment::Processed => write!(f, "processed"),
+            Commitment::Confirmed => write!(f, "confirmed"),
+            Commitment::Finalized => write!(f, "finalized"),
+        }
+    }
+}
+
+impl ProgramTemplate {
+    /// Name of the template as accepted by `--template`.
+    pub fn name(&self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Short description of the template, taken from its doc comment.
+    pub fn description(&self) -> String {
+        self.to_possible_value()
+            .and_then(|value| value.get_help().map(ToString::to_string))
+            .unwrap_or_default()
+    }
+
+    /// Cluster the template's Anchor.toml provider points at.
+    pub fn default_cluster(&self) -> &'static str {
+        match self {
+            ProgramTemplate::MintToken | ProgramTemplate::Nft => "devnet",
+            ProgramTemplate::Basic
+            | ProgramTemplate::Counter
+            | ProgramTemplate::Pausable
+            | ProgramTemplate::PdaSigner
+            | ProgramTemplate::Escrow
+            | ProgramTemplate::Vesting
+            | ProgramTemplate::Staking
+            | ProgramTemplate::Multisig
+            | ProgramTemplate::Token2022
+            | ProgramTemplate::TransferHook
+            | ProgramTemplate::Crud => "localnet",
+        }
+    }
+
+    /// Clusters the template's Anchor.toml lists the program under.
+    pub fn program_clusters(&self) -> &'static [&'static str] {
+        match self {
+            ProgramTemplate::MintToken | ProgramTemplate::Nft => &["localnet", "devnet"],
+            ProgramTemplate::Basic
+            | ProgramTemplate::Counter
+            | ProgramTemplate::Pausable
+            | ProgramTemplate::PdaSigner
+            | ProgramTemplate::Escrow
+            | ProgramTemplate::Vesting
+            | ProgramTemplate::Staking
+            | ProgramTemplate::Multisig
+            | ProgramTemplate::Token2022
+            | ProgramTemplate::TransferHook
+            | ProgramTemplate::Crud => &["localnet"],
+        }
+    }
 }
 
-/// Create a program from the given name and template.
-pub fn create_program(name: &str, template: ProgramTemplate) -> Result<()> {
+/// Accounts of a program constrained by `seeds` that a test never passes to an instruction,
+/// leaving them for Anchor to resolve from their seeds.
+pub fn resolved_pdas(program: &str, test: &str) -> Vec<String> {
+    // `#[account(...)]` attributes with the name of the field they annotate. Seeds contain `)]`
+    // too, e.g. `user.key().as_ref()]`, which isn't followed by the field
+    let re = Regex::new(r"(?s)#\[account\((.*?)\)\]\s*pub\s+(\w+)\s*:").expect("Valid regex");
+    re.captures_iter(program)
+        .filter(|captures| captures[1].contains("seeds"))
+        .map(|captures| captures[2].to_lower_camel_case())
+        // Passed as a key or a shorthand property of the accounts object
+        .filter(|pda| !Regex::new(&format!(r"\b{pda}\s*[:,}}]")).is_ok_and(|re| re.is_match(test)))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Warn when a test leaves PDAs to seed resolution but the Anchor.toml disables it.
+pub fn seeds_feature_warning(anchor_toml: &str, program: &str, test: &str) -> Option<String> {
+    let seeds = anchor_toml
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|table| table.get("features")?.get("seeds")?.as_bool())
+        .unwrap_or(false);
+    if seeds {
+        return None;
+    }
+
+    let pdas = resolved_pdas(program, test);
+    if pdas.is_empty() {
+        return None;
+    }
+    let pdas = pdas
+        .iter()
+        .map(|pda| format!("`{pda}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "The tests resolve {pdas} from their seeds, set `seeds = true` under `[features]` in Anchor.toml or pass them to `.accounts()`"
+    ))
+}
+
+/// Resolve a 1-based selection from the template menu.
+pub fn template_from_selection(selection: &str) -> Option<ProgramTemplate> {
+    let index = selection.trim().parse::<usize>().ok()?;
+    ProgramTemplate::value_variants()
+        .get(index.checked_sub(1)?)
+        .copied()
+}
+
+/// Show a numbered menu of the available templates and read the user's pick.
+pub fn prompt_template(mut input: impl BufRead, mut output: impl Write) -> Result<ProgramTemplate> {
+    let templates = ProgramTemplate::value_variants();
+    writeln!(output, "Available templates:")?;
+    for (i, template) in templates.iter().enumerate() {
+        writeln!(
+            output,
+            "  {}) {:<12} {}",
+            i + 1,
+            template.name(),
+            template.description()
+        )?;
+    }
+
+    loop {
+        write!(output, "Select a template [1-{}]: ", templates.len())?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Err(anyhow!("No template selected"));
+        }
+        match template_from_selection(&line) {
+            Some(template) => return Ok(template),
+            None => writeln!(output, "Invalid selection: {}", line.trim())?,
+        }
+    }
+}
+
+/// Create a program from the given name and template depending on `anchor_version` of the
+/// Anchor crates, returning the paths of the files created.
+pub fn create_program(
+    name: &str,
+    template: ProgramTemplate,
+    anchor_version: &str,
+    pda_from: Option<PdaSeed>,
+    mints: Option<u8>,
+    error_base: Option<u32>,
+) -> Result<Vec<PathBuf>> {
+    let program_id = get_or_create_program_id(name);
+    create_files(&program_files(
+        name,
+        template,
+        program_id,
+        anchor_version,
+        pda_from,
+        mints,
+        error_base,
+        false,
+    ))
+}
+
+/// Files of a program created from the given name and template, declaring `program_id` and
+/// depending on `anchor_version` of the Anchor crates, along with the workspace Cargo.toml.
+#[allow(clippy::too_many_arguments)]
+pub fn program_files(
+    name: &str,
+    template: ProgramTemplate,
+    program_id: Pubkey,
+    anchor_version: &str,
+    pda_from: Option<PdaSeed>,
+    mints: Option<u8>,
+    error_base: Option<u32>,
+    fast_build: bool,
+) -> Files {
     let program_path = Path::new("programs").join(name);
     let common_files = vec![
-        ("Cargo.toml".into(), workspace_manifest().into()),
-        (program_path.join("Cargo.toml"), cargo_toml(name, template)),
+        ("Cargo.toml".into(), workspace_manifest(fast_build)),
+        (
+            program_path.join("Cargo.toml"),
+            cargo_toml(name, template, anchor_version),
+        ),
         (program_path.join("Xargo.toml"), xargo_toml().into()),
     ];
 
     let template_files = match template {
-        ProgramTemplate::Basic => create_program_template_basic(name, &program_path),
-        ProgramTemplate::Counter => create_program_template_counter(name, &program_path),
-        ProgramTemplate::MintToken => create_program_template_mint_token(name, &program_path),
+        ProgramTemplate::Basic => create_program_template_basic(name, &program_path, program_id),
+        ProgramTemplate::Counter => {
+            create_program_template_counter(name, &program_path, program_id, pda_from)
+        }
+        ProgramTemplate::MintToken => {
+            create_program_template_mint_token(name, &program_path, program_id, mints, error_base)
+        }
+        ProgramTemplate::Pausable => {
+            create_program_template_pausable(name, &program_path, program_id, error_base)
+        }
+        ProgramTemplate::PdaSigner => {
+            create_program_template_pda_signer(name, &program_path, program_id)
+        }
+        ProgramTemplate::Escrow => create_program_template_escrow(name, &program_path, program_id),
+        ProgramTemplate::Vesting => {
+            create_program_template_vesting(name, &program_path, program_id, error_base)
+        }
+        ProgramTemplate::Nft => create_program_template_nft(name, &program_path, program_id),
+        ProgramTemplate::Staking => {
+            create_program_template_staking(name, &program_path, program_id, error_base)
+        }
+        ProgramTemplate::Multisig => {
+            create_program_template_multisig(name, &program_path, program_id, error_base)
+        }
+        ProgramTemplate::Token2022 => {
+            create_program_template_token_2022(name, &program_path, program_id)
+        }
+        ProgramTemplate::TransferHook => {
+            create_program_template_transfer_hook(name, &program_path, program_id)
+        }
+        ProgramTemplate::Crud => {
+            create_program_template_crud(name, &program_path, program_id, error_base)
+        }
     };
 
-    create_files(&[common_files, template_files].concat())
+    [common_files, template_files].concat()
+}
+
+/// `#[error_code]` attribute of the templates' custom errors, numbered from `error_base`.
+fn error_code_attribute(error_base: Option<u32>) -> String {
+    match error_base {
+        Some(base) => format!(
+            "// Error codes start at {base} instead of Anchor's default 6000, the first variant is {base}\n#[error_code(offset = {base})]"
+        ),
+        None => "// Anchor numbers custom errors from 6000, the first variant is 6000\n#[error_code]"
+            .to_string(),
+    }
 }
 
 /// Create a program with a basic template
-fn create_program_template_basic(name: &str, program_path: &Path) -> Files {
+fn create_program_template_basic(name: &str, program_path: &Path, program_id: Pubkey) -> Files {
     vec![(
         program_path.join("src").join("lib.rs"),
         format!(
@@ -56,7 +470,7 @@ declare_id!("{}");
 pub mod {} {{
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {{
+    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {{
         Ok(())
     }}
 }}
@@ -64,14 +478,27 @@ pub mod {} {{
 #[derive(Accounts)]
 pub struct Initialize {{}}
 "#,
-            get_or_create_program_id(name),
+            program_id,
             name.to_snake_case(),
         ),
     )]
 }
 
 /// Create a program with counter template
-fn create_program_template_counter(name: &str, program_path: &Path) -> Files {
+fn create_program_template_counter(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+    pda_from: Option<PdaSeed>,
+) -> Files {
+    let (seeds, increment_counter) = match pda_from {
+        None => (r#"[b"counter"]"#, "#[account(mut)]".to_string()),
+        Some(PdaSeed::User) => {
+            let seeds = r#"[b"counter", user.key().as_ref()]"#;
+            (seeds, format!("#[account(mut, seeds = {seeds}, bump)]"))
+        }
+    };
+
     vec![(
         program_path.join("src").join("lib.rs"),
         format!(
@@ -100,7 +527,7 @@ pub mod {} {{
 pub struct Initialize<'info> {{
     #[account(
         init,
-        seeds = [b"counter"],
+        seeds = {seeds},
         bump,
         payer=user,
         space = Counter::space()
@@ -115,7 +542,7 @@ pub struct Initialize<'info> {{
 
 #[derive(Accounts)]
 pub struct Increment<'info> {{
-    #[account(mut)]
+    {increment_counter}
     pub counter: Account<'info, Counter>,
 
     #[account(mut)]  // Remove leading space
@@ -136,14 +563,57 @@ impl Counter {{
     }}
 }}
 "#,
-            get_or_create_program_id(name),
+            program_id,
             name.to_snake_case(),
         ),
     )]
 }
 
 /// Create a program with mint token template
-fn create_program_template_mint_token(name: &str, program_path: &Path) -> Files {
+fn create_program_template_mint_token(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+    mints: Option<u8>,
+    error_base: Option<u32>,
+) -> Files {
+    let error_code = error_code_attribute(error_base);
+    // With several mints, each mint PDA is derived from its index
+    let (index_arg, index_instruction, mint_seeds, signer_seeds, index_check, index_items) =
+        match mints {
+            Some(mints) => (
+                "index: u8, ",
+                "index: u8,\n    ",
+                "[b\"mint\", index.to_le_bytes().as_ref()]",
+                "&[\"mint\".as_bytes(), &index.to_le_bytes(), &[ctx.bumps.mint]]",
+                "\n        require!(index < MINT_COUNT, MintError::InvalidMintIndex);\n",
+                format!(
+                    r#"
+// Number of mints managed by the program
+pub const MINT_COUNT: u8 = {mints};
+
+{error_code}
+pub enum MintError {{
+    #[msg("The mint index is out of range")]
+    InvalidMintIndex,
+}}
+"#
+                ),
+            ),
+            None => (
+                "",
+                "",
+                "[b\"mint\"]",
+                "&[\"mint\".as_bytes(), &[ctx.bumps.mint]]",
+                "",
+                String::new(),
+            ),
+        };
+    let mint_tokens_instruction = match mints {
+        Some(_) => "\n#[instruction(index: u8)]",
+        None => "",
+    };
+
     vec![(
         program_path.join("src").join("lib.rs"),
         format!(
@@ -151,7 +621,9 @@ fn create_program_template_mint_token(name: &str, program_path: &Path) -> Files
 use anchor_spl::{{
     associated_token::AssociatedToken,
     metadata::{{
-        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
+        create_metadata_accounts_v3,
+        mpl_token_metadata::{{self, types::DataV2}},
+        CreateMetadataAccountsV3,
     }},
     token::{{mint_to, Mint, MintTo, Token, TokenAccount}},
 }};
@@ -161,9 +633,9 @@ declare_id!("{}");
 #[program]
 pub mod {} {{
     use super::*;
-    pub fn init_token(ctx: Context<InitToken>, metadata: InitTokenParams) -> Result<()> {{
+    pub fn init_token(ctx: Context<InitToken>, {index_arg}metadata: InitTokenParams) -> Result<()> {{{index_check}
         // Define seeds and signer for creating a token account
-        let seeds = &["mint".as_bytes(), &[ctx.bumps.mint]];
+        let seeds = {signer_seeds};
         let signer = [&seeds[..]];
 
         // Define the token data with provided metadata
@@ -200,9 +672,9 @@ pub mod {} {{
         Ok(())
     }}
 
-    pub fn mint_tokens(ctx: Context<MintTokens>, quantity: u64) -> Result<()> {{
+    pub fn mint_tokens(ctx: Context<MintTokens>, {index_arg}quantity: u64) -> Result<()> {{
         // Define seeds and signer for minting tokens
-        let seeds = &["mint".as_bytes(), &[ctx.bumps.mint]];
+        let seeds = {signer_seeds};
         let signer = [&seeds[..]];
 
         // Mint tokens to the destination account with the given quantity
@@ -226,15 +698,21 @@ pub mod {} {{
 // Struct defining the context for initializing a token
 #[derive(Accounts)]
 #[instruction(
-    params: InitTokenParams
+    {index_instruction}params: InitTokenParams
 )]
 pub struct InitToken<'info> {{
-    /// CHECK: New Metaplex Account being created
-    #[account(mut)]
+    /// CHECK: New Metaplex account being created, validated by the Metaplex program. The seeds
+    /// constraint still makes sure it is the metadata PDA of this mint.
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
     pub metadata: UncheckedAccount<'info>,
     #[account(
         init,
-        seeds = [b"mint"],
+        seeds = {mint_seeds},
         bump,
         payer = payer,
         mint::decimals = params.decimals,
@@ -246,7 +724,8 @@ pub struct InitToken<'info> {{
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
-    /// CHECK: Metaplex program ID
+    /// CHECK: Constrained to the Metaplex program ID, so a malicious program can't be passed in
+    #[account(address = mpl_token_metadata::ID)]
     pub token_metadata_program: UncheckedAccount<'info>,
 }}
 
@@ -260,11 +739,11 @@ pub struct InitTokenParams {{
 }}
 
 // Struct defining the context for minting tokens
-#[derive(Accounts)]
+#[derive(Accounts)]{mint_tokens_instruction}
 pub struct MintTokens<'info> {{
     #[account(
         mut,
-        seeds = [b"mint"],
+        seeds = {mint_seeds},
         bump,
         mint::authority = mint,
     )]
@@ -273,9 +752,12 @@ pub struct MintTokens<'info> {{
         init_if_needed, //Initializes the destination account if it does not exist
         payer = payer,
         associated_token::mint = mint,
-        associated_token::authority = payer,
+        associated_token::authority = recipient,
     )]
     pub destination: Account<'info, TokenAccount>,
+    /// CHECK: Only used as the authority of the destination token account, which the
+    /// `associated_token::authority` constraint checks. No data is read from it.
+    pub recipient: UncheckedAccount<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub rent: Sysvar<'info, Rent>,
@@ -283,631 +765,5879 @@ pub struct MintTokens<'info> {{
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }}
-"#,
-            get_or_create_program_id(name),
+{index_items}"#,
+            program_id,
             name.to_snake_case(),
         ),
     )]
 }
 
-const fn workspace_manifest() -> &'static str {
-    r#"[workspace]
-members = [
-    "programs/*"
-]
-resolver = "2"
-
-[profile.release]
-overflow-checks = true
-lto = "fat"
-codegen-units = 1
-[profile.release.build-override]
-opt-level = 3
-incremental = false
-codegen-units = 1
-"#
-}
+/// Create a program with pausable template
+fn create_program_template_pausable(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+    error_base: Option<u32>,
+) -> Files {
+    let error_code = error_code_attribute(error_base);
 
-fn cargo_toml(name: &str, template: ProgramTemplate) -> String {
-    let template_files = match template {
-        ProgramTemplate::Basic => cargo_toml_basic(name),
-        ProgramTemplate::Counter => cargo_toml_counter(name),
-        ProgramTemplate::MintToken => cargo_toml_mint_token(name),
-    };
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
 
-    template_files
-}
+declare_id!("{}");
 
-fn cargo_toml_basic(name: &str) -> String {
-    format!(
-        r#"[package]
-name = "{0}"
-version = "0.1.0"
-description = "Created with Anchor"
-edition = "2021"
-
-[lib]
-crate-type = ["cdylib", "lib"]
-name = "{1}"
-
-[features]
-default = []
-cpi = ["no-entrypoint"]
-no-entrypoint = []
-no-idl = []
-no-log-ix-name = []
-idl-build = ["anchor-lang/idl-build"]
-
-[dependencies]
-anchor-lang = "{2}"
-"#,
-        name,
-        name.to_snake_case(),
-        ANCHOR_VERSION,
-    )
-}
+#[program]
+pub mod {} {{
+    use super::*;
 
-fn cargo_toml_counter(name: &str) -> String {
-    format!(
-        r#"[package]
-name = "{0}"
-version = "0.1.0"
-description = "Created with Anchor"
-edition = "2021"
-
-[lib]
-crate-type = ["cdylib", "lib"]
-name = "{1}"
-
-[features]
-default = []
-cpi = ["no-entrypoint"]
-no-entrypoint = []
-no-idl = []
-no-log-ix-name = []
-idl-build = ["anchor-lang/idl-build"]
-
-[dependencies]
-anchor-lang = "{2}"
-"#,
-        name,
-        name.to_snake_case(),
-        ANCHOR_VERSION,
-    )
-}
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {{
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.paused = false;
+        ctx.accounts.counter.count = 0;
+        Ok(())
+    }}
 
-fn cargo_toml_mint_token(name: &str) -> String {
-    format!(
-        r#"[package]
-name = "{0}"
-version = "0.1.0"
-description = "Created with Anchor"
-edition = "2021"
-
-[lib]
-crate-type = ["cdylib", "lib"]
-name = "{1}"
-
-[features]
-default = []
-cpi = ["no-entrypoint"]
-no-entrypoint = []
-no-idl = []
-no-log-ix-name = []
-idl-build = ["anchor-lang/idl-build", "anchor-spl/idl-build"]
-
-[dependencies]
-anchor-lang = {{ version = "{2}", features = ["init-if-needed"] }}
-anchor-spl = {{ version = "{3}", features = ["metadata"] }}
-"#,
-        name,
-        name.to_snake_case(),
-        ANCHOR_VERSION,
-        ANCHOR_VERSION
-    )
-}
+    pub fn pause(ctx: Context<SetPaused>) -> Result<()> {{
+        ctx.accounts.config.paused = true;
+        Ok(())
+    }}
 
-fn xargo_toml() -> &'static str {
-    r#"[target.bpfel-unknown-unknown.dependencies.std]
-features = []
-"#
-}
+    pub fn unpause(ctx: Context<SetPaused>) -> Result<()> {{
+        ctx.accounts.config.paused = false;
+        Ok(())
+    }}
 
-/// Read the program keypair file or create a new one if it doesn't exist.
-pub fn get_or_create_program_id(name: &str) -> Pubkey {
-    let keypair_path = Path::new("target")
-        .join("deploy")
-        .join(format!("{}-keypair.json", name.to_snake_case()));
+    pub fn increment(ctx: Context<Increment>) -> Result<()> {{
+        // Reject the instruction while the program is paused
+        require!(!ctx.accounts.config.paused, PausableError::ProgramPaused);
 
-    read_keypair_file(&keypair_path)
-        .unwrap_or_else(|_| {
-            let keypair = Keypair::new();
-            write_keypair_file(&keypair, keypair_path).expect("Unable to create program keypair");
-            keypair
-        })
-        .pubkey()
-}
+        ctx.accounts.counter.count += 1;
+        Ok(())
+    }}
+}}
 
-pub fn create_anchor_toml(
-    program_id: String,
-    test_script: String,
-    template: ProgramTemplate,
-) -> String {
-    let template_files = match template {
-        ProgramTemplate::Basic => create_anchor_toml_basic(program_id, test_script),
-        ProgramTemplate::Counter => create_anchor_toml_counter(program_id, test_script),
-        ProgramTemplate::MintToken => create_anchor_toml_mint_token(program_id, test_script),
-    };
+#[derive(Accounts)]
+pub struct Initialize<'info> {{
+    #[account(
+        init,
+        seeds = [b"config"],
+        bump,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE
+    )]
+    pub config: Account<'info, Config>,
 
-    template_files
-}
+    #[account(
+        init,
+        seeds = [b"counter"],
+        bump,
+        payer = admin,
+        space = 8 + Counter::INIT_SPACE
+    )]
+    pub counter: Account<'info, Counter>,
 
-pub fn create_anchor_toml_basic(program_id: String, test_script: String) -> String {
-    format!(
-        r#"[toolchain]
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {{
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ PausableError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}}
+
+#[derive(Accounts)]
+pub struct Increment<'info> {{
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
 
-[features]
-seeds = false
-skip-lint = false
+    #[account(mut, seeds = [b"counter"], bump)]
+    pub counter: Account<'info, Counter>,
 
-[programs.localnet]
-counter = "{program_id}"
+    pub user: Signer<'info>,
+}}
 
-[registry]
-url = "https://api.apr.dev"
+#[account]
+#[derive(InitSpace)]
+pub struct Config {{
+    pub admin: Pubkey,
+    pub paused: bool,
+}}
 
-[provider]
-cluster = "Localnet"
-wallet = "wallet.json"
+#[account]
+#[derive(InitSpace)]
+pub struct Counter {{
+    pub count: u64,
+}}
 
-[scripts]
-test = "{test_script}"
+{error_code}
+pub enum PausableError {{
+    #[msg("The program is paused")]
+    ProgramPaused,
+    #[msg("Only the admin can pause or unpause the program")]
+    Unauthorized,
+}}
 "#,
-    )
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
 }
 
-pub fn create_anchor_toml_counter(program_id: String, test_script: String) -> String {
-    format!(
-        r#"[toolchain]
+/// Create a program with PDA signer template
+fn create_program_template_pda_signer(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+) -> Files {
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+use anchor_lang::system_program::{{transfer, Transfer}};
 
-[features]
-seeds = false
-skip-lint = false
+declare_id!("{}");
 
-[programs.localnet]
-counter = "{program_id}"
+#[program]
+pub mod {} {{
+    use super::*;
 
-[registry]
-url = "https://api.apr.dev"
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {{
+        // The user signs the transfer into the vault
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                }},
+            ),
+            amount,
+        )
+    }}
 
-[provider]
-cluster = "Localnet"
-wallet = "wallet.json"
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {{
+        // The vault PDA signs the transfer out of itself with its seeds
+        let user_key = ctx.accounts.user.key();
+        let seeds = &[b"vault".as_ref(), user_key.as_ref(), &[ctx.bumps.vault]];
+        let signer = [&seeds[..]];
 
-[scripts]
-test = "{test_script}"
-"#,
-    )
-}
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.user.to_account_info(),
+                }},
+                &signer,
+            ),
+            amount,
+        )
+    }}
+}}
 
-pub fn create_anchor_toml_mint_token(program_id: String, test_script: String) -> String {
-    format!(
-        r#"[toolchain]
+#[derive(Accounts)]
+pub struct Deposit<'info> {{
+    #[account(mut, seeds = [b"vault", user.key().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
 
-[features]
-seeds = false
-skip-lint = false
+    #[account(mut)]
+    pub user: Signer<'info>,
 
-[programs.localnet]
-counter = "{program_id}"
-[programs.devnet]
-counter = "{program_id}"
+    pub system_program: Program<'info, System>,
+}}
 
-[registry]
-url = "https://api.apr.dev"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {{
+    #[account(mut, seeds = [b"vault", user.key().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
 
-[provider]
-cluster = "devnet"
-wallet = "wallet.json"
+    #[account(mut)]
+    pub user: Signer<'info>,
 
-[scripts]
-test = "{test_script}"
+    pub system_program: Program<'info, System>,
+}}
 "#,
-    )
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
 }
 
-pub fn ts_deploy_script() -> &'static str {
-    r#"// Migrations are an early feature. Currently, they're nothing more than this
-// single deploy script that's invoked from the CLI, injecting a provider
-// configured from the workspace's Anchor.toml.
+/// Create a program with escrow template
+fn create_program_template_escrow(name: &str, program_path: &Path, program_id: Pubkey) -> Files {
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+use anchor_spl::{{
+    associated_token::AssociatedToken,
+    token::{{close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer}},
+}};
 
-const anchor = require("@coral-xyz/anchor");
+declare_id!("{}");
 
-module.exports = async function (provider) {
-  // Configure client to use the provider.
-  anchor.setProvider(provider);
+#[program]
+pub mod {} {{
+    use super::*;
 
-  // Add your deploy script here.
-};
-"#
-}
+    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {{
+        ctx.accounts.escrow.set_inner(Escrow {{
+            seed,
+            maker: ctx.accounts.maker.key(),
+            mint_a: ctx.accounts.mint_a.key(),
+            mint_b: ctx.accounts.mint_b.key(),
+            receive,
+            bump: ctx.bumps.escrow,
+        }});
+
+        // Move the maker's tokens into the vault owned by the escrow PDA
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.maker_ata_a.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.maker.to_account_info(),
+                }},
+            ),
+            deposit,
+        )
+    }}
 
-pub fn ts_package_json(license: String, template: ProgramTemplate) -> String {
-    let template_files = match template {
-        ProgramTemplate::Basic => ts_package_json_basic(license),
-        ProgramTemplate::Counter => ts_package_json_counter(license),
-        ProgramTemplate::MintToken => ts_package_json_mint_token(license),
-    };
+    pub fn take(ctx: Context<Take>) -> Result<()> {{
+        // The taker pays the maker the amount of mint B they asked for
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.taker_ata_b.to_account_info(),
+                    to: ctx.accounts.maker_ata_b.to_account_info(),
+                    authority: ctx.accounts.taker.to_account_info(),
+                }},
+            ),
+            ctx.accounts.escrow.receive,
+        )?;
 
-    template_files
-}
+        // The escrow PDA signs the release of the vault to the taker
+        let escrow = &ctx.accounts.escrow;
+        let seed = escrow.seed.to_le_bytes();
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.maker.as_ref(),
+            seed.as_ref(),
+            &[escrow.bump],
+        ];
+        let signer = [&seeds[..]];
 
-pub fn ts_package_json_basic(license: String) -> String {
-    format!(
-        r#"{{
-  "license": "{license}",
-  "scripts": {{
-    "lint:fix": "prettier */*.js \"*/**/*{{.js,.ts}}\" -w",
-    "lint": "prettier */*.js \"*/**/*{{.js,.ts}}\" --check"
-  }},
-  "dependencies": {{
-    "@coral-xyz/anchor": "^{ANCHOR_VERSION}"
-  }},
-  "devDependencies": {{
-    "chai": "^4.3.4",
-    "mocha": "^9.0.3",
-    "ts-mocha": "^10.0.0",
-    "@types/bn.js": "^5.1.0",
-    "@types/chai": "^4.3.0",
-    "@types/mocha": "^9.0.0",
-    "typescript": "^4.3.5",
-    "prettier": "^2.6.2"
-  }}
-}}
-"#
-    )
-}
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.taker_ata_a.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                }},
+                &signer,
+            ),
+            ctx.accounts.vault.amount,
+        )?;
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {{
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.maker.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            }},
+            &signer,
+        ))
+    }}
 
-pub fn ts_package_json_counter(license: String) -> String {
-    format!(
-        r#"{{
-  "license": "{license}",
-  "scripts": {{
-    "lint:fix": "prettier */*.js \"*/**/*{{.js,.ts}}\" -w",
-    "lint": "prettier */*.js \"*/**/*{{.js,.ts}}\" --check"
-  }},
-  "dependencies": {{
-    "@coral-xyz/anchor": "^{ANCHOR_VERSION}",
-    "@solana/web3.js": "^1.92.3"
-  }},
-  "devDependencies": {{
-    "chai": "^4.3.4",
-    "mocha": "^9.0.3",
-    "ts-mocha": "^10.0.0",
-    "@types/bn.js": "^5.1.0",
-    "@types/chai": "^4.3.0",
-    "@types/mocha": "^9.0.0",
-    "typescript": "^4.3.5",
-    "prettier": "^2.6.2"
-  }}
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {{
+        // The escrow PDA signs the return of the vault to the maker
+        let escrow = &ctx.accounts.escrow;
+        let seed = escrow.seed.to_le_bytes();
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.maker.as_ref(),
+            seed.as_ref(),
+            &[escrow.bump],
+        ];
+        let signer = [&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.maker_ata_a.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                }},
+                &signer,
+            ),
+            ctx.accounts.vault.amount,
+        )?;
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {{
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.maker.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            }},
+            &signer,
+        ))
+    }}
 }}
-"#
-    )
-}
 
-pub fn ts_package_json_mint_token(license: String) -> String {
-    format!(
-        r#"{{
-  "license": "{license}",
-  "scripts": {{
-    "lint:fix": "prettier */*.js \"*/**/*{{.js,.ts}}\" -w",
-    "lint": "prettier */*.js \"*/**/*{{.js,.ts}}\" --check"
-  }},
-  "dependencies": {{
-    "@coral-xyz/anchor": "^{ANCHOR_VERSION}",
-    "@solana/web3.js": "^1.92.3"
-  }},
-  "devDependencies": {{
-    "chai": "^4.3.4",
-    "mocha": "^9.0.3",
-    "ts-mocha": "^10.0.0",
-    "@types/bn.js": "^5.1.0",
-    "@types/chai": "^4.3.0",
-    "@types/mocha": "^9.0.0",
-    "typescript": "^4.3.5",
-    "prettier": "^2.6.2"
-  }}
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct Make<'info> {{
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub mint_a: Account<'info, Mint>,
+
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = maker)]
+    pub maker_ata_a: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }}
-"#
-    )
-}
 
-pub fn ts_mocha(name: &str, template: ProgramTemplate) -> String {
-    let template_files = match template {
-        ProgramTemplate::Basic => ts_mocha_basic(name),
-        ProgramTemplate::Counter => ts_mocha_counter(name),
-        ProgramTemplate::MintToken => ts_mocha_mint_token(name),
-    };
+#[derive(Accounts)]
+pub struct Take<'info> {{
+    #[account(mut)]
+    pub taker: Signer<'info>,
 
-    template_files
-}
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
 
-pub fn ts_mocha_basic(name: &str) -> String {
-    format!(
-        r#"import * as anchor from "@coral-xyz/anchor";
-import {{ Program }} from "@coral-xyz/anchor";
-import {{ {} }} from "../target/types/{}";
+    pub mint_a: Account<'info, Mint>,
 
-describe("{}", () => {{
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+    pub mint_b: Account<'info, Mint>,
 
-  const program = anchor.workspace.{} as Program<{}>;
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker
+    )]
+    pub taker_ata_a: Account<'info, TokenAccount>,
 
-  it("Is initialized!", async () => {{
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
-  }});
-}});
-"#,
-        name.to_pascal_case(),
-        name.to_snake_case(),
-        name,
-        name.to_pascal_case(),
-        name.to_pascal_case(),
-    )
-}
+    #[account(mut, associated_token::mint = mint_b, associated_token::authority = taker)]
+    pub taker_ata_b: Account<'info, TokenAccount>,
 
-pub fn ts_mocha_counter(name: &str) -> String {
-    format!(
-        r#"import * as anchor from "@coral-xyz/anchor";
-import {{ Program }} from "@coral-xyz/anchor";
-import {{  PublicKey }} from "@solana/web3.js";
-import {{ expect }} from "chai";
-import {{ {} }} from "../target/types/{}";
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker
+    )]
+    pub maker_ata_b: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
 
-describe("{}", () => {{
-  // Configure the client to use the local cluster.
-  const provider = anchor.AnchorProvider.env();
-  anchor.setProvider(provider);
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = escrow)]
+    pub vault: Account<'info, TokenAccount>,
 
-  const program = anchor.workspace.{} as Program<{}>;
-  let counterAccount: PublicKey;
-  let counterBump: number;
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}}
 
-  before("Boilerplates", async () => {{
-    [counterAccount, counterBump] = await PublicKey.findProgramAddress(
-      [Buffer.from("counter")],
-      program.programId
-    );
-  }});
+#[derive(Accounts)]
+pub struct Refund<'info> {{
+    #[account(mut)]
+    pub maker: Signer<'info>,
 
-  it("Initialize counter!", async () => {{
-    await program.methods
-      .initialize()
-      .accounts({{
-        counter: counterAccount,
-        user: provider.wallet.publicKey,
-      }})
-      .rpc();
+    pub mint_a: Account<'info, Mint>,
 
-    const counter = await program.account.counter.fetch(counterAccount);
-    expect(counter.count.toString()).eq("0")
-  }});
-  it("Increment counter", async () => {{
-    await program.methods
-      .increment()
-      .accounts({{
-        counter: counterAccount,
-        user: provider.wallet.publicKey,
-      }})
-      .rpc();
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = maker)]
+    pub maker_ata_a: Account<'info, TokenAccount>,
 
-    const counter = await program.account.counter.fetch(counterAccount);
-    expect(counter.count.toString()).eq("1")
-  }});
-}});
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = escrow)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {{
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub bump: u8,
+}}
 "#,
-        name.to_pascal_case(),
-        name.to_snake_case(),
-        name,
-        name.to_pascal_case(),
-        name.to_pascal_case(),
-    )
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
 }
 
-pub fn ts_mocha_mint_token(name: &str) -> String {
-    format!(
-        r#"import * as anchor from "@coral-xyz/anchor";
-import {{ Program }} from "@coral-xyz/anchor";
-import {{ PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY }} from "@solana/web3.js";
-import {{ assert }} from "chai";
-import BN from "bn.js";
-import {{ {} }} from "../target/types/{}";
+/// Create a program with vesting template
+fn create_program_template_vesting(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+    error_base: Option<u32>,
+) -> Files {
+    let error_code = error_code_attribute(error_base);
 
-describe("{}", () => {{
-  // Configure the client to use the local cluster.
-  const provider = anchor.AnchorProvider.env();
-  anchor.setProvider(provider);
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+use anchor_spl::{{
+    associated_token::AssociatedToken,
+    token::{{transfer, Mint, Token, TokenAccount, Transfer}},
+}};
 
-  const program = anchor.workspace.{} as Program<{}>;
+declare_id!("{}");
 
-  // Metaplex Constants
-  const METADATA_SEED = "metadata";
-  const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
-    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
-  );
+#[program]
+pub mod {} {{
+    use super::*;
 
-  // Constants from our program
-  const MINT_SEED = "mint";
+    pub fn initialize_vesting(
+        ctx: Context<InitializeVesting>,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        total_amount: u64,
+    ) -> Result<()> {{
+        require!(
+            start_ts <= cliff_ts && cliff_ts <= end_ts && start_ts < end_ts,
+            VestingError::InvalidSchedule
+        );
 
-  // Data for our tests
-  const payer = provider.wallet.publicKey;
-  const metadata = {{
-    name: "Icy",
-    symbol: "ICY",
-    uri: "https://cdn.discordapp.com/emojis/1192768878183465062.png?size=240&quality=lossless",
-    decimals: 9,
-  }};
-  const mintAmount = 10;
+        ctx.accounts.vesting.set_inner(Vesting {{
+            beneficiary: ctx.accounts.beneficiary.key(),
+            mint: ctx.accounts.mint.key(),
+            start_ts,
+            cliff_ts,
+            end_ts,
+            total_amount,
+            claimed: 0,
+            bump: ctx.bumps.vesting,
+        }});
+
+        // Lock the vested tokens in a vault owned by the vesting PDA
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.funder_ata.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                }},
+            ),
+            total_amount,
+        )
+    }}
 
-  // Derive the public key for our mint account
-  const [mint] = PublicKey.findProgramAddressSync(
-    [Buffer.from(MINT_SEED)],
-    program.programId
-  );
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {{
+        let now = Clock::get()?.unix_timestamp;
+        let vesting = &ctx.accounts.vesting;
+        let amount = vesting.unlocked(now) - vesting.claimed;
+        require!(amount > 0, VestingError::NothingToClaim);
+
+        // The vesting PDA signs the release of the unlocked tokens
+        let beneficiary = vesting.beneficiary;
+        let mint = vesting.mint;
+        let seeds = &[
+            b"vesting".as_ref(),
+            beneficiary.as_ref(),
+            mint.as_ref(),
+            &[vesting.bump],
+        ];
+        let signer = [&seeds[..]];
 
-  // Derive the public key for our metadata account using the Metaplex program
-  const [metadataAddress] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(METADATA_SEED),
-      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-      mint.toBuffer(),
-    ],
-    TOKEN_METADATA_PROGRAM_ID
-  );
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.beneficiary_ata.to_account_info(),
+                    authority: ctx.accounts.vesting.to_account_info(),
+                }},
+                &signer,
+            ),
+            amount,
+        )?;
 
-  it("initialize", async () => {{
-    // Check if the mint account already exists
-    const info = await provider.connection.getAccountInfo(mint);
-    if (info) {{
-      return; // Do not attempt to initialize if already initialized
+        ctx.accounts.vesting.claimed += amount;
+        Ok(())
     }}
-    console.log("  Mint not found. Attempting to initialize.");
+}}
 
-    // Define the accounts and arguments for the `initToken` function call
-    const context = {{
-      metadata: metadataAddress,
-      mint,
-      payer,
-      rent: SYSVAR_RENT_PUBKEY,
-      systemProgram: SystemProgram.programId,
-      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
-    }};
+#[derive(Accounts)]
+pub struct InitializeVesting<'info> {{
+    #[account(mut)]
+    pub funder: Signer<'info>,
 
-    // Call the `initToken` function to initialize the mint account
-    const txHash = await program.methods
-      .initToken(metadata)
-      .accounts(context)
-      .rpc();
+    pub beneficiary: SystemAccount<'info>,
 
-    // Wait for confirmation and log transaction details
-    await provider.connection.confirmTransaction(txHash, "finalized");
-    console.log(`  https://explorer.solana.com/tx/${{txHash}}?cluster=devnet`);
+    pub mint: Account<'info, Mint>,
 
-    // Verify that the mint account was initialized
-    const newInfo = await provider.connection.getAccountInfo(mint);
-    assert(newInfo, "  Mint should be initialized.");
-  }});
+    #[account(mut, associated_token::mint = mint, associated_token::authority = funder)]
+    pub funder_ata: Account<'info, TokenAccount>,
 
-  it("mint tokens", async () => {{
-    // Derive the associated token account address for the payer
-    const destination = anchor.utils.token.associatedAddress({{
-      mint: mint,
-      owner: payer,
-    }});
+    #[account(
+        init,
+        payer = funder,
+        space = Vesting::space(),
+        seeds = [b"vesting", beneficiary.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vesting: Account<'info, Vesting>,
 
-    // Get initial token balance (0 if account not yet created)
-    let initialBalance: number;
-    try {{
-      const balance = await provider.connection.getTokenAccountBalance(
-        destination
-      );
-      initialBalance = balance.value.uiAmount;
-    }} catch {{
-      // Token account not yet initiated has 0 balance
-      initialBalance = 0;
+    #[account(
+        init,
+        payer = funder,
+        associated_token::mint = mint,
+        associated_token::authority = vesting
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {{
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary
+    )]
+    pub beneficiary_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = beneficiary,
+        has_one = mint,
+        seeds = [b"vesting", beneficiary.key().as_ref(), mint.key().as_ref()],
+        bump = vesting.bump
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    #[account(mut, associated_token::mint = mint, associated_token::authority = vesting)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}}
+
+#[account]
+pub struct Vesting {{
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub total_amount: u64,
+    pub claimed: u64,
+    pub bump: u8,
+}}
+
+impl Vesting {{
+    pub fn space() -> usize {{
+        8 +  // discriminator
+        32 + // beneficiary
+        32 + // mint
+        8 +  // start_ts
+        8 +  // cliff_ts
+        8 +  // end_ts
+        8 +  // total_amount
+        8 +  // claimed
+        1 // bump
+    }}
+
+    /// Amount unlocked at `now`, nothing before the cliff and then linearly until the end.
+    pub fn unlocked(&self, now: i64) -> u64 {{
+        if now < self.cliff_ts {{
+            return 0;
+        }}
+        if now >= self.end_ts {{
+            return self.total_amount;
+        }}
+
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }}
+}}
+
+{error_code}
+pub enum VestingError {{
+    #[msg("The schedule must satisfy start <= cliff <= end with start < end")]
+    InvalidSchedule,
+    #[msg("No tokens are unlocked yet")]
+    NothingToClaim,
+}}
+"#,
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
+}
+
+/// Create a program with nft template
+fn create_program_template_nft(name: &str, program_path: &Path, program_id: Pubkey) -> Files {
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+use anchor_spl::{{
+    associated_token::AssociatedToken,
+    metadata::{{
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mpl_token_metadata::{{self, types::DataV2}},
+        CreateMasterEditionV3, CreateMetadataAccountsV3,
+    }},
+    token::{{mint_to, Mint, MintTo, Token, TokenAccount}},
+}};
+
+declare_id!("{}");
+
+#[program]
+pub mod {} {{
+    use super::*;
+
+    pub fn mint_nft(ctx: Context<MintNft>, params: NftParams) -> Result<()> {{
+        // Mint the one and only token of the NFT to the payer
+        mint_to(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {{
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_account.to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                }},
+            ),
+            1,
+        )?;
+
+        // Attach the metadata to the mint
+        create_metadata_accounts_v3(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {{
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    mint_authority: ctx.accounts.payer.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    update_authority: ctx.accounts.payer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                }},
+            ),
+            DataV2 {{
+                name: params.name,
+                symbol: params.symbol,
+                uri: params.uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            }},
+            false,
+            true,
+            None,
+        )?;
+
+        // The master edition takes over the mint authority, so no other token can be minted
+        create_master_edition_v3(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMasterEditionV3 {{
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    update_authority: ctx.accounts.payer.to_account_info(),
+                    mint_authority: ctx.accounts.payer.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                }},
+            ),
+            Some(0),
+        )?;
+
+        msg!("NFT minted successfully.");
+
+        Ok(())
+    }}
+}}
+
+// Struct defining the context for minting the NFT
+#[derive(Accounts)]
+pub struct MintNft<'info> {{
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = payer,
+        mint::freeze_authority = payer,
+    )]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = payer,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK: New Metaplex account being created, validated by the Metaplex program. The seeds
+    /// constraint still makes sure it is the metadata PDA of this mint.
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: New Metaplex account being created, validated by the Metaplex program. The seeds
+    /// constraint still makes sure it is the master edition PDA of this mint.
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition",
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: Constrained to the Metaplex program ID, so a malicious program can't be passed in
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}}
+
+// Struct defining the metadata of the NFT
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct NftParams {{
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}}
+"#,
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
+}
+
+/// Create a program with staking template
+fn create_program_template_staking(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+    error_base: Option<u32>,
+) -> Files {
+    let error_code = error_code_attribute(error_base);
+
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+use anchor_spl::{{
+    associated_token::AssociatedToken,
+    token::{{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer}},
+}};
+
+declare_id!("{}");
+
+// `reward_rate` is the reward per staked token and slot, in millionths
+pub const RATE_PRECISION: u128 = 1_000_000;
+
+#[program]
+pub mod {} {{
+    use super::*;
+
+    pub fn initialize_pool(ctx: Context<InitializePool>, reward_rate: u64) -> Result<()> {{
+        ctx.accounts.pool.set_inner(StakePool {{
+            authority: ctx.accounts.authority.key(),
+            stake_mint: ctx.accounts.stake_mint.key(),
+            reward_mint: ctx.accounts.reward_mint.key(),
+            reward_rate,
+            total_staked: 0,
+            bump: ctx.bumps.pool,
+        }});
+        Ok(())
+    }}
+
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {{
+        require!(amount > 0, StakingError::ZeroAmount);
+
+        let slot = Clock::get()?.slot;
+        let reward_rate = ctx.accounts.pool.reward_rate;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.owner = ctx.accounts.user.key();
+        stake_account.pool = ctx.accounts.pool.key();
+        stake_account.bump = ctx.bumps.stake_account;
+        stake_account.accrue(reward_rate, slot);
+        stake_account.amount += amount;
+        ctx.accounts.pool.total_staked += amount;
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.user_ata.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                }},
+            ),
+            amount,
+        )
+    }}
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {{
+        require!(amount > 0, StakingError::ZeroAmount);
+        require!(
+            amount <= ctx.accounts.stake_account.amount,
+            StakingError::InsufficientStake
+        );
+
+        let slot = Clock::get()?.slot;
+        let reward_rate = ctx.accounts.pool.reward_rate;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.accrue(reward_rate, slot);
+        stake_account.amount -= amount;
+        ctx.accounts.pool.total_staked -= amount;
+
+        // The pool PDA signs the release of the staked tokens
+        let stake_mint = ctx.accounts.stake_mint.key();
+        let seeds = &[
+            b"pool".as_ref(),
+            stake_mint.as_ref(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let signer = [&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {{
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                }},
+                &signer,
+            ),
+            amount,
+        )
+    }}
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {{
+        let slot = Clock::get()?.slot;
+        let reward_rate = ctx.accounts.pool.reward_rate;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.accrue(reward_rate, slot);
+        let rewards = stake_account.pending_rewards;
+        require!(rewards > 0, StakingError::NothingToClaim);
+        stake_account.pending_rewards = 0;
+
+        // The pool PDA is the authority of the reward mint
+        let stake_mint = ctx.accounts.pool.stake_mint;
+        let seeds = &[
+            b"pool".as_ref(),
+            stake_mint.as_ref(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let signer = [&seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {{
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.user_reward_ata.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                }},
+                &signer,
+            ),
+            rewards,
+        )
     }}
+}}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {{
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = StakePool::space(),
+        seeds = [b"pool", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward", pool.key().as_ref()],
+        bump,
+        mint::decimals = stake_mint.decimals,
+        mint::authority = pool
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = stake_mint,
+        associated_token::authority = pool
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {{
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = stake_mint,
+        seeds = [b"pool", stake_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = StakeAccount::space(),
+        seeds = [b"stake", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut, associated_token::mint = stake_mint, associated_token::authority = user)]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    #[account(mut, associated_token::mint = stake_mint, associated_token::authority = pool)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {{
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = stake_mint,
+        seeds = [b"pool", stake_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut, associated_token::mint = stake_mint, associated_token::authority = user)]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    #[account(mut, associated_token::mint = stake_mint, associated_token::authority = pool)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {{
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(has_one = reward_mint, seeds = [b"pool", pool.stake_mint.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(mut)]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user
+    )]
+    pub user_reward_ata: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}}
+
+#[account]
+pub struct StakePool {{
+    pub authority: Pubkey,
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_rate: u64,
+    pub total_staked: u64,
+    pub bump: u8,
+}}
+
+impl StakePool {{
+    pub fn space() -> usize {{
+        8 +  // discriminator
+        32 + // authority
+        32 + // stake_mint
+        32 + // reward_mint
+        8 +  // reward_rate
+        8 +  // total_staked
+        1 // bump
+    }}
+}}
+
+#[account]
+pub struct StakeAccount {{
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub pending_rewards: u64,
+    pub last_update_slot: u64,
+    pub bump: u8,
+}}
+
+impl StakeAccount {{
+    pub fn space() -> usize {{
+        8 +  // discriminator
+        32 + // owner
+        32 + // pool
+        8 +  // amount
+        8 +  // pending_rewards
+        8 +  // last_update_slot
+        1 // bump
+    }}
+
+    /// Add the rewards of the slots elapsed since the last update to the pending rewards.
+    pub fn accrue(&mut self, reward_rate: u64, slot: u64) {{
+        let elapsed = slot.saturating_sub(self.last_update_slot) as u128;
+        let rewards = self.amount as u128 * reward_rate as u128 * elapsed / RATE_PRECISION;
+        self.pending_rewards += rewards as u64;
+        self.last_update_slot = slot;
+    }}
+}}
+
+{error_code}
+pub enum StakingError {{
+    #[msg("The amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("The amount is greater than the staked amount")]
+    InsufficientStake,
+    #[msg("No rewards have accrued yet")]
+    NothingToClaim,
+}}
+"#,
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
+}
+
+/// Create a program with multisig template
+fn create_program_template_multisig(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+    error_base: Option<u32>,
+) -> Files {
+    let error_code = error_code_attribute(error_base);
+
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{{
+    instruction::{{AccountMeta, Instruction}},
+    program::invoke_signed,
+}};
+
+declare_id!("{}");
+
+/// Maximum number of owners of a multisig
+pub const MAX_OWNERS: usize = 10;
+
+#[program]
+pub mod {} {{
+    use super::*;
+
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {{
+        require!(
+            !owners.is_empty() && owners.len() <= MAX_OWNERS,
+            MultisigError::InvalidOwners
+        );
+        for (i, owner) in owners.iter().enumerate() {{
+            require!(!owners[..i].contains(owner), MultisigError::DuplicateOwner);
+        }}
+        require!(
+            threshold > 0 && threshold as usize <= owners.len(),
+            MultisigError::InvalidThreshold
+        );
+
+        ctx.accounts.multisig.set_inner(Multisig {{
+            create_key: ctx.accounts.create_key.key(),
+            owners,
+            threshold,
+            transaction_count: 0,
+            bump: ctx.bumps.multisig,
+        }});
+        Ok(())
+    }}
+
+    pub fn propose_transaction(
+        ctx: Context<ProposeTransaction>,
+        program_id: Pubkey,
+        accounts: Vec<TransactionAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {{
+        let multisig = &mut ctx.accounts.multisig;
+        let owner_index = multisig.owner_index(&ctx.accounts.proposer.key())?;
+
+        // Proposing a transaction approves it
+        let mut approvals = vec![false; multisig.owners.len()];
+        approvals[owner_index] = true;
+        ctx.accounts.transaction.set_inner(Transaction {{
+            multisig: multisig.key(),
+            program_id,
+            accounts,
+            data,
+            approvals,
+            executed: false,
+        }});
+        multisig.transaction_count += 1;
+        Ok(())
+    }}
+
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {{
+        let owner_index = ctx
+            .accounts
+            .multisig
+            .owner_index(&ctx.accounts.owner.key())?;
+        ctx.accounts.transaction.approvals[owner_index] = true;
+        Ok(())
+    }}
+
+    pub fn execute(ctx: Context<Execute>) -> Result<()> {{
+        let multisig = &ctx.accounts.multisig;
+        multisig.owner_index(&ctx.accounts.owner.key())?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        let approvals = transaction
+            .approvals
+            .iter()
+            .filter(|approved| **approved)
+            .count();
+        require!(
+            approvals >= multisig.threshold as usize,
+            MultisigError::NotEnoughApprovals
+        );
+        transaction.executed = true;
+
+        // The multisig PDA signs for the accounts of the instruction marked as signers, the
+        // accounts and the called program are passed as remaining accounts
+        let instruction = Instruction {{
+            program_id: transaction.program_id,
+            accounts: transaction.accounts.iter().map(Into::into).collect(),
+            data: transaction.data.clone(),
+        }};
+        let seeds = &[
+            b"multisig".as_ref(),
+            multisig.create_key.as_ref(),
+            &[multisig.bump],
+        ];
+        invoke_signed(&instruction, ctx.remaining_accounts, &[&seeds[..]])?;
+        Ok(())
+    }}
+}}
+
+#[derive(Accounts)]
+#[instruction(owners: Vec<Pubkey>)]
+pub struct CreateMultisig<'info> {{
+    #[account(
+        init,
+        payer = payer,
+        space = Multisig::space(owners.len()),
+        seeds = [b"multisig", create_key.key().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: Any public key, it only makes the multisig address unique
+    pub create_key: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<TransactionAccount>, data: Vec<u8>)]
+pub struct ProposeTransaction<'info> {{
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Transaction::space(multisig.owners.len(), accounts.len(), data.len()),
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &multisig.transaction_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {{
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        has_one = multisig,
+        constraint = !transaction.executed @ MultisigError::AlreadyExecuted
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub owner: Signer<'info>,
+}}
+
+#[derive(Accounts)]
+pub struct Execute<'info> {{
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        has_one = multisig,
+        constraint = !transaction.executed @ MultisigError::AlreadyExecuted
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub owner: Signer<'info>,
+}}
+
+#[account]
+pub struct Multisig {{
+    pub create_key: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub transaction_count: u64,
+    pub bump: u8,
+}}
+
+impl Multisig {{
+    /// Size of a multisig account with `owners` owners
+    pub fn space(owners: usize) -> usize {{
+        8 + 32 + (4 + 32 * owners) + 1 + 8 + 1
+    }}
+
+    fn owner_index(&self, key: &Pubkey) -> Result<usize> {{
+        self.owners
+            .iter()
+            .position(|owner| owner == key)
+            .ok_or_else(|| error!(MultisigError::NotAnOwner))
+    }}
+}}
+
+#[account]
+pub struct Transaction {{
+    pub multisig: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<TransactionAccount>,
+    pub data: Vec<u8>,
+    /// Whether each owner approved the transaction, in the order of `Multisig::owners`
+    pub approvals: Vec<bool>,
+    pub executed: bool,
+}}
+
+impl Transaction {{
+    /// Size of a transaction account of a multisig with `owners` owners, calling an
+    /// instruction with `accounts` accounts and `data_len` bytes of data
+    pub fn space(owners: usize, accounts: usize, data_len: usize) -> usize {{
+        let accounts = 4 + TransactionAccount::SPACE * accounts;
+        8 + 32 + 32 + accounts + (4 + data_len) + (4 + owners) + 1
+    }}
+}}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransactionAccount {{
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}}
+
+impl TransactionAccount {{
+    pub const SPACE: usize = 32 + 1 + 1;
+}}
+
+impl From<&TransactionAccount> for AccountMeta {{
+    fn from(account: &TransactionAccount) -> Self {{
+        AccountMeta {{
+            pubkey: account.pubkey,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        }}
+    }}
+}}
+
+{error_code}
+pub enum MultisigError {{
+    #[msg("A multisig needs between 1 and 10 owners")]
+    InvalidOwners,
+    #[msg("The owners must be distinct")]
+    DuplicateOwner,
+    #[msg("The threshold must be between 1 and the number of owners")]
+    InvalidThreshold,
+    #[msg("The signer is not an owner of the multisig")]
+    NotAnOwner,
+    #[msg("The transaction doesn't have enough approvals")]
+    NotEnoughApprovals,
+    #[msg("The transaction was already executed")]
+    AlreadyExecuted,
+}}
+"#,
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
+}
+
+/// Create a program with Token-2022 mint template
+fn create_program_template_token_2022(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+) -> Files {
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+use anchor_spl::{{
+    associated_token::AssociatedToken,
+    token_2022::Token2022,
+    token_interface::{{mint_to, Mint, MintTo, TokenAccount}},
+}};
+
+declare_id!("{}");
+
+#[program]
+pub mod {} {{
+    use super::*;
+
+    pub fn init_token(ctx: Context<InitToken>, decimals: u8) -> Result<()> {{
+        msg!(
+            "Token-2022 mint {{}} created with {{}} decimals.",
+            ctx.accounts.mint.key(),
+            decimals
+        );
+        Ok(())
+    }}
+
+    pub fn mint_tokens(ctx: Context<MintTokens>, quantity: u64) -> Result<()> {{
+        // The mint PDA is its own mint authority
+        let seeds = &["mint".as_bytes(), &[ctx.bumps.mint]];
+        let signer = [&seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {{
+                    authority: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                }},
+                &signer,
+            ),
+            quantity,
+        )?;
+
+        Ok(())
+    }}
+}}
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct InitToken<'info> {{
+    #[account(
+        init,
+        seeds = [b"mint"],
+        bump,
+        payer = payer,
+        mint::decimals = decimals,
+        mint::authority = mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}}
+
+#[derive(Accounts)]
+pub struct MintTokens<'info> {{
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump,
+        mint::authority = mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Only used as the authority of the destination token account, which the
+    /// `associated_token::authority` constraint checks. No data is read from it.
+    pub recipient: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}}
+"#,
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
+}
+
+/// Create a program with Token-2022 transfer hook template
+fn create_program_template_transfer_hook(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+) -> Files {
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{{Mint, TokenAccount}};
+use spl_tlv_account_resolution::{{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+}};
+use spl_transfer_hook_interface::instruction::{{ExecuteInstruction, TransferHookInstruction}};
+
+declare_id!("{}");
+
+#[program]
+pub mod {} {{
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {{
+        // Token-2022 reads the extra accounts of `execute` from this list on every transfer
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas()?,
+        )?;
+        Ok(())
+    }}
+
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {{
+        let counter = &mut ctx.accounts.counter;
+        counter.transfers += 1;
+        counter.amount = counter.amount.saturating_add(amount);
+        msg!("Transfer {{}} of {{}} tokens", counter.transfers, amount);
+        Ok(())
+    }}
+
+    // Token-2022 calls the hook with the `execute` discriminator of the transfer hook interface
+    // instead of Anchor's, so it is routed to `transfer_hook` here
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {{
+        match TransferHookInstruction::unpack(data)? {{
+            TransferHookInstruction::Execute {{ amount }} => {{
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
+            }}
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }}
+    }}
+}}
+
+/// Extra accounts passed to `execute` after the ones of the interface: the counter of the mint
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {{
+    Ok(vec![ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal {{
+                bytes: b"counter".to_vec(),
+            }},
+            // The mint is the second account of `execute`
+            Seed::AccountKey {{ index: 1 }},
+        ],
+        false,
+        true,
+    )?])
+}}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {{
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: List of the extra accounts read by Token-2022, the seeds are fixed by the interface
+    #[account(
+        init,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        payer = payer
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        seeds = [b"counter", mint.key().as_ref()],
+        bump,
+        space = 8 + Counter::INIT_SPACE,
+        payer = payer
+    )]
+    pub counter: Account<'info, Counter>,
+    pub system_program: Program<'info, System>,
+}}
+
+// The order of the accounts is fixed by the transfer hook interface
+#[derive(Accounts)]
+pub struct TransferHook<'info> {{
+    #[account(token::mint = mint, token::authority = owner)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Owner of the source token account, checked by the `token::authority` constraint
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: List of the extra accounts, only its address is checked
+    #[account(seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"counter", mint.key().as_ref()], bump)]
+    pub counter: Account<'info, Counter>,
+}}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Counter {{
+    pub transfers: u64,
+    pub amount: u64,
+}}
+"#,
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
+}
+
+/// Create a program with CRUD template
+fn create_program_template_crud(
+    name: &str,
+    program_path: &Path,
+    program_id: Pubkey,
+    error_base: Option<u32>,
+) -> Files {
+    let error_code = error_code_attribute(error_base);
+
+    vec![(
+        program_path.join("src").join("lib.rs"),
+        format!(
+            r#"use anchor_lang::prelude::*;
+
+declare_id!("{}");
+
+/// Maximum length in bytes of the title of an entry
+pub const MAX_TITLE_LEN: usize = 50;
+/// Maximum length in bytes of the message of an entry
+pub const MAX_MESSAGE_LEN: usize = 280;
+
+#[program]
+pub mod {} {{
+    use super::*;
+
+    pub fn create_entry(ctx: Context<CreateEntry>, title: String, message: String) -> Result<()> {{
+        require!(title.len() <= MAX_TITLE_LEN, CrudError::TitleTooLong);
+        require!(message.len() <= MAX_MESSAGE_LEN, CrudError::MessageTooLong);
+
+        ctx.accounts.entry.set_inner(Entry {{
+            owner: ctx.accounts.owner.key(),
+            title,
+            message,
+            bump: ctx.bumps.entry,
+        }});
+        Ok(())
+    }}
+
+    pub fn update_entry(ctx: Context<UpdateEntry>, message: String) -> Result<()> {{
+        require!(message.len() <= MAX_MESSAGE_LEN, CrudError::MessageTooLong);
+
+        ctx.accounts.entry.message = message;
+        Ok(())
+    }}
+
+    pub fn delete_entry(_ctx: Context<DeleteEntry>) -> Result<()> {{
+        // The `close` constraint refunds the rent of the entry to its owner
+        Ok(())
+    }}
+}}
+
+#[derive(Accounts)]
+pub struct CreateEntry<'info> {{
+    // One entry per user, derived from the owner's public key
+    #[account(
+        init,
+        seeds = [b"entry", owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + Entry::INIT_SPACE
+    )]
+    pub entry: Account<'info, Entry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}}
+
+#[derive(Accounts)]
+pub struct UpdateEntry<'info> {{
+    #[account(
+        mut,
+        seeds = [b"entry", owner.key().as_ref()],
+        bump = entry.bump,
+        has_one = owner
+    )]
+    pub entry: Account<'info, Entry>,
+
+    pub owner: Signer<'info>,
+}}
+
+#[derive(Accounts)]
+pub struct DeleteEntry<'info> {{
+    #[account(
+        mut,
+        seeds = [b"entry", owner.key().as_ref()],
+        bump = entry.bump,
+        has_one = owner,
+        close = owner
+    )]
+    pub entry: Account<'info, Entry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Entry {{
+    pub owner: Pubkey,
+    // `max_len` sizes the account for the longest strings accepted by the program
+    #[max_len(MAX_TITLE_LEN)]
+    pub title: String,
+    #[max_len(MAX_MESSAGE_LEN)]
+    pub message: String,
+    pub bump: u8,
+}}
+
+{error_code}
+pub enum CrudError {{
+    #[msg("The title is longer than 50 bytes")]
+    TitleTooLong,
+    #[msg("The message is longer than 280 bytes")]
+    MessageTooLong,
+}}
+"#,
+            program_id,
+            name.to_snake_case(),
+        ),
+    )]
+}
+
+/// Cargo.toml of the workspace. Its release profile is optimized for size and speed, or for
+/// build time with `fast_build`.
+pub fn workspace_manifest(fast_build: bool) -> String {
+    let (lto, codegen_units) = if fast_build {
+        (Lto::Enabled(false), 16)
+    } else {
+        (Lto::Mode("fat".into()), 1)
+    };
+    to_toml(&WorkspaceManifest {
+        workspace: Workspace {
+            members: vec!["programs/*".into()],
+            resolver: "2".into(),
+        },
+        profile: Profiles {
+            release: ReleaseProfile {
+                overflow_checks: true,
+                lto,
+                codegen_units,
+                build_override: BuildOverride {
+                    opt_level: 3,
+                    incremental: false,
+                    codegen_units,
+                },
+            },
+        },
+    })
+}
+
+/// Cargo.toml of a program depending only on `anchor-lang`.
+fn program_manifest(name: &str, anchor_version: &str) -> ProgramManifest {
+    let features = [
+        ("default", vec![]),
+        ("cpi", vec!["no-entrypoint"]),
+        ("no-entrypoint", vec![]),
+        ("no-idl", vec![]),
+        ("no-log-ix-name", vec![]),
+        ("idl-build", vec!["anchor-lang/idl-build"]),
+    ];
+
+    ProgramManifest {
+        package: Package {
+            name: name.into(),
+            version: "0.1.0".into(),
+            description: "Created with Anchor".into(),
+            edition: "2021".into(),
+        },
+        lib: Lib {
+            crate_type: vec!["cdylib".into(), "lib".into()],
+            name: name.to_snake_case(),
+        },
+        features: features
+            .into_iter()
+            .map(|(feature, deps)| (feature.into(), deps.into_iter().map(Into::into).collect()))
+            .collect(),
+        dependencies: [(
+            "anchor-lang".to_string(),
+            Dependency::Version(anchor_version.into()),
+        )]
+        .into(),
+    }
+}
+
+/// Release of the SPL transfer hook crates matching the Token-2022 version of `anchor-spl`
+const SPL_TRANSFER_HOOK_VERSION: &str = "0.6.3";
+
+/// Dependencies of a template's program, on top of `anchor-lang`.
+struct ProgramDependencies {
+    /// Features of `anchor-lang`
+    anchor_lang: &'static [&'static str],
+    /// Features of `anchor-spl`, `None` when the program doesn't depend on it
+    anchor_spl: Option<&'static [&'static str]>,
+    /// Other crates and their versions
+    crates: &'static [(&'static str, &'static str)],
+}
+
+fn program_dependencies(template: ProgramTemplate) -> ProgramDependencies {
+    let (anchor_lang, anchor_spl, crates): (_, Option<&[&str]>, &[_]) = match template {
+        ProgramTemplate::Basic
+        | ProgramTemplate::Counter
+        | ProgramTemplate::Pausable
+        | ProgramTemplate::PdaSigner
+        | ProgramTemplate::Multisig
+        | ProgramTemplate::Crud => (&[][..], None, &[]),
+        ProgramTemplate::MintToken => (&["init-if-needed"][..], Some(&["metadata"]), &[]),
+        ProgramTemplate::Escrow | ProgramTemplate::Vesting | ProgramTemplate::Staking => {
+            (&["init-if-needed"][..], Some(&[]), &[])
+        }
+        ProgramTemplate::Nft => (&[][..], Some(&["metadata"]), &[]),
+        ProgramTemplate::Token2022 => (&["init-if-needed"][..], Some(&["token_2022"]), &[]),
+        ProgramTemplate::TransferHook => (
+            &[][..],
+            Some(&["token_2022"]),
+            &[
+                ("spl-transfer-hook-interface", SPL_TRANSFER_HOOK_VERSION),
+                ("spl-tlv-account-resolution", SPL_TRANSFER_HOOK_VERSION),
+            ],
+        ),
+    };
+
+    ProgramDependencies {
+        anchor_lang,
+        anchor_spl,
+        crates,
+    }
+}
+
+fn cargo_toml(name: &str, template: ProgramTemplate, anchor_version: &str) -> String {
+    let dependencies = program_dependencies(template);
+    let anchor_dependency = |features: &[&str]| {
+        if features.is_empty() {
+            Dependency::Version(anchor_version.into())
+        } else {
+            Dependency::Detailed {
+                version: anchor_version.into(),
+                features: features.iter().map(ToString::to_string).collect(),
+            }
+        }
+    };
+
+    let mut manifest = program_manifest(name, anchor_version);
+    manifest.dependencies.insert(
+        "anchor-lang".into(),
+        anchor_dependency(dependencies.anchor_lang),
+    );
+    if let Some(features) = dependencies.anchor_spl {
+        manifest.features.insert(
+            "idl-build".into(),
+            vec![
+                "anchor-lang/idl-build".into(),
+                "anchor-spl/idl-build".into(),
+            ],
+        );
+        manifest
+            .dependencies
+            .insert("anchor-spl".into(), anchor_dependency(features));
+    }
+    for (name, version) in dependencies.crates {
+        manifest
+            .dependencies
+            .insert(name.to_string(), Dependency::Version(version.to_string()));
+    }
+    to_toml(&manifest)
+}
+
+fn xargo_toml() -> &'static str {
+    r#"[target.bpfel-unknown-unknown.dependencies.std]
+features = []
+"#
+}
+
+/// `solana-security-txt` release added by `--with-security-txt`
+const SECURITY_TXT_VERSION: &str = "1.1.1";
+
+/// Content of the file at `path` among the program files.
+fn file_mut<'a>(files: &'a mut Files, path: &Path) -> Result<&'a mut String> {
+    files
+        .iter_mut()
+        .find(|(file_path, _)| file_path == path)
+        .map(|(_, content)| content)
+        .ok_or_else(|| anyhow!("{} is not generated", path.display()))
+}
+
+/// Embed a `security.txt` in the program files with the `security_txt!` macro.
+pub fn add_security_txt(files: &mut Files, name: &str, contact: Option<&str>) -> Result<()> {
+    let program_path = Path::new("programs").join(name);
+
+    let cargo_toml = file_mut(files, &program_path.join("Cargo.toml"))?;
+    let mut manifest = cargo_toml.parse::<toml::Table>()?;
+    if let Some(toml::Value::Table(dependencies)) = manifest.get_mut("dependencies") {
+        dependencies.insert("solana-security-txt".into(), SECURITY_TXT_VERSION.into());
+    }
+    *cargo_toml = to_toml(&manifest);
+
+    let lib_rs_path = program_path.join("src").join("lib.rs");
+    let lib_rs = file_mut(files, &lib_rs_path)?;
+    let declare_id_end = lib_rs
+        .find("declare_id!")
+        .and_then(|start| lib_rs[start..].find('\n').map(|end| start + end + 1))
+        .ok_or_else(|| anyhow!("No `declare_id!` found in {}", lib_rs_path.display()))?;
+    let contact = contact.unwrap_or("security@example.com");
+    let security_txt = format!(
+        r#"
+// Fill in the project details before deploying, see https://github.com/neodyme-labs/solana-security-txt
+#[cfg(not(feature = "no-entrypoint"))]
+solana_security_txt::security_txt! {{
+    name: "{name}",
+    project_url: "https://example.com",
+    contacts: "email:{contact}",
+    policy: "https://example.com/SECURITY.md"
+}}
+"#
+    );
+    lib_rs.insert_str(declare_id_end, &security_txt);
+
+    Ok(())
+}
+
+const LITESVM_VERSION: &str = "0.1.0";
+
+/// Add a Rust integration test running the program in LiteSVM to the program files, along with
+/// its dev-dependencies.
+pub fn add_rust_tests(
+    files: &mut Files,
+    name: &str,
+    template: ProgramTemplate,
+    pda_from: Option<PdaSeed>,
+) -> Result<()> {
+    let program_path = Path::new("programs").join(name);
+
+    let cargo_toml = file_mut(files, &program_path.join("Cargo.toml"))?;
+    let mut manifest = cargo_toml.parse::<toml::Table>()?;
+    let dev_dependencies = toml::Table::from_iter([
+        ("litesvm".to_string(), LITESVM_VERSION.into()),
+        ("solana-sdk".to_string(), SOLANA_VERSION.into()),
+    ]);
+    manifest.insert("dev-dependencies".into(), dev_dependencies.into());
+    *cargo_toml = to_toml(&manifest);
+
+    files.push((
+        program_path
+            .join("tests")
+            .join(format!("test_{}.rs", name.to_snake_case())),
+        rust_test(name, template, pda_from),
+    ));
+
+    Ok(())
+}
+
+/// Rust integration test of the program. The counter and basic templates call their
+/// instructions, the others only check that the program loads.
+fn rust_test(name: &str, template: ProgramTemplate, pda_from: Option<PdaSeed>) -> String {
+    let snake = name.to_snake_case();
+    let (imports, tests) = match template {
+        ProgramTemplate::Basic => (
+            "use anchor_lang::{InstructionData, ToAccountMetas};\n".to_string(),
+            format!(
+                r#"
+#[test]
+fn test_initialize() {{
+    let (mut svm, user) = setup();
+
+    send(
+        &mut svm,
+        &user,
+        {snake}::instruction::Initialize {{}}.data(),
+        {snake}::accounts::Initialize {{}}.to_account_metas(None),
+    );
+}}
+"#
+            ),
+        ),
+        ProgramTemplate::Counter => {
+            let counter = match pda_from {
+                None => r#" Pubkey::find_program_address(&[b"counter"], &PROGRAM_ID)"#,
+                Some(PdaSeed::User) => {
+                    r#"
+        Pubkey::find_program_address(&[b"counter", user.pubkey().as_ref()], &PROGRAM_ID)"#
+                }
+            };
+            (
+                "use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};\n"
+                    .to_string(),
+                format!(
+                    r#"
+#[test]
+fn test_increment() {{
+    let (mut svm, user) = setup();
+    let (counter, _) ={counter};
+
+    send(
+        &mut svm,
+        &user,
+        {snake}::instruction::Initialize {{}}.data(),
+        {snake}::accounts::Initialize {{
+            counter,
+            user: user.pubkey(),
+            system_program: system_program::ID,
+        }}
+        .to_account_metas(None),
+    );
+    send(
+        &mut svm,
+        &user,
+        {snake}::instruction::Increment {{}}.data(),
+        {snake}::accounts::Increment {{
+            counter,
+            user: user.pubkey(),
+            system_program: system_program::ID,
+        }}
+        .to_account_metas(None),
+    );
+
+    let account = svm.get_account(&counter).unwrap();
+    let mut data = account.data.as_slice();
+    let counter_account = {snake}::Counter::try_deserialize(&mut data).unwrap();
+    assert_eq!(counter_account.count, 1);
+}}
+"#
+                ),
+            )
+        }
+        ProgramTemplate::MintToken
+        | ProgramTemplate::Pausable
+        | ProgramTemplate::PdaSigner
+        | ProgramTemplate::Escrow
+        | ProgramTemplate::Vesting
+        | ProgramTemplate::Nft
+        | ProgramTemplate::Staking
+        | ProgramTemplate::Multisig
+        | ProgramTemplate::Token2022
+        | ProgramTemplate::TransferHook
+        | ProgramTemplate::Crud => (
+            String::new(),
+            r#"
+// Add tests calling the program's instructions with `send`
+#[test]
+fn test_program_loads() {
+    let (svm, _user) = setup();
+
+    assert!(svm.get_account(&PROGRAM_ID).unwrap().executable);
+}
+"#
+            .to_string(),
+        ),
+    };
+
+    format!(
+        r#"#![allow(dead_code, unused_imports)]
+
+{imports}use litesvm::LiteSVM;
+use solana_sdk::{{
+    instruction::{{AccountMeta, Instruction}},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+}};
+use std::path::Path;
+
+const PROGRAM_ID: Pubkey = {snake}::ID;
+
+// Built by `anchor build` in target/deploy, which must run before `cargo test`
+const PROGRAM_SO: &str = "{snake}.so";
+
+/// Start a LiteSVM instance with the program deployed and a funded user.
+fn setup() -> (LiteSVM, Keypair) {{
+    let mut svm = LiteSVM::new();
+    let program_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../target/deploy")
+        .join(PROGRAM_SO);
+    svm.add_program_from_file(PROGRAM_ID, program_path).unwrap();
+
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), 1_000_000_000).unwrap();
+
+    (svm, user)
+}}
+
+/// Send an instruction of the program signed and paid by the user.
+fn send(svm: &mut LiteSVM, user: &Keypair, data: Vec<u8>, accounts: Vec<AccountMeta>) {{
+    let instruction = Instruction {{
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    }};
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&user.pubkey()),
+        &[user],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(transaction).unwrap();
+}}
+{tests}"#
+    )
+}
+
+/// Read the program keypair file or create a new one if it doesn't exist.
+pub fn get_or_create_program_id(name: &str) -> Pubkey {
+    let keypair_path = Path::new("target")
+        .join("deploy")
+        .join(format!("{}-keypair.json", name.to_snake_case()));
+
+    get_or_create_keypair(&keypair_path)
+}
+
+/// Read the keypair at `keypair_path` or create it. Concurrent callers converge on a single
+/// keypair: they take an advisory lock next to it, and only the first one creates it.
+fn get_or_create_keypair(keypair_path: &Path) -> Pubkey {
+    if let Ok(keypair) = read_keypair_file(keypair_path) {
+        return keypair.pubkey();
+    }
+
+    let file_name = keypair_path
+        .file_name()
+        .expect("Keypair path has a file name")
+        .to_string_lossy();
+    if let Some(dir) = keypair_path.parent() {
+        fs::create_dir_all(dir).expect("Unable to create program keypair directory");
+    }
+    let lock_path = keypair_path.with_file_name(format!(".{file_name}.lock"));
+    let lock = File::create(&lock_path).expect("Unable to create program keypair lock");
+    lock.lock_exclusive()
+        .expect("Unable to lock program keypair");
+
+    let pubkey = if keypair_path.exists() {
+        read_keypair_file(keypair_path)
+            .expect("Unable to read program keypair")
+            .pubkey()
+    } else {
+        // Written to a temporary file and renamed into place, so that callers reading it
+        // without the lock never see a partial keypair
+        let keypair = Keypair::new();
+        let tmp_path = keypair_path.with_file_name(format!(".{file_name}.tmp"));
+        write_keypair_file(&keypair, &tmp_path).expect("Unable to create program keypair");
+        fs::rename(&tmp_path, keypair_path).expect("Unable to create program keypair");
+        keypair.pubkey()
+    };
+    // The keypair exists by now, so callers locking another lock file after its removal
+    // only read it
+    let _ = fs::remove_file(&lock_path);
+    let _ = lock.unlock();
+
+    pubkey
+}
+
+/// Anchor.toml declaring the program `name` on each of `clusters`.
+fn anchor_toml(
+    name: &str,
+    program_id: String,
+    test_script: String,
+    wallet: &str,
+    clusters: &[&str],
+    provider_cluster: &str,
+) -> AnchorToml {
+    // Deploying to a named provider cluster needs the program listed under it, unlike a URL.
+    // Anchor lists the programs of mainnet-beta under `mainnet`, like `--clusters` does
+    let mut clusters = clusters.to_vec();
+    let provider_key = match provider_cluster.to_lowercase().as_str() {
+        "mainnet-beta" => "mainnet".to_string(),
+        cluster => cluster.to_string(),
+    };
+    if !provider_cluster.contains("://") && !clusters.contains(&provider_key.as_str()) {
+        clusters.push(&provider_key);
+    }
+    let programs = clusters
+        .iter()
+        .map(|cluster| {
+            let ids = [(name.to_snake_case(), program_id.clone())].into();
+            (cluster.to_string(), ids)
+        })
+        .collect();
+
+    AnchorToml {
+        toolchain: Toolchain::default(),
+        features: AnchorFeatures {
+            seeds: false,
+            skip_lint: false,
+        },
+        programs,
+        registry: Registry {
+            url: "https://api.apr.dev".into(),
+        },
+        provider: Provider {
+            cluster: provider_cluster.into(),
+            wallet: wallet.into(),
+        },
+        scripts: [("test".to_string(), test_script)].into(),
+    }
+}
+
+/// Anchor.toml of the workspace. The program is listed under `[programs.<cluster>]` for each of
+/// `clusters`, the template's clusters when empty.
+pub fn create_anchor_toml(
+    name: &str,
+    program_id: String,
+    test_script: String,
+    wallet: &str,
+    template: ProgramTemplate,
+    cluster: Option<&str>,
+    clusters: &[String],
+) -> String {
+    let clusters = if clusters.is_empty() {
+        template.program_clusters().to_vec()
+    } else {
+        clusters.iter().map(String::as_str).collect()
+    };
+    // Anchor spells the default local provider cluster `Localnet`
+    let provider_cluster = cluster.unwrap_or(match template.default_cluster() {
+        "localnet" => "Localnet",
+        cluster => cluster,
+    });
+
+    to_toml(&anchor_toml(
+        name,
+        program_id,
+        test_script,
+        wallet,
+        &clusters,
+        provider_cluster,
+    ))
+}
+
+/// Metaplex Token Metadata program used by the mint token and nft templates
+pub const TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// Anchor.toml section cloning the Metaplex metadata program from devnet into the local test
+/// validator, so that the mint token and nft tests can run on localnet.
+pub fn test_validator_clone_metaplex() -> String {
+    format!(
+        r#"
+[test.validator]
+url = "https://api.devnet.solana.com"
+
+[[test.validator.clone]]
+address = "{TOKEN_METADATA_PROGRAM_ID}"
+"#
+    )
+}
+
+/// Anchor.toml section overriding the provider cluster for the tests only. Anchor doesn't read
+/// it, the test script exports the same cluster as `ANCHOR_PROVIDER_URL`.
+pub fn test_provider(cluster: &str) -> String {
+    format!(
+        r#"
+[test.provider]
+cluster = "{cluster}"
+"#
+    )
+}
+
+pub fn ts_deploy_script() -> &'static str {
+    r#"// Migrations are an early feature. Currently, they're nothing more than this
+// single deploy script that's invoked from the CLI, injecting a provider
+// configured from the workspace's Anchor.toml.
+
+const anchor = require("@coral-xyz/anchor");
+
+module.exports = async function (provider) {
+  // Configure client to use the provider.
+  anchor.setProvider(provider);
+
+  // Add your deploy script here.
+};
+"#
+}
+
+/// package.json of the workspace, with the dependencies of the tests of each of `templates`, the
+/// first of which is the template of the workspace's own program.
+pub fn ts_package_json(
+    license: String,
+    templates: &[ProgramTemplate],
+    npm_scripts: bool,
+    cluster: &str,
+    anchor_version: &str,
+    javascript: bool,
+) -> String {
+    let mut scripts = serde_json::json!({
+        "lint:fix": "prettier */*.js \"*/**/*{.js,.ts}\" -w",
+        "lint": "prettier */*.js \"*/**/*{.js,.ts}\" --check",
+    });
+    if npm_scripts {
+        scripts
+            .as_object_mut()
+            .unwrap()
+            .extend(npm_scripts_json(templates[0], cluster));
+    }
+
+    let mut dependencies = serde_json::Map::new();
+    dependencies.insert(
+        "@coral-xyz/anchor".into(),
+        format!("^{anchor_version}").into(),
+    );
+    for (package, version) in templates
+        .iter()
+        .flat_map(|template| test_dependencies(*template))
+    {
+        dependencies
+            .entry(*package)
+            .or_insert_with(|| (*version).into());
+    }
+
+    let mut dev_dependencies = serde_json::json!({
+        "chai": "^4.3.4",
+        "mocha": "^9.0.3",
+        "ts-mocha": "^10.0.0",
+        "@types/bn.js": "^5.1.0",
+        "@types/chai": "^4.3.0",
+        "@types/mocha": "^9.0.0",
+        "typescript": "^4.3.5",
+        "prettier": "^2.6.2",
+    });
+    // JavaScript tests need neither the TypeScript compiler nor the type definitions
+    if javascript {
+        dev_dependencies
+            .as_object_mut()
+            .unwrap()
+            .retain(|package, _| {
+                package != "ts-mocha" && package != "typescript" && !package.starts_with("@types/")
+            });
+    }
+
+    let package_json = serde_json::json!({
+        "license": license,
+        "scripts": scripts,
+        "dependencies": dependencies,
+        "devDependencies": dev_dependencies,
+    });
+    format!("{}\n", serde_json::to_string_pretty(&package_json).unwrap())
+}
+
+/// `build`, `test`, `deploy` and `localnet` scripts of package.json wrapping anchor commands.
+fn npm_scripts_json(
+    template: ProgramTemplate,
+    cluster: &str,
+) -> serde_json::Map<String, serde_json::Value> {
+    // Programs on a live cluster are deployed separately before testing
+    let test = match template {
+        ProgramTemplate::MintToken | ProgramTemplate::Nft => {
+            format!("anchor test --skip-deploy --provider.cluster {cluster}")
+        }
+        _ => "anchor test".to_string(),
+    };
+
+    [
+        ("build", "anchor build".to_string()),
+        ("test", test),
+        (
+            "deploy",
+            format!("anchor deploy --provider.cluster {cluster}"),
+        ),
+        ("localnet", "solana-test-validator --reset".to_string()),
+    ]
+    .into_iter()
+    .map(|(script, command)| (script.to_string(), command.into()))
+    .collect()
+}
+
+/// npm packages the tests of a template use besides `@coral-xyz/anchor`, with their versions.
+fn test_dependencies(template: ProgramTemplate) -> &'static [(&'static str, &'static str)] {
+    match template {
+        ProgramTemplate::Basic => &[],
+        ProgramTemplate::Counter
+        | ProgramTemplate::MintToken
+        | ProgramTemplate::Pausable
+        | ProgramTemplate::PdaSigner
+        | ProgramTemplate::Nft
+        | ProgramTemplate::Multisig
+        | ProgramTemplate::Crud => &[("@solana/web3.js", "^1.92.3")],
+        ProgramTemplate::Escrow
+        | ProgramTemplate::Vesting
+        | ProgramTemplate::Staking
+        | ProgramTemplate::Token2022
+        | ProgramTemplate::TransferHook => &[("@solana/spl-token", "^0.4.6")],
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ts_mocha(
+    name: &str,
+    template: ProgramTemplate,
+    mint_recipient: Option<Pubkey>,
+    path_alias: Option<&str>,
+    pda_from: Option<PdaSeed>,
+    mints: Option<u8>,
+    mint_amount: Option<u64>,
+    commitment: Option<Commitment>,
+) -> String {
+    let types_path = types_import_path(name, path_alias);
+    match template {
+        ProgramTemplate::Basic => ts_mocha_basic(name, &types_path, commitment),
+        ProgramTemplate::Counter => ts_mocha_counter(name, &types_path, pda_from, commitment),
+        ProgramTemplate::MintToken if mints.is_some() => {
+            ts_mocha_multiple_mints(name, &types_path, mint_recipient, mint_amount, commitment)
+        }
+        ProgramTemplate::MintToken => {
+            ts_mocha_mint_token(name, &types_path, mint_recipient, mint_amount, commitment)
+        }
+        ProgramTemplate::Pausable => ts_mocha_pausable(name, &types_path, commitment),
+        ProgramTemplate::PdaSigner => ts_mocha_pda_signer(name, &types_path, commitment),
+        ProgramTemplate::Escrow => ts_mocha_escrow(name, &types_path, commitment),
+        ProgramTemplate::Vesting => ts_mocha_vesting(name, &types_path, commitment),
+        ProgramTemplate::Nft => ts_mocha_nft(name, &types_path, commitment),
+        ProgramTemplate::Staking => ts_mocha_staking(name, &types_path, commitment),
+        ProgramTemplate::Multisig => ts_mocha_multisig(name, &types_path, commitment),
+        ProgramTemplate::Token2022 => ts_mocha_token_2022(name, &types_path, commitment),
+        ProgramTemplate::TransferHook => ts_mocha_transfer_hook(name, &types_path, commitment),
+        ProgramTemplate::Crud => ts_mocha_crud(name, &types_path, commitment),
+    }
+}
+
+/// JavaScript test of the program, the same as `ts_mocha` without the TypeScript types.
+pub fn js_mocha(
+    name: &str,
+    template: ProgramTemplate,
+    mint_recipient: Option<Pubkey>,
+    pda_from: Option<PdaSeed>,
+    mints: Option<u8>,
+    mint_amount: Option<u64>,
+    commitment: Option<Commitment>,
+) -> String {
+    js_from_ts(&ts_mocha(
+        name,
+        template,
+        mint_recipient,
+        None,
+        pda_from,
+        mints,
+        mint_amount,
+        commitment,
+    ))
+}
+
+/// JavaScript version of a generated TypeScript test, with `require` calls instead of imports and
+/// without the program types. Only the TypeScript syntax of the generated tests is handled: `as`
+/// casts and the type annotations of `let` declarations and arrow function parameters.
+fn js_from_ts(ts: &str) -> String {
+    let default_import =
+        Regex::new(r#"^import (?:\* as )?(\w+) from (".*");$"#).expect("Valid regex");
+    let named_import = Regex::new(r#"^import \{\s*(.*?)\s*\} from (".*");$"#).expect("Valid regex");
+    let import_end = Regex::new(r#"^\} from (".*");$"#).expect("Valid regex");
+    let cast = Regex::new(r"\(([\w.]+) as [\w.]+\)| as (?:anchor\.)?[A-Z]\w*(?:<\w+>)?")
+        .expect("Valid regex");
+    let let_type = Regex::new(r"^(\s*let \w+): [^=;]+;$").expect("Valid regex");
+    let arrow_params = Regex::new(r"\(([^()]*)\) =>").expect("Valid regex");
+    let param_type = Regex::new(r"(\w+): [\w.]+").expect("Valid regex");
+
+    let mut js = String::new();
+    for line in ts.lines() {
+        // The program is typed by its generated types, untyped in JavaScript
+        if line == r#"import { Program } from "@coral-xyz/anchor";"#
+            || line.contains(r#" from "../target/types/"#)
+        {
+            continue;
+        }
+        let line = if line == "import {" {
+            "const {".to_string()
+        } else if let Some(import) = default_import.captures(line) {
+            format!("const {} = require({});", &import[1], &import[2])
+        } else if let Some(import) = named_import.captures(line) {
+            format!("const {{ {} }} = require({});", &import[1], &import[2])
+        } else if let Some(import) = import_end.captures(line) {
+            format!("}} = require({});", &import[1])
+        } else {
+            let line = cast.replace_all(line, "$1");
+            let line = let_type.replace(&line, "$1;");
+            arrow_params
+                .replace_all(&line, |params: &regex::Captures| {
+                    format!("({}) =>", param_type.replace_all(&params[1], "$1"))
+                })
+                .into_owned()
+        };
+        js.push_str(&line);
+        js.push('\n');
+    }
+
+    js
+}
+
+/// Expression creating the tests' provider with the given commitment, if any.
+fn ts_provider(commitment: Option<Commitment>) -> String {
+    match commitment {
+        Some(commitment) => format!(
+            r#"new anchor.AnchorProvider(
+    anchor.AnchorProvider.env().connection,
+    anchor.AnchorProvider.env().wallet,
+    {{ commitment: "{commitment}", preflightCommitment: "{commitment}" }}
+  )"#
+        ),
+        None => "anchor.AnchorProvider.env()".to_string(),
+    }
+}
+
+/// Import path of the program's generated types as seen from the `tests` directory, the given
+/// tsconfig.json path alias or a relative path without one.
+fn types_import_path(name: &str, path_alias: Option<&str>) -> String {
+    match path_alias {
+        Some(path_alias) => path_alias.to_string(),
+        None => format!("../target/types/{}", name.to_snake_case()),
+    }
+}
+
+/// Path alias of the generated types of a program other than the workspace's own, resolved by
+/// [`PROGRAM_TYPES_PATH_ALIAS`].
+pub fn program_types_path_alias(name: &str) -> String {
+    format!("{TYPES_PATH_ALIAS}/{}", name.to_snake_case())
+}
+
+pub fn ts_mocha_basic(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  anchor.setProvider({provider});
+
+  const program = anchor.workspace.{} as Program<{}>;
+
+  it("Is initialized!", async () => {{
+    // Add your test here.
+    const tx = await program.methods.initialize().rpc();
+    console.log("Your transaction signature", tx);
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_counter(
+    name: &str,
+    types_path: &str,
+    pda_from: Option<PdaSeed>,
+    commitment: Option<Commitment>,
+) -> String {
+    let provider = ts_provider(commitment);
+    // Must match the seeds of the counter account in the program
+    let seeds = match pda_from {
+        None => r#"[Buffer.from("counter")]"#,
+        Some(PdaSeed::User) => r#"[Buffer.from("counter"), provider.wallet.publicKey.toBuffer()]"#,
+    };
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{  PublicKey }} from "@solana/web3.js";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  let counterAccount: PublicKey;
+  let counterBump: number;
+
+  before("Boilerplates", async () => {{
+    [counterAccount, counterBump] = await PublicKey.findProgramAddress(
+      {seeds},
+      program.programId
+    );
+  }});
+
+  it("Initialize counter!", async () => {{
+    await program.methods
+      .initialize()
+      .accounts({{
+        counter: counterAccount,
+        user: provider.wallet.publicKey,
+      }})
+      .rpc();
+
+    const counter = await program.account.counter.fetch(counterAccount);
+    expect(counter.count.toString()).eq("0")
+  }});
+  it("Increment counter", async () => {{
+    await program.methods
+      .increment()
+      .accounts({{
+        counter: counterAccount,
+        user: provider.wallet.publicKey,
+      }})
+      .rpc();
+
+    const counter = await program.account.counter.fetch(counterAccount);
+    expect(counter.count.toString()).eq("1")
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+/// Tokens minted by the generated mint tests, unless `--demo-mint-amount` is given
+pub const DEFAULT_MINT_AMOUNT: u64 = 10;
+
+/// Largest amount minted by the generated test whose base units, with the 9 decimals of the
+/// generated mint, fit in a u64
+pub const MAX_MINT_AMOUNT: u64 = u64::MAX / 1_000_000_000;
+
+pub fn ts_mocha_mint_token(
+    name: &str,
+    types_path: &str,
+    mint_recipient: Option<Pubkey>,
+    mint_amount: Option<u64>,
+    commitment: Option<Commitment>,
+) -> String {
+    let mint_amount = mint_amount.unwrap_or(DEFAULT_MINT_AMOUNT);
+    let provider = ts_provider(commitment);
+    let (init_commitment, mint_commitment) = match commitment {
+        Some(commitment) => (
+            format!(r#", "{commitment}""#),
+            format!(r#", "{commitment}""#),
+        ),
+        None => (r#", "finalized""#.to_string(), String::new()),
+    };
+    // Tokens go to the payer's associated token account unless a recipient is given
+    let recipient = match mint_recipient {
+        Some(pubkey) => format!(r#"new PublicKey("{pubkey}")"#),
+        None => "payer".to_string(),
+    };
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY }} from "@solana/web3.js";
+import {{ assert }} from "chai";
+import BN from "bn.js";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+
+  // Metaplex Constants
+  const METADATA_SEED = "metadata";
+  const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+
+  // Constants from our program
+  const MINT_SEED = "mint";
+
+  // Data for our tests
+  const payer = provider.wallet.publicKey;
+  const recipient = {recipient};
+  const metadata = {{
+    name: "Icy",
+    symbol: "ICY",
+    uri: "https://cdn.discordapp.com/emojis/1192768878183465062.png?size=240&quality=lossless",
+    decimals: 9,
+  }};
+  const mintAmount = {mint_amount};
+
+  // Derive the public key for our mint account
+  const [mint] = PublicKey.findProgramAddressSync(
+    [Buffer.from(MINT_SEED)],
+    program.programId
+  );
+
+  // Derive the public key for our metadata account using the Metaplex program
+  const [metadataAddress] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(METADATA_SEED),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+
+  it("initialize", async () => {{
+    // Check if the mint account already exists
+    const info = await provider.connection.getAccountInfo(mint);
+    if (info) {{
+      return; // Do not attempt to initialize if already initialized
+    }}
+    console.log("  Mint not found. Attempting to initialize.");
+
+    // Define the accounts and arguments for the `initToken` function call
+    const context = {{
+      metadata: metadataAddress,
+      mint,
+      payer,
+      rent: SYSVAR_RENT_PUBKEY,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    }};
+
+    // Call the `initToken` function to initialize the mint account
+    const txHash = await program.methods
+      .initToken(metadata)
+      .accounts(context)
+      .rpc();
+
+    // Wait for confirmation and log transaction details
+    await provider.connection.confirmTransaction(txHash{init_commitment});
+    console.log(`  https://explorer.solana.com/tx/${{txHash}}?cluster=devnet`);
+
+    // Verify that the mint account was initialized
+    const newInfo = await provider.connection.getAccountInfo(mint);
+    assert(newInfo, "  Mint should be initialized.");
+  }});
+
+  it("mint tokens", async () => {{
+    // Derive the associated token account address for the recipient
+    const destination = anchor.utils.token.associatedAddress({{
+      mint: mint,
+      owner: recipient,
+    }});
+
+    // Get initial token balance (0 if account not yet created)
+    let initialBalance: number;
+    try {{
+      const balance = await provider.connection.getTokenAccountBalance(
+        destination
+      );
+      initialBalance = balance.value.uiAmount;
+    }} catch {{
+      // Token account not yet initiated has 0 balance
+      initialBalance = 0;
+    }}
+
+    // Define the accounts and arguments for the `mintTokens` function call
+    const context = {{
+      mint,
+      destination,
+      recipient,
+      payer,
+      rent: SYSVAR_RENT_PUBKEY,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+    }};
+
+    // Call the `mintTokens` function to mint tokens
+    const txHash = await program.methods
+      .mintTokens(new BN(mintAmount).mul(new BN(10).pow(new BN(metadata.decimals))))
+      .accounts(context)
+      .rpc();
+    await provider.connection.confirmTransaction(txHash{mint_commitment});
+    console.log(`  https://explorer.solana.com/tx/${{txHash}}?cluster=devnet`);
+
+    // check icy balance of recipient
+    const postBalance = (
+      await provider.connection.getTokenAccountBalance(destination)
+    ).value.uiAmount;
+    assert.equal(
+      initialBalance + mintAmount,
+      postBalance,
+      "Post balance should equal initial plus mint amount"
+    );
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+/// Test of the mint template with `--mints`, creating the first two mints of the program.
+pub fn ts_mocha_multiple_mints(
+    name: &str,
+    types_path: &str,
+    mint_recipient: Option<Pubkey>,
+    mint_amount: Option<u64>,
+    commitment: Option<Commitment>,
+) -> String {
+    let mint_amount = mint_amount.unwrap_or(DEFAULT_MINT_AMOUNT);
+    let provider = ts_provider(commitment);
+    let confirm_commitment = match commitment {
+        Some(commitment) => format!(r#", "{commitment}""#),
+        None => String::new(),
+    };
+    // Tokens go to the payer's associated token account unless a recipient is given
+    let recipient = match mint_recipient {
+        Some(pubkey) => format!(r#"new PublicKey("{pubkey}")"#),
+        None => "payer".to_string(),
+    };
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY }} from "@solana/web3.js";
+import {{ assert }} from "chai";
+import BN from "bn.js";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+
+  // Metaplex Constants
+  const METADATA_SEED = "metadata";
+  const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+
+  // Constants from our program
+  const MINT_SEED = "mint";
+
+  // Data for our tests
+  const payer = provider.wallet.publicKey;
+  const recipient = {recipient};
+  const mintAmount = {mint_amount};
+  const decimals = 9;
+
+  // Each mint is derived from its index
+  const mintAddress = (index: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(MINT_SEED), Buffer.from([index])],
+      program.programId
+    )[0];
+
+  const metadataAddress = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(METADATA_SEED),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.toBuffer(),
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0];
+
+  it("mints are distinct", () => {{
+    assert.notEqual(mintAddress(0).toBase58(), mintAddress(1).toBase58());
+  }});
+
+  for (const index of [0, 1]) {{
+    const mint = mintAddress(index);
+
+    it(`initialize mint ${{index}}`, async () => {{
+      // Do not attempt to initialize if already initialized
+      if (await provider.connection.getAccountInfo(mint)) {{
+        return;
+      }}
+
+      const txHash = await program.methods
+        .initToken(index, {{
+          name: `Icy ${{index}}`,
+          symbol: `ICY${{index}}`,
+          uri: "https://cdn.discordapp.com/emojis/1192768878183465062.png?size=240&quality=lossless",
+          decimals,
+        }})
+        .accounts({{
+          metadata: metadataAddress(mint),
+          mint,
+          payer,
+          rent: SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        }})
+        .rpc();
+      await provider.connection.confirmTransaction(txHash{confirm_commitment});
+
+      const info = await provider.connection.getAccountInfo(mint);
+      assert(info, "  Mint should be initialized.");
+    }});
+
+    it(`mint tokens of mint ${{index}}`, async () => {{
+      const destination = anchor.utils.token.associatedAddress({{
+        mint,
+        owner: recipient,
+      }});
+
+      // Token account not yet initiated has 0 balance
+      let initialBalance = 0;
+      try {{
+        initialBalance = (
+          await provider.connection.getTokenAccountBalance(destination)
+        ).value.uiAmount;
+      }} catch {{}}
+
+      const txHash = await program.methods
+        .mintTokens(index, new BN(mintAmount).mul(new BN(10).pow(new BN(decimals))))
+        .accounts({{
+          mint,
+          destination,
+          recipient,
+          payer,
+          rent: SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        }})
+        .rpc();
+      await provider.connection.confirmTransaction(txHash{confirm_commitment});
+
+      const postBalance = (
+        await provider.connection.getTokenAccountBalance(destination)
+      ).value.uiAmount;
+      assert.equal(
+        initialBalance + mintAmount,
+        postBalance,
+        "Post balance should equal initial plus mint amount"
+      );
+    }});
+  }}
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_pausable(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ PublicKey }} from "@solana/web3.js";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const admin = provider.wallet.publicKey;
+
+  const [config] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const [counter] = PublicKey.findProgramAddressSync(
+    [Buffer.from("counter")],
+    program.programId
+  );
+
+  it("Initialize", async () => {{
+    await program.methods
+      .initialize()
+      .accounts({{ config, counter, admin }})
+      .rpc();
+
+    const state = await program.account.config.fetch(config);
+    expect(state.paused).eq(false);
+  }});
+
+  it("Rejects increment while paused", async () => {{
+    await program.methods.pause().accounts({{ config, admin }}).rpc();
+
+    let error: anchor.AnchorError | undefined;
+    try {{
+      await program.methods
+        .increment()
+        .accounts({{ config, counter, user: admin }})
+        .rpc();
+    }} catch (err) {{
+      error = err as anchor.AnchorError;
+    }}
+    expect(error?.error.errorCode.code).eq("ProgramPaused");
+  }});
+
+  it("Increments after unpause", async () => {{
+    await program.methods.unpause().accounts({{ config, admin }}).rpc();
+    await program.methods
+      .increment()
+      .accounts({{ config, counter, user: admin }})
+      .rpc();
+
+    const state = await program.account.counter.fetch(counter);
+    expect(state.count.toString()).eq("1");
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_pda_signer(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ LAMPORTS_PER_SOL, PublicKey }} from "@solana/web3.js";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const user = provider.wallet.publicKey;
+
+  // The vault is a PDA owned by the system program, one per user
+  const [vault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), user.toBuffer()],
+    program.programId
+  );
+
+  it("Deposit into the vault", async () => {{
+    await program.methods
+      .deposit(new anchor.BN(LAMPORTS_PER_SOL))
+      .accounts({{ vault, user }})
+      .rpc();
+
+    const balance = await provider.connection.getBalance(vault);
+    expect(balance).eq(LAMPORTS_PER_SOL);
+  }});
+
+  it("Withdraw from the vault", async () => {{
+    await program.methods
+      .withdraw(new anchor.BN(LAMPORTS_PER_SOL / 2))
+      .accounts({{ vault, user }})
+      .rpc();
+
+    const balance = await provider.connection.getBalance(vault);
+    expect(balance).eq(LAMPORTS_PER_SOL / 2);
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_escrow(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+}} from "@solana/web3.js";
+import {{
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+}} from "@solana/spl-token";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const connection = provider.connection;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const maker = provider.wallet.publicKey;
+  const taker = Keypair.generate();
+  const deposit = 1_000;
+  const receive = 500;
+
+  let mintA: PublicKey;
+  let mintB: PublicKey;
+
+  const ata = (mint: PublicKey, owner: PublicKey) =>
+    getAssociatedTokenAddressSync(mint, owner, true);
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  // Each escrow of the maker is derived from a seed
+  const escrowAddress = (seed: anchor.BN) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("escrow"),
+        maker.toBuffer(),
+        seed.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  const make = async (seed: anchor.BN) => {{
+    const escrow = escrowAddress(seed);
+    await program.methods
+      .make(seed, new anchor.BN(deposit), new anchor.BN(receive))
+      .accounts({{
+        maker,
+        mintA,
+        mintB,
+        makerAtaA: ata(mintA, maker),
+        escrow,
+        vault: ata(mintA, escrow),
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+    return escrow;
+  }};
+
+  before(async () => {{
+    // The maker holds mint A and the taker holds mint B
+    await connection.confirmTransaction(
+      await connection.requestAirdrop(taker.publicKey, LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    mintA = await createMint(connection, payer, maker, null, 0);
+    mintB = await createMint(connection, payer, maker, null, 0);
+
+    const makerAtaA = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      mintA,
+      maker
+    );
+    const takerAtaB = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      mintB,
+      taker.publicKey
+    );
+    await mintTo(connection, payer, mintA, makerAtaA.address, payer, deposit * 2);
+    await mintTo(connection, payer, mintB, takerAtaB.address, payer, receive);
+  }});
+
+  it("Make and take", async () => {{
+    const escrow = await make(new anchor.BN(1));
+    const vault = ata(mintA, escrow);
+    expect(await balance(vault)).eq(deposit);
+
+    await program.methods
+      .take()
+      .accounts({{
+        taker: taker.publicKey,
+        maker,
+        mintA,
+        mintB,
+        takerAtaA: ata(mintA, taker.publicKey),
+        takerAtaB: ata(mintB, taker.publicKey),
+        makerAtaB: ata(mintB, maker),
+        escrow,
+        vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      }})
+      .signers([taker])
+      .rpc();
+
+    expect(await balance(ata(mintA, taker.publicKey))).eq(deposit);
+    expect(await balance(ata(mintB, maker))).eq(receive);
+    expect(await connection.getAccountInfo(escrow)).eq(null);
+  }});
+
+  it("Make and refund", async () => {{
+    const escrow = await make(new anchor.BN(2));
+
+    await program.methods
+      .refund()
+      .accounts({{
+        maker,
+        mintA,
+        makerAtaA: ata(mintA, maker),
+        escrow,
+        vault: ata(mintA, escrow),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }})
+      .rpc();
+
+    // The first escrow was taken, so the maker is left with the refunded deposit
+    expect(await balance(ata(mintA, maker))).eq(deposit);
+    expect(await connection.getAccountInfo(escrow)).eq(null);
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_vesting(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ PublicKey, SystemProgram }} from "@solana/web3.js";
+import {{
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+}} from "@solana/spl-token";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const connection = provider.connection;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  // The provider wallet funds the vesting and is also its beneficiary
+  const beneficiary = provider.wallet.publicKey;
+  const totalAmount = 1_000_000;
+
+  // The cliff is a few seconds away and the end far in the future, so only part unlocks
+  const now = Math.floor(Date.now() / 1000);
+  const startTs = now - 10;
+  const cliffTs = now + 3;
+  const endTs = now + 1000;
+
+  let mint: PublicKey;
+  let vesting: PublicKey;
+  let vault: PublicKey;
+
+  const ata = (owner: PublicKey) =>
+    getAssociatedTokenAddressSync(mint, owner, true);
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  const claim = () =>
+    program.methods
+      .claim()
+      .accounts({{
+        beneficiary,
+        mint,
+        beneficiaryAta: ata(beneficiary),
+        vesting,
+        vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+
+  before(async () => {{
+    mint = await createMint(connection, payer, beneficiary, null, 0);
+    const funderAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      mint,
+      beneficiary
+    );
+    await mintTo(connection, payer, mint, funderAta.address, payer, totalAmount);
+
+    [vesting] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting"), beneficiary.toBuffer(), mint.toBuffer()],
+      program.programId
+    );
+    vault = ata(vesting);
+  }});
+
+  it("Initialize vesting", async () => {{
+    await program.methods
+      .initializeVesting(
+        new anchor.BN(startTs),
+        new anchor.BN(cliffTs),
+        new anchor.BN(endTs),
+        new anchor.BN(totalAmount)
+      )
+      .accounts({{
+        funder: beneficiary,
+        beneficiary,
+        mint,
+        funderAta: ata(beneficiary),
+        vesting,
+        vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+
+    expect(await balance(vault)).eq(totalAmount);
+  }});
+
+  it("Rejects claims before the cliff", async () => {{
+    let error: anchor.AnchorError | undefined;
+    try {{
+      await claim();
+    }} catch (err) {{
+      error = err as anchor.AnchorError;
+    }}
+    expect(error?.error.errorCode.code).eq("NothingToClaim");
+  }});
+
+  it("Claims part of the tokens after the cliff", async () => {{
+    // Wait until the validator clock is past the cliff
+    const wait = cliffTs + 3 - Math.floor(Date.now() / 1000);
+    await new Promise((resolve) => setTimeout(resolve, Math.max(wait, 0) * 1000));
+
+    await claim();
+
+    const claimed = await balance(ata(beneficiary));
+    expect(claimed).greaterThan(0);
+    expect(claimed).lessThan(totalAmount);
+    expect(await balance(vault)).eq(totalAmount - claimed);
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_nft(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+}} from "@solana/web3.js";
+import {{ assert }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+
+  // Metaplex Constants
+  const METADATA_SEED = "metadata";
+  const EDITION_SEED = "edition";
+  const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+
+  // Data for our tests
+  const payer = provider.wallet.publicKey;
+  const metadata = {{
+    name: "Icy",
+    symbol: "ICY",
+    uri: "https://cdn.discordapp.com/emojis/1192768878183465062.png?size=240&quality=lossless",
+  }};
+
+  // Every run mints a new NFT
+  const mint = Keypair.generate();
+
+  // Derive the metadata and master edition accounts using the Metaplex program
+  const [metadataAddress] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(METADATA_SEED),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.publicKey.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  const [masterEdition] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(METADATA_SEED),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.publicKey.toBuffer(),
+      Buffer.from(EDITION_SEED),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  const tokenAccount = anchor.utils.token.associatedAddress({{
+    mint: mint.publicKey,
+    owner: payer,
+  }});
+
+  it("mint nft", async () => {{
+    const txHash = await program.methods
+      .mintNft(metadata)
+      .accounts({{
+        payer,
+        mint: mint.publicKey,
+        tokenAccount,
+        metadata: metadataAddress,
+        masterEdition,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      }})
+      .signers([mint])
+      .rpc();
+    console.log(`  https://explorer.solana.com/tx/${{txHash}}?cluster=devnet`);
+
+    // The payer holds the single token of the NFT
+    const balance = (
+      await provider.connection.getTokenAccountBalance(tokenAccount)
+    ).value;
+    assert.equal(balance.amount, "1");
+    assert.equal(balance.decimals, 0);
+
+    // The master edition was created by the Metaplex program
+    const edition = await provider.connection.getAccountInfo(masterEdition);
+    assert(edition, "  Master edition should exist.");
+    assert(edition.owner.equals(TOKEN_METADATA_PROGRAM_ID));
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_staking(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ PublicKey, SystemProgram }} from "@solana/web3.js";
+import {{
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+}} from "@solana/spl-token";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const connection = provider.connection;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const user = provider.wallet.publicKey;
+
+  const stakeAmount = 1_000;
+  // One reward token per slot for the whole stake, in millionths per staked token
+  const rewardRate = 1_000;
+
+  let stakeMint: PublicKey;
+  let pool: PublicKey;
+  let rewardMint: PublicKey;
+  let stakeAccount: PublicKey;
+  let vault: PublicKey;
+  let userAta: PublicKey;
+
+  const ata = (mint: PublicKey, owner: PublicKey) =>
+    getAssociatedTokenAddressSync(mint, owner, true);
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  before(async () => {{
+    stakeMint = await createMint(connection, payer, user, null, 0);
+    userAta = (
+      await getOrCreateAssociatedTokenAccount(connection, payer, stakeMint, user)
+    ).address;
+    await mintTo(connection, payer, stakeMint, userAta, payer, stakeAmount);
+
+    [pool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), stakeMint.toBuffer()],
+      program.programId
+    );
+    [rewardMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward"), pool.toBuffer()],
+      program.programId
+    );
+    [stakeAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake"), pool.toBuffer(), user.toBuffer()],
+      program.programId
+    );
+    vault = ata(stakeMint, pool);
+  }});
+
+  it("Initialize the pool", async () => {{
+    await program.methods
+      .initializePool(new anchor.BN(rewardRate))
+      .accounts({{
+        authority: user,
+        stakeMint,
+        pool,
+        rewardMint,
+        vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+
+    const account = await program.account.stakePool.fetch(pool);
+    expect(account.rewardRate.toNumber()).eq(rewardRate);
+  }});
+
+  it("Stake", async () => {{
+    await program.methods
+      .stake(new anchor.BN(stakeAmount))
+      .accounts({{
+        user,
+        stakeMint,
+        pool,
+        stakeAccount,
+        userAta,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+
+    expect(await balance(vault)).eq(stakeAmount);
+  }});
+
+  it("Claim rewards after some slots", async () => {{
+    // Let a few slots pass so that rewards accrue
+    const {{ lastUpdateSlot }} = await program.account.stakeAccount.fetch(
+      stakeAccount
+    );
+    while ((await connection.getSlot()) <= lastUpdateSlot.toNumber() + 2) {{
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }}
+
+    await program.methods
+      .claimRewards()
+      .accounts({{
+        user,
+        pool,
+        rewardMint,
+        stakeAccount,
+        userRewardAta: ata(rewardMint, user),
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+
+    expect(await balance(ata(rewardMint, user))).greaterThan(0);
+  }});
+
+  it("Unstake", async () => {{
+    await program.methods
+      .unstake(new anchor.BN(stakeAmount))
+      .accounts({{
+        user,
+        stakeMint,
+        pool,
+        stakeAccount,
+        userAta,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }})
+      .rpc();
+
+    expect(await balance(vault)).eq(0);
+    expect(await balance(userAta)).eq(stakeAmount);
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_multisig(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ Keypair, PublicKey, SystemProgram }} from "@solana/web3.js";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+// The memo program is loaded by the local validator, the multisig signs a memo with its PDA
+const MEMO_PROGRAM_ID = new PublicKey(
+  "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+);
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const owner = provider.wallet.publicKey;
+  const otherOwners = [Keypair.generate(), Keypair.generate()];
+
+  // The create key makes the multisig address unique, so the tests can run again
+  const createKey = Keypair.generate().publicKey;
+  const [multisig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("multisig"), createKey.toBuffer()],
+    program.programId
+  );
+  const [transaction] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("transaction"),
+      multisig.toBuffer(),
+      new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+
+  // The accounts of the proposed instruction and its program are passed to `execute`
+  const execute = () =>
+    program.methods
+      .execute()
+      .accounts({{ multisig, transaction, owner }})
+      .remainingAccounts([
+        {{ pubkey: multisig, isSigner: false, isWritable: false }},
+        {{ pubkey: MEMO_PROGRAM_ID, isSigner: false, isWritable: false }},
+      ])
+      .rpc();
+
+  it("Create a 2-of-3 multisig", async () => {{
+    await program.methods
+      .createMultisig([owner, ...otherOwners.map((o) => o.publicKey)], 2)
+      .accounts({{
+        multisig,
+        createKey,
+        payer: owner,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+
+    const state = await program.account.multisig.fetch(multisig);
+    expect(state.owners.length).eq(3);
+    expect(state.threshold).eq(2);
+  }});
+
+  it("Propose a memo signed by the multisig", async () => {{
+    await program.methods
+      .proposeTransaction(
+        MEMO_PROGRAM_ID,
+        [{{ pubkey: multisig, isSigner: true, isWritable: false }}],
+        Buffer.from("Approved by the multisig")
+      )
+      .accounts({{
+        multisig,
+        transaction,
+        proposer: owner,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+
+    const state = await program.account.transaction.fetch(transaction);
+    expect(state.approvals).deep.eq([true, false, false]);
+  }});
+
+  it("Rejects execution below the threshold", async () => {{
+    let error: anchor.AnchorError | undefined;
+    try {{
+      await execute();
+    }} catch (err) {{
+      error = err as anchor.AnchorError;
+    }}
+    expect(error?.error.errorCode.code).eq("NotEnoughApprovals");
+  }});
+
+  it("Execute after a second approval", async () => {{
+    await program.methods
+      .approve()
+      .accounts({{ multisig, transaction, owner: otherOwners[0].publicKey }})
+      .signers([otherOwners[0]])
+      .rpc();
+    await execute();
+
+    const state = await program.account.transaction.fetch(transaction);
+    expect(state.executed).eq(true);
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_token_2022(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ PublicKey, SystemProgram }} from "@solana/web3.js";
+import {{
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+}} from "@solana/spl-token";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const payer = provider.wallet.publicKey;
+  const decimals = 9;
+
+  const [mint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("mint")],
+    program.programId
+  );
+  // Associated token accounts of Token-2022 mints are derived from the Token-2022 program
+  const destination = getAssociatedTokenAddressSync(
+    mint,
+    payer,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  it("Initialize the mint", async () => {{
+    // The mint PDA survives between runs on a persistent validator
+    if (await provider.connection.getAccountInfo(mint)) {{
+      return;
+    }}
+
+    await program.methods
+      .initToken(decimals)
+      .accounts({{
+        mint,
+        payer,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }})
+      .rpc();
+
+    const info = await getMint(
+      provider.connection,
+      mint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(info.decimals).eq(decimals);
+  }});
+
+  it("Mint tokens", async () => {{
+    const balance = async () => {{
+      const info = await provider.connection.getAccountInfo(destination);
+      if (!info) {{
+        return BigInt(0);
+      }}
+      const account = await getAccount(
+        provider.connection,
+        destination,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      return account.amount;
+    }};
+    const before = await balance();
+
+    await program.methods
+      .mintTokens(new anchor.BN(1_000))
+      .accounts({{
+        mint,
+        destination,
+        recipient: payer,
+        payer,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      }})
+      .rpc();
+
+    expect((await balance()) - before).eq(BigInt(1_000));
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_transfer_hook(
+    name: &str,
+    types_path: &str,
+    commitment: Option<Commitment>,
+) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+}} from "@solana/web3.js";
+import {{
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferHookInstruction,
+  createMintToInstruction,
+  createTransferCheckedWithTransferHookInstruction,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+}} from "@solana/spl-token";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const connection = provider.connection;
+  const owner = provider.wallet.publicKey;
+  const recipient = Keypair.generate().publicKey;
+  const decimals = 9;
+
+  // A new mint each run, its transfer hook extension points at the program
+  const mint = Keypair.generate();
+  const source = getAssociatedTokenAddressSync(
+    mint.publicKey,
+    owner,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const destination = getAssociatedTokenAddressSync(
+    mint.publicKey,
+    recipient,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const [counter] = PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), mint.publicKey.toBuffer()],
+    program.programId
+  );
+
+  it("Create a mint with the transfer hook extension", async () => {{
+    const space = getMintLen([ExtensionType.TransferHook]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(space);
+    const transaction = new Transaction().add(
+      SystemProgram.createAccount({{
+        fromPubkey: owner,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }}),
+      createInitializeTransferHookInstruction(
+        mint.publicKey,
+        owner,
+        program.programId,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mint.publicKey,
+        decimals,
+        owner,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createAssociatedTokenAccountInstruction(
+        owner,
+        source,
+        owner,
+        mint.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createAssociatedTokenAccountInstruction(
+        owner,
+        destination,
+        recipient,
+        mint.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createMintToInstruction(
+        mint.publicKey,
+        source,
+        owner,
+        1_000,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await provider.sendAndConfirm(transaction, [mint]);
+  }});
+
+  it("Initialize the extra account meta list", async () => {{
+    const [extraAccountMetaList] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extra-account-metas"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeExtraAccountMetaList()
+      .accounts({{
+        payer: owner,
+        extraAccountMetaList,
+        mint: mint.publicKey,
+        counter,
+        systemProgram: SystemProgram.programId,
+      }})
+      .rpc();
+  }});
+
+  it("Transfer calling the hook", async () => {{
+    // Resolves the accounts of the hook from the extra account meta list
+    const instruction = await createTransferCheckedWithTransferHookInstruction(
+      connection,
+      source,
+      mint.publicKey,
+      destination,
+      owner,
+      BigInt(100),
+      decimals,
+      [],
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    await provider.sendAndConfirm(new Transaction().add(instruction));
+
+    const state = await program.account.counter.fetch(counter);
+    expect(state.transfers.toNumber()).eq(1);
+    expect(state.amount.toNumber()).eq(100);
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+pub fn ts_mocha_crud(name: &str, types_path: &str, commitment: Option<Commitment>) -> String {
+    let provider = ts_provider(commitment);
+
+    format!(
+        r#"import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ PublicKey, SystemProgram }} from "@solana/web3.js";
+import {{ expect }} from "chai";
+import {{ {} }} from "{}";
+
+describe("{}", () => {{
+  // Configure the client to use the local cluster.
+  const provider = {provider};
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{} as Program<{}>;
+  const owner = provider.wallet.publicKey;
+
+  const [entry] = PublicKey.findProgramAddressSync(
+    [Buffer.from("entry"), owner.toBuffer()],
+    program.programId
+  );
+
+  it("Create an entry", async () => {{
+    await program.methods
+      .createEntry("Favorites", "Blue, pizza and Solana")
+      .accounts({{ entry, owner, systemProgram: SystemProgram.programId }})
+      .rpc();
+  }});
+
+  it("Read the entry", async () => {{
+    const state = await program.account.entry.fetch(entry);
+    expect(state.owner.toBase58()).eq(owner.toBase58());
+    expect(state.title).eq("Favorites");
+    expect(state.message).eq("Blue, pizza and Solana");
+  }});
+
+  it("Update the entry", async () => {{
+    await program.methods
+      .updateEntry("Green, sushi and Solana")
+      .accounts({{ entry, owner }})
+      .rpc();
+
+    const state = await program.account.entry.fetch(entry);
+    expect(state.message).eq("Green, sushi and Solana");
+  }});
+
+  it("Delete the entry", async () => {{
+    await program.methods.deleteEntry().accounts({{ entry, owner }}).rpc();
+
+    // The entry is closed, so the tests can run again on the same validator
+    const state = await program.account.entry.fetchNullable(entry);
+    expect(state).eq(null);
+  }});
+}});
+"#,
+        name.to_pascal_case(),
+        types_path,
+        name,
+        name.to_pascal_case(),
+        name.to_pascal_case(),
+    )
+}
+
+/// `ts_fund_wallet_hook` of JavaScript workspaces.
+pub fn js_fund_wallet_hook() -> String {
+    js_from_ts(ts_fund_wallet_hook())
+}
+
+/// Root-level mocha hook funding the provider wallet before the tests when running on localnet.
+pub fn ts_fund_wallet_hook() -> &'static str {
+    r#"import * as anchor from "@coral-xyz/anchor";
+
+// Root hook: runs once before all test files
+before("Fund the provider wallet", async () => {
+  const provider = anchor.AnchorProvider.env();
+
+  // Airdrops are only available on a local validator
+  const endpoint = provider.connection.rpcEndpoint;
+  if (!endpoint.includes("localhost") && !endpoint.includes("127.0.0.1")) {
+    return;
+  }
+
+  const signature = await provider.connection.requestAirdrop(
+    provider.wallet.publicKey,
+    10 * anchor.web3.LAMPORTS_PER_SOL
+  );
+  const latestBlockhash = await provider.connection.getLatestBlockhash();
+  await provider.connection.confirmTransaction({ signature, ...latestBlockhash });
+});
+"#
+}
+
+pub fn ts_config(name: &str, path_alias: bool) -> String {
+    // Let tests import the program types via an alias instead of a relative path
+    let paths = if path_alias {
+        format!(
+            r#",
+    "baseUrl": ".",
+    "paths": {{
+      "{TYPES_PATH_ALIAS}": ["./target/types/{}"],
+      "{PROGRAM_TYPES_PATH_ALIAS}": ["./target/types/*"]
+    }}"#,
+            name.to_snake_case()
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        r#"{{
+  "compilerOptions": {{
+    "types": ["mocha", "chai"],
+    "typeRoots": ["./node_modules/@types"],
+    "lib": ["es2015"],
+    "module": "commonjs",
+    "target": "es6",
+    "esModuleInterop": true{paths}
+  }}
+}}
+"#
+    )
+}
+
+pub fn git_ignore() -> &'static str {
+    r#".anchor
+.DS_Store
+target
+**/*.rs.bk
+node_modules
+test-ledger
+.yarn
+.env
+wallet.json
+"#
+}
+
+/// .env.example with the provider settings read by `anchor` and the scripts, pointing at the
+/// given cluster.
+pub fn env_example(cluster: &str, wallet: &str) -> String {
+    format!(
+        r#"# Copy to .env and adjust, .env is not committed
+ANCHOR_PROVIDER_URL={}
+ANCHOR_WALLET={wallet}
+"#,
+        cluster_url(cluster)
+    )
+}
+
+pub fn prettier_ignore() -> &'static str {
+    r#".anchor
+.DS_Store
+target
+node_modules
+dist
+build
+test-ledger
+"#
+}
+
+pub fn docker_ignore() -> &'static str {
+    r#"target
+node_modules
+.anchor
+test-ledger
+"#
+}
+
+/// Dockerfile installing the given Solana and Anchor versions and the package manager, running
+/// `anchor test` by default, e.g. `docker run <image> build` builds instead.
+pub fn dockerfile(
+    solana_version: &str,
+    anchor_version: &str,
+    package_manager: PackageManager,
+) -> String {
+    // npm comes with node
+    let install_package_manager = match package_manager {
+        PackageManager::Npm => String::new(),
+        package_manager => format!("RUN npm install -g {}\n", package_manager.command()),
+    };
+    let package_manager = package_manager.command();
+
+    format!(
+        r#"FROM rust:1-bookworm
+
+ARG SOLANA_VERSION={solana_version}
+ARG ANCHOR_VERSION={anchor_version}
+
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends build-essential pkg-config libssl-dev libudev-dev nodejs npm \
+    && rm -rf /var/lib/apt/lists/*
+{install_package_manager}
+RUN sh -c "$(curl -sSfL https://release.anza.xyz/v${{SOLANA_VERSION}}/install)"
+ENV PATH="/root/.local/share/solana/install/active_release/bin:${{PATH}}"
+
+RUN cargo install --git https://github.com/coral-xyz/anchor --tag v${{ANCHOR_VERSION}} anchor-cli --locked
+
+WORKDIR /workspace
+COPY . .
+RUN {package_manager} install
+# Workspaces generated with --no-wallet use the default Solana wallet
+RUN [ -f /root/.config/solana/id.json ] || solana-keygen new --no-bip39-passphrase --silent --outfile /root/.config/solana/id.json
+
+ENTRYPOINT ["anchor"]
+CMD ["test"]
+"#
+    )
+}
+
+/// Contents of `.anchorversion`, which avm uses to pick the Anchor version of the workspace.
+pub fn anchorversion_file(version: &str) -> String {
+    format!("{version}\n")
+}
+
+pub fn vscode_settings() -> &'static str {
+    r#"{
+  "rust-analyzer.cargo.features": "all",
+  "rust-analyzer.check.command": "clippy",
+  "editor.formatOnSave": true,
+  "[rust]": {
+    "editor.defaultFormatter": "rust-lang.rust-analyzer"
+  },
+  "[typescript]": {
+    "editor.defaultFormatter": "esbenp.prettier-vscode"
+  },
+  "[javascript]": {
+    "editor.defaultFormatter": "esbenp.prettier-vscode"
+  },
+  "[json]": {
+    "editor.defaultFormatter": "esbenp.prettier-vscode"
+  }
+}
+"#
+}
+
+pub fn vscode_extensions() -> &'static str {
+    r#"{
+  "recommendations": [
+    "rust-lang.rust-analyzer",
+    "esbenp.prettier-vscode",
+    "ackeeblockchain.solana"
+  ]
+}
+"#
+}
+
+pub fn deploy_script(name: &str, loader: DeployLoader) -> String {
+    let loader_flag = match loader {
+        DeployLoader::Upgradeable => "",
+        DeployLoader::Final => " --final",
+    };
+
+    format!(
+        r#"#!/usr/bin/env bash
+# Build and deploy the program, then record its program ID.
+set -euo pipefail
+
+PROGRAM_SO="target/deploy/{0}.so"
+PROGRAM_KEYPAIR="target/deploy/{0}-keypair.json"
+
+anchor build
+solana program deploy "$PROGRAM_SO" --program-id "$PROGRAM_KEYPAIR"{1} "$@"
+
+PROGRAM_ID=$(solana address --keypair "$PROGRAM_KEYPAIR")
+echo "$PROGRAM_ID" > target/deploy/{0}-program-id.txt
+echo "Deployed $PROGRAM_ID"
+"#,
+        name.to_snake_case(),
+        loader_flag,
+    )
+}
+
+/// GitHub Actions workflow running `anchor test` against each of `clusters`.
+pub fn ci_workflow(
+    clusters: &[String],
+    wallet: &str,
+    package_manager: PackageManager,
+    solana_version: &str,
+    anchor_version: &str,
+) -> String {
+    let setup = ci_setup_steps(package_manager, solana_version, anchor_version);
+
+    format!(
+        r#"name: Test
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      fail-fast: false
+      matrix:
+        cluster: [{clusters}]
+    steps:
+{setup}
+      - name: Create the wallet
+        if: matrix.cluster == 'localnet'
+        run: "[ -f {wallet} ] || solana-keygen new --no-bip39-passphrase --silent --outfile {wallet}"
+      # Live clusters need a funded wallet, stored in the DEPLOY_WALLET secret
+      - name: Fund the wallet
+        if: matrix.cluster != 'localnet'
+        env:
+          DEPLOY_WALLET: ${{{{ secrets.DEPLOY_WALLET }}}}
+        run: |
+          mkdir -p "$(dirname {wallet})"
+          echo "$DEPLOY_WALLET" > {wallet}
+          solana airdrop 2 --keypair {wallet} --url ${{{{ matrix.cluster }}}} || true
+      - name: Test
+        run: anchor test --provider.cluster ${{{{ matrix.cluster }}}}
+"#,
+        clusters = clusters.join(", "),
+    )
+}
+
+/// GitHub Actions workflow building and testing the workspace on localnet.
+pub fn github_ci(
+    wallet: &str,
+    package_manager: PackageManager,
+    solana_version: &str,
+    anchor_version: &str,
+) -> String {
+    let setup = ci_setup_steps(package_manager, solana_version, anchor_version);
+
+    format!(
+        r#"name: CI
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+jobs:
+  build-and-test:
+    runs-on: ubuntu-latest
+    steps:
+{setup}
+      # The wallet of Anchor.toml isn't committed
+      - name: Create the wallet
+        run: "[ -f {wallet} ] || solana-keygen new --no-bip39-passphrase --silent --outfile {wallet}"
+      - run: anchor build
+      - run: anchor test
+"#
+    )
+}
+
+/// Steps of the GitHub Actions workflows checking out the workspace and installing the given
+/// Solana and Anchor versions and the node modules, caching ~/.cargo and node_modules between
+/// runs.
+fn ci_setup_steps(
+    package_manager: PackageManager,
+    solana_version: &str,
+    anchor_version: &str,
+) -> String {
+    // GitHub runners come with yarn and npm
+    let package_manager_setup = match package_manager {
+        PackageManager::Yarn | PackageManager::Npm => "",
+        PackageManager::Pnpm => {
+            "\n      - uses: pnpm/action-setup@v4\n        with:\n          version: 9"
+        }
+        PackageManager::Bun => "\n      - uses: oven-sh/setup-bun@v2",
+    };
+    let package_manager = package_manager.command();
+
+    format!(
+        r#"      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@stable
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20{package_manager_setup}
+      - uses: actions/cache@v4
+        with:
+          path: |
+            ~/.cargo/bin
+            ~/.cargo/registry
+            ~/.cargo/git
+          key: cargo-${{{{ runner.os }}}}-anchor-{anchor_version}-${{{{ hashFiles('**/Cargo.lock') }}}}
+          restore-keys: cargo-${{{{ runner.os }}}}-anchor-{anchor_version}-
+      - uses: actions/cache@v4
+        with:
+          path: node_modules
+          key: node-modules-${{{{ runner.os }}}}-${{{{ hashFiles('package.json', 'yarn.lock', 'package-lock.json', 'pnpm-lock.yaml', 'bun.lock*') }}}}
+      - name: Install Solana
+        run: |
+          sh -c "$(curl -sSfL https://release.anza.xyz/v{solana_version}/install)"
+          echo "$HOME/.local/share/solana/install/active_release/bin" >> $GITHUB_PATH
+      # Skipped when the cached ~/.cargo/bin already has this version
+      - name: Install Anchor
+        run: |
+          anchor --version 2>/dev/null | grep -q "{anchor_version}" \
+            || cargo install --git https://github.com/coral-xyz/anchor --tag v{anchor_version} anchor-cli --locked --force
+      - run: {package_manager} install"#
+    )
+}
+
+pub fn ts_client(name: &str, template: ProgramTemplate) -> String {
+    let instructions = match template {
+        ProgramTemplate::Basic => {
+            r#"
+export async function initialize(program: Program<IDL>): Promise<string> {
+  return program.methods.initialize().rpc();
+}
+"#
+        }
+        ProgramTemplate::Counter => {
+            r#"
+export function counterAddress(program: Program<IDL>): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("counter")],
+    program.programId
+  )[0];
+}
+
+export async function initialize(program: Program<IDL>): Promise<string> {
+  return program.methods
+    .initialize()
+    .accounts({
+      counter: counterAddress(program),
+      user: program.provider.publicKey,
+    })
+    .rpc();
+}
+
+export async function increment(program: Program<IDL>): Promise<string> {
+  return program.methods
+    .increment()
+    .accounts({
+      counter: counterAddress(program),
+      user: program.provider.publicKey,
+    })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::MintToken => {
+            r#"
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+export type TokenMetadata = {
+  name: string;
+  symbol: string;
+  uri: string;
+  decimals: number;
+};
+
+export function mintAddress(program: Program<IDL>): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("mint")],
+    program.programId
+  )[0];
+}
+
+export function metadataAddress(program: Program<IDL>): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mintAddress(program).toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+export async function initToken(
+  program: Program<IDL>,
+  metadata: TokenMetadata
+): Promise<string> {
+  return program.methods
+    .initToken(metadata)
+    .accounts({
+      metadata: metadataAddress(program),
+      mint: mintAddress(program),
+      payer: program.provider.publicKey,
+      rent: SYSVAR_RENT_PUBKEY,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    })
+    .rpc();
+}
+
+export async function mintTokens(
+  program: Program<IDL>,
+  quantity: anchor.BN,
+  recipient: PublicKey = program.provider.publicKey
+): Promise<string> {
+  const mint = mintAddress(program);
+  return program.methods
+    .mintTokens(quantity)
+    .accounts({
+      mint,
+      destination: anchor.utils.token.associatedAddress({
+        mint,
+        owner: recipient,
+      }),
+      recipient,
+      payer: program.provider.publicKey,
+      rent: SYSVAR_RENT_PUBKEY,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+    })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::Pausable => {
+            r#"
+export function configAddress(program: Program<IDL>): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  )[0];
+}
+
+export function counterAddress(program: Program<IDL>): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("counter")],
+    program.programId
+  )[0];
+}
+
+export async function initialize(program: Program<IDL>): Promise<string> {
+  return program.methods
+    .initialize()
+    .accounts({
+      config: configAddress(program),
+      counter: counterAddress(program),
+      admin: program.provider.publicKey,
+    })
+    .rpc();
+}
+
+export async function pause(program: Program<IDL>): Promise<string> {
+  return program.methods
+    .pause()
+    .accounts({
+      config: configAddress(program),
+      admin: program.provider.publicKey,
+    })
+    .rpc();
+}
+
+export async function unpause(program: Program<IDL>): Promise<string> {
+  return program.methods
+    .unpause()
+    .accounts({
+      config: configAddress(program),
+      admin: program.provider.publicKey,
+    })
+    .rpc();
+}
+
+export async function increment(program: Program<IDL>): Promise<string> {
+  return program.methods
+    .increment()
+    .accounts({
+      config: configAddress(program),
+      counter: counterAddress(program),
+      user: program.provider.publicKey,
+    })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::PdaSigner => {
+            r#"
+export function vaultAddress(program: Program<IDL>, user: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), user.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export async function deposit(
+  program: Program<IDL>,
+  amount: anchor.BN
+): Promise<string> {
+  const user = program.provider.publicKey;
+  return program.methods
+    .deposit(amount)
+    .accounts({ vault: vaultAddress(program, user), user })
+    .rpc();
+}
+
+export async function withdraw(
+  program: Program<IDL>,
+  amount: anchor.BN
+): Promise<string> {
+  const user = program.provider.publicKey;
+  return program.methods
+    .withdraw(amount)
+    .accounts({ vault: vaultAddress(program, user), user })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::Escrow => {
+            r#"
+const ata = (mint: PublicKey, owner: PublicKey) =>
+  anchor.utils.token.associatedAddress({ mint, owner });
+
+export function escrowAddress(
+  program: Program<IDL>,
+  maker: PublicKey,
+  seed: anchor.BN
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), maker.toBuffer(), seed.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
 
-    // Define the accounts and arguments for the `mintTokens` function call
-    const context = {{
+export async function make(
+  program: Program<IDL>,
+  seed: anchor.BN,
+  mintA: PublicKey,
+  mintB: PublicKey,
+  deposit: anchor.BN,
+  receive: anchor.BN
+): Promise<string> {
+  const maker = program.provider.publicKey;
+  const escrow = escrowAddress(program, maker, seed);
+  return program.methods
+    .make(seed, deposit, receive)
+    .accounts({
+      maker,
+      mintA,
+      mintB,
+      makerAtaA: ata(mintA, maker),
+      escrow,
+      vault: ata(mintA, escrow),
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+export async function take(
+  program: Program<IDL>,
+  maker: PublicKey,
+  seed: anchor.BN,
+  mintA: PublicKey,
+  mintB: PublicKey
+): Promise<string> {
+  const taker = program.provider.publicKey;
+  const escrow = escrowAddress(program, maker, seed);
+  return program.methods
+    .take()
+    .accounts({
+      taker,
+      maker,
+      mintA,
+      mintB,
+      takerAtaA: ata(mintA, taker),
+      takerAtaB: ata(mintB, taker),
+      makerAtaB: ata(mintB, maker),
+      escrow,
+      vault: ata(mintA, escrow),
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+export async function refund(
+  program: Program<IDL>,
+  seed: anchor.BN,
+  mintA: PublicKey
+): Promise<string> {
+  const maker = program.provider.publicKey;
+  const escrow = escrowAddress(program, maker, seed);
+  return program.methods
+    .refund()
+    .accounts({
+      maker,
+      mintA,
+      makerAtaA: ata(mintA, maker),
+      escrow,
+      vault: ata(mintA, escrow),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::Vesting => {
+            r#"
+const ata = (mint: PublicKey, owner: PublicKey) =>
+  anchor.utils.token.associatedAddress({ mint, owner });
+
+export function vestingAddress(
+  program: Program<IDL>,
+  beneficiary: PublicKey,
+  mint: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vesting"), beneficiary.toBuffer(), mint.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export async function initializeVesting(
+  program: Program<IDL>,
+  beneficiary: PublicKey,
+  mint: PublicKey,
+  startTs: anchor.BN,
+  cliffTs: anchor.BN,
+  endTs: anchor.BN,
+  totalAmount: anchor.BN
+): Promise<string> {
+  const funder = program.provider.publicKey;
+  const vesting = vestingAddress(program, beneficiary, mint);
+  return program.methods
+    .initializeVesting(startTs, cliffTs, endTs, totalAmount)
+    .accounts({
+      funder,
+      beneficiary,
       mint,
-      destination,
+      funderAta: ata(mint, funder),
+      vesting,
+      vault: ata(mint, vesting),
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+export async function claim(
+  program: Program<IDL>,
+  mint: PublicKey
+): Promise<string> {
+  const beneficiary = program.provider.publicKey;
+  const vesting = vestingAddress(program, beneficiary, mint);
+  return program.methods
+    .claim()
+    .accounts({
+      beneficiary,
+      mint,
+      beneficiaryAta: ata(mint, beneficiary),
+      vesting,
+      vault: ata(mint, vesting),
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::Nft => {
+            r#"
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+export type NftMetadata = {
+  name: string;
+  symbol: string;
+  uri: string;
+};
+
+export function metadataAddress(mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+export function masterEditionAddress(mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("edition"),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+export async function mintNft(
+  program: Program<IDL>,
+  metadata: NftMetadata,
+  mint: anchor.web3.Keypair = anchor.web3.Keypair.generate()
+): Promise<string> {
+  const payer = program.provider.publicKey;
+  return program.methods
+    .mintNft(metadata)
+    .accounts({
       payer,
+      mint: mint.publicKey,
+      tokenAccount: anchor.utils.token.associatedAddress({
+        mint: mint.publicKey,
+        owner: payer,
+      }),
+      metadata: metadataAddress(mint.publicKey),
+      masterEdition: masterEditionAddress(mint.publicKey),
       rent: SYSVAR_RENT_PUBKEY,
       systemProgram: SystemProgram.programId,
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-    }};
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    })
+    .signers([mint])
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::Staking => {
+            r#"
+const ata = (mint: PublicKey, owner: PublicKey) =>
+  anchor.utils.token.associatedAddress({ mint, owner });
+
+export function poolAddress(program: Program<IDL>, stakeMint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool"), stakeMint.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function rewardMintAddress(program: Program<IDL>, pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("reward"), pool.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function stakeAccountAddress(
+  program: Program<IDL>,
+  pool: PublicKey,
+  user: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("stake"), pool.toBuffer(), user.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export async function initializePool(
+  program: Program<IDL>,
+  stakeMint: PublicKey,
+  rewardRate: anchor.BN
+): Promise<string> {
+  const pool = poolAddress(program, stakeMint);
+  return program.methods
+    .initializePool(rewardRate)
+    .accounts({
+      authority: program.provider.publicKey,
+      stakeMint,
+      pool,
+      rewardMint: rewardMintAddress(program, pool),
+      vault: ata(stakeMint, pool),
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+export async function stake(
+  program: Program<IDL>,
+  stakeMint: PublicKey,
+  amount: anchor.BN
+): Promise<string> {
+  const user = program.provider.publicKey;
+  const pool = poolAddress(program, stakeMint);
+  return program.methods
+    .stake(amount)
+    .accounts({
+      user,
+      stakeMint,
+      pool,
+      stakeAccount: stakeAccountAddress(program, pool, user),
+      userAta: ata(stakeMint, user),
+      vault: ata(stakeMint, pool),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+export async function unstake(
+  program: Program<IDL>,
+  stakeMint: PublicKey,
+  amount: anchor.BN
+): Promise<string> {
+  const user = program.provider.publicKey;
+  const pool = poolAddress(program, stakeMint);
+  return program.methods
+    .unstake(amount)
+    .accounts({
+      user,
+      stakeMint,
+      pool,
+      stakeAccount: stakeAccountAddress(program, pool, user),
+      userAta: ata(stakeMint, user),
+      vault: ata(stakeMint, pool),
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+}
+
+export async function claimRewards(
+  program: Program<IDL>,
+  stakeMint: PublicKey
+): Promise<string> {
+  const user = program.provider.publicKey;
+  const pool = poolAddress(program, stakeMint);
+  const rewardMint = rewardMintAddress(program, pool);
+  return program.methods
+    .claimRewards()
+    .accounts({
+      user,
+      pool,
+      rewardMint,
+      stakeAccount: stakeAccountAddress(program, pool, user),
+      userRewardAta: ata(rewardMint, user),
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::Multisig => {
+            r#"
+export function multisigAddress(program: Program<IDL>, createKey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("multisig"), createKey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function transactionAddress(
+  program: Program<IDL>,
+  multisig: PublicKey,
+  index: number
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("transaction"),
+      multisig.toBuffer(),
+      new anchor.BN(index).toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  )[0];
+}
+
+export async function createMultisig(
+  program: Program<IDL>,
+  createKey: PublicKey,
+  owners: PublicKey[],
+  threshold: number
+): Promise<string> {
+  return program.methods
+    .createMultisig(owners, threshold)
+    .accounts({
+      multisig: multisigAddress(program, createKey),
+      createKey,
+      payer: program.provider.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+// Propose an instruction for the multisig PDA to sign, returning the transaction address
+export async function proposeTransaction(
+  program: Program<IDL>,
+  multisig: PublicKey,
+  instruction: TransactionInstruction
+): Promise<PublicKey> {
+  const { transactionCount } = await program.account.multisig.fetch(multisig);
+  const transaction = transactionAddress(program, multisig, transactionCount.toNumber());
+  await program.methods
+    .proposeTransaction(instruction.programId, instruction.keys, instruction.data)
+    .accounts({
+      multisig,
+      transaction,
+      proposer: program.provider.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+  return transaction;
+}
+
+export async function approve(
+  program: Program<IDL>,
+  multisig: PublicKey,
+  transaction: PublicKey
+): Promise<string> {
+  return program.methods
+    .approve()
+    .accounts({ multisig, transaction, owner: program.provider.publicKey })
+    .rpc();
+}
+
+export async function execute(
+  program: Program<IDL>,
+  multisig: PublicKey,
+  transaction: PublicKey
+): Promise<string> {
+  const { programId, accounts } = await program.account.transaction.fetch(transaction);
+  return program.methods
+    .execute()
+    .accounts({ multisig, transaction, owner: program.provider.publicKey })
+    .remainingAccounts([
+      // The multisig PDA signs inside the program, not in the transaction
+      ...accounts.map((account) => ({ ...account, isSigner: false })),
+      { pubkey: programId, isSigner: false, isWritable: false },
+    ])
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::Token2022 => {
+            r#"
+const TOKEN_2022_PROGRAM_ID = new PublicKey(
+  "TokenzQdBNbLqP5VEhdkAS6EtFJxHgCsb3Wr1eNXWSF"
+);
+
+export function mintAddress(program: Program<IDL>): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("mint")],
+    program.programId
+  )[0];
+}
+
+// Associated token accounts of Token-2022 mints are derived from the Token-2022 program
+export function associatedAddress(mint: PublicKey, owner: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    anchor.utils.token.ASSOCIATED_PROGRAM_ID
+  )[0];
+}
+
+export async function initToken(
+  program: Program<IDL>,
+  decimals: number
+): Promise<string> {
+  return program.methods
+    .initToken(decimals)
+    .accounts({
+      mint: mintAddress(program),
+      payer: program.provider.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    })
+    .rpc();
+}
+
+export async function mintTokens(
+  program: Program<IDL>,
+  quantity: anchor.BN,
+  recipient: PublicKey = program.provider.publicKey
+): Promise<string> {
+  const mint = mintAddress(program);
+  return program.methods
+    .mintTokens(quantity)
+    .accounts({
+      mint,
+      destination: associatedAddress(mint, recipient),
+      recipient,
+      payer: program.provider.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+    })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::TransferHook => {
+            r#"
+export function extraAccountMetaListAddress(
+  program: Program<IDL>,
+  mint: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), mint.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function counterAddress(program: Program<IDL>, mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), mint.toBuffer()],
+    program.programId
+  )[0];
+}
+
+// Must be called once for each mint whose transfer hook extension points at the program
+export async function initializeExtraAccountMetaList(
+  program: Program<IDL>,
+  mint: PublicKey
+): Promise<string> {
+  return program.methods
+    .initializeExtraAccountMetaList()
+    .accounts({
+      payer: program.provider.publicKey,
+      extraAccountMetaList: extraAccountMetaListAddress(program, mint),
+      mint,
+      counter: counterAddress(program, mint),
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+"#
+        }
+        ProgramTemplate::Crud => {
+            r#"
+export function entryAddress(
+  program: Program<IDL>,
+  owner: PublicKey = program.provider.publicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("entry"), owner.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export async function createEntry(
+  program: Program<IDL>,
+  title: string,
+  message: string
+): Promise<string> {
+  return program.methods
+    .createEntry(title, message)
+    .accounts({
+      entry: entryAddress(program),
+      owner: program.provider.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+export async function updateEntry(
+  program: Program<IDL>,
+  message: string
+): Promise<string> {
+  return program.methods
+    .updateEntry(message)
+    .accounts({
+      entry: entryAddress(program),
+      owner: program.provider.publicKey,
+    })
+    .rpc();
+}
+
+export async function deleteEntry(program: Program<IDL>): Promise<string> {
+  return program.methods
+    .deleteEntry()
+    .accounts({
+      entry: entryAddress(program),
+      owner: program.provider.publicKey,
+    })
+    .rpc();
+}
+"#
+        }
+    };
+
+    format!(
+        r#"// Typed client for the `{snake}` program, wrapping each instruction in an async function.
+import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  TransactionInstruction,
+}} from "@solana/web3.js";
+import {{ {pascal} as IDL }} from "../target/types/{snake}";
+
+export function getProgram(
+  provider: anchor.AnchorProvider = anchor.AnchorProvider.env()
+): Program<IDL> {{
+  anchor.setProvider(provider);
+  return anchor.workspace.{pascal} as Program<IDL>;
+}}
+{instructions}"#,
+        pascal = name.to_pascal_case(),
+        snake = name.to_snake_case(),
+    )
+}
+
+pub fn loadtest_script(name: &str, template: ProgramTemplate) -> String {
+    // Accounts the primary instruction needs and the call sending it
+    let (instruction, setup, call) = match template {
+        ProgramTemplate::Basic => ("initialize", "", "program.methods.initialize()"),
+        ProgramTemplate::Counter => (
+            "increment",
+            r#"
+  const [counter] = PublicKey.findProgramAddressSync(
+    [Buffer.from("counter")],
+    program.programId
+  );
+"#,
+            r#"program.methods
+      .increment()
+      .accounts({ counter, user: provider.wallet.publicKey })"#,
+        ),
+        ProgramTemplate::MintToken => (
+            "mintTokens",
+            r#"
+  const payer = provider.wallet.publicKey;
+  const [mint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("mint")],
+    program.programId
+  );
+  const destination = anchor.utils.token.associatedAddress({
+    mint,
+    owner: payer,
+  });
+"#,
+            r#"program.methods
+      .mintTokens(new anchor.BN(1))
+      .accounts({
+        mint,
+        destination,
+        recipient: payer,
+        payer,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      })"#,
+        ),
+        ProgramTemplate::Pausable => (
+            "increment",
+            r#"
+  const [config] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const [counter] = PublicKey.findProgramAddressSync(
+    [Buffer.from("counter")],
+    program.programId
+  );
+"#,
+            r#"program.methods
+      .increment()
+      .accounts({ config, counter, user: provider.wallet.publicKey })"#,
+        ),
+        ProgramTemplate::PdaSigner => (
+            "deposit",
+            r#"
+  const user = provider.wallet.publicKey;
+  const [vault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), user.toBuffer()],
+    program.programId
+  );
+"#,
+            r#"program.methods
+      .deposit(new anchor.BN(1))
+      .accounts({ vault, user })"#,
+        ),
+        ProgramTemplate::Escrow => (
+            "make",
+            r#"
+  // Each transaction escrows one token of mint A in a new escrow
+  const { createMint, getOrCreateAssociatedTokenAccount, mintTo } = await import(
+    "@solana/spl-token"
+  );
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const maker = provider.wallet.publicKey;
+  const mintA = await createMint(provider.connection, payer, maker, null, 0);
+  const mintB = await createMint(provider.connection, payer, maker, null, 0);
+  const makerAtaA = (
+    await getOrCreateAssociatedTokenAccount(provider.connection, payer, mintA, maker)
+  ).address;
+  await mintTo(provider.connection, payer, mintA, makerAtaA, payer, TRANSACTIONS);
+
+  const escrowAddress = (i: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("escrow"),
+        maker.toBuffer(),
+        new anchor.BN(i).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+"#,
+            r#"program.methods
+      .make(new anchor.BN(i), new anchor.BN(1), new anchor.BN(1))
+      .accounts({
+        maker,
+        mintA,
+        mintB,
+        makerAtaA,
+        escrow: escrowAddress(i),
+        vault: anchor.utils.token.associatedAddress({
+          mint: mintA,
+          owner: escrowAddress(i),
+        }),
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })"#,
+        ),
+        ProgramTemplate::Vesting => (
+            "initializeVesting",
+            r#"
+  // Each transaction vests one token to a new beneficiary
+  const { createMint, getOrCreateAssociatedTokenAccount, mintTo } = await import(
+    "@solana/spl-token"
+  );
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const funder = provider.wallet.publicKey;
+  const mint = await createMint(provider.connection, payer, funder, null, 0);
+  const funderAta = (
+    await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, funder)
+  ).address;
+  await mintTo(provider.connection, payer, mint, funderAta, payer, TRANSACTIONS);
+
+  const now = Math.floor(Date.now() / 1000);
+  const beneficiaries = Array.from(
+    { length: TRANSACTIONS },
+    () => anchor.web3.Keypair.generate().publicKey
+  );
+  const vestingAddress = (i: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting"), beneficiaries[i].toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+"#,
+            r#"program.methods
+      .initializeVesting(
+        new anchor.BN(now),
+        new anchor.BN(now + 60),
+        new anchor.BN(now + 3600),
+        new anchor.BN(1)
+      )
+      .accounts({
+        funder,
+        beneficiary: beneficiaries[i],
+        mint,
+        funderAta,
+        vesting: vestingAddress(i),
+        vault: anchor.utils.token.associatedAddress({
+          mint,
+          owner: vestingAddress(i),
+        }),
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })"#,
+        ),
+        ProgramTemplate::Nft => (
+            "mintNft",
+            r#"
+  // Each transaction mints a new NFT
+  const payer = provider.wallet.publicKey;
+  const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+  const mints = Array.from({ length: TRANSACTIONS }, () =>
+    anchor.web3.Keypair.generate()
+  );
+  const metaplexAddress = (i: number, ...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mints[i].publicKey.toBuffer(),
+        ...seeds,
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0];
+"#,
+            r#"program.methods
+      .mintNft({ name: `NFT #${i}`, symbol: "NFT", uri: "" })
+      .accounts({
+        payer,
+        mint: mints[i].publicKey,
+        tokenAccount: anchor.utils.token.associatedAddress({
+          mint: mints[i].publicKey,
+          owner: payer,
+        }),
+        metadata: metaplexAddress(i),
+        masterEdition: metaplexAddress(i, Buffer.from("edition")),
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([mints[i]])"#,
+        ),
+        ProgramTemplate::Staking => (
+            "stake",
+            r#"
+  // Each transaction stakes one token in a new pool
+  const { createMint, getOrCreateAssociatedTokenAccount, mintTo } = await import(
+    "@solana/spl-token"
+  );
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const user = provider.wallet.publicKey;
+  const stakeMint = await createMint(provider.connection, payer, user, null, 0);
+  const userAta = (
+    await getOrCreateAssociatedTokenAccount(provider.connection, payer, stakeMint, user)
+  ).address;
+  await mintTo(provider.connection, payer, stakeMint, userAta, payer, TRANSACTIONS);
+
+  const [pool] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pool"), stakeMint.toBuffer()],
+    program.programId
+  );
+  const [rewardMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reward"), pool.toBuffer()],
+    program.programId
+  );
+  const [stakeAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("stake"), pool.toBuffer(), user.toBuffer()],
+    program.programId
+  );
+  const vault = anchor.utils.token.associatedAddress({
+    mint: stakeMint,
+    owner: pool,
+  });
+  await program.methods
+    .initializePool(new anchor.BN(1_000))
+    .accounts({
+      authority: user,
+      stakeMint,
+      pool,
+      rewardMint,
+      vault,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .rpc();
+"#,
+            r#"program.methods
+      .stake(new anchor.BN(1))
+      .accounts({
+        user,
+        stakeMint,
+        pool,
+        stakeAccount,
+        userAta,
+        vault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })"#,
+        ),
+        ProgramTemplate::Multisig => (
+            "approve",
+            r#"
+  // Each transaction approves the same proposal of a new 1-of-1 multisig again
+  const owner = provider.wallet.publicKey;
+  const createKey = anchor.web3.Keypair.generate().publicKey;
+  const [multisig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("multisig"), createKey.toBuffer()],
+    program.programId
+  );
+  const [transaction] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("transaction"),
+      multisig.toBuffer(),
+      new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+  await program.methods
+    .createMultisig([owner], 1)
+    .accounts({
+      multisig,
+      createKey,
+      payer: owner,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .rpc();
+  await program.methods
+    .proposeTransaction(anchor.web3.SystemProgram.programId, [], Buffer.alloc(0))
+    .accounts({
+      multisig,
+      transaction,
+      proposer: owner,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .rpc();
+"#,
+            r#"program.methods
+      .approve()
+      .accounts({ multisig, transaction, owner })"#,
+        ),
+        ProgramTemplate::Token2022 => (
+            "mintTokens",
+            r#"
+  const payer = provider.wallet.publicKey;
+  const tokenProgram = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EtFJxHgCsb3Wr1eNXWSF");
+  const [mint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("mint")],
+    program.programId
+  );
+  const [destination] = PublicKey.findProgramAddressSync(
+    [payer.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
+    anchor.utils.token.ASSOCIATED_PROGRAM_ID
+  );
+"#,
+            r#"program.methods
+      .mintTokens(new anchor.BN(1))
+      .accounts({
+        mint,
+        destination,
+        recipient: payer,
+        payer,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      })"#,
+        ),
+        ProgramTemplate::TransferHook => (
+            "transferHook",
+            r#"
+  // The hook is called directly, without a transfer. MINT is a Token-2022 mint whose transfer
+  // hook points at the program, with an initialized extra account meta list and a token
+  // account of the wallet
+  const owner = provider.wallet.publicKey;
+  const mint = new PublicKey(process.env.MINT!);
+  const tokenProgram = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EtFJxHgCsb3Wr1eNXWSF");
+  const [source] = PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
+    anchor.utils.token.ASSOCIATED_PROGRAM_ID
+  );
+  const [extraAccountMetaList] = PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), mint.toBuffer()],
+    program.programId
+  );
+  const [counter] = PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), mint.toBuffer()],
+    program.programId
+  );
+"#,
+            r#"program.methods
+      .transferHook(new anchor.BN(0))
+      .accounts({
+        sourceToken: source,
+        mint,
+        destinationToken: source,
+        owner,
+        extraAccountMetaList,
+        counter,
+      })"#,
+        ),
+        ProgramTemplate::Crud => (
+            "updateEntry",
+            r#"
+  // Each transaction updates the entry of the wallet, created first if needed
+  const owner = provider.wallet.publicKey;
+  const [entry] = PublicKey.findProgramAddressSync(
+    [Buffer.from("entry"), owner.toBuffer()],
+    program.programId
+  );
+  if (!(await provider.connection.getAccountInfo(entry))) {
+    await program.methods
+      .createEntry("Load test", "")
+      .accounts({
+        entry,
+        owner,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+"#,
+            r#"program.methods
+      .updateEntry(`Update ${i}`)
+      .accounts({ entry, owner })"#,
+        ),
+    };
+
+    format!(
+        r#"// Load test firing concurrent `{instruction}` transactions and reporting the throughput.
+//
+// Usage:
+//   ANCHOR_PROVIDER_URL=http://127.0.0.1:8899 ANCHOR_WALLET=wallet.json \
+//     TRANSACTIONS=100 CONCURRENCY=10 npx ts-node scripts/loadtest.ts
+import * as anchor from "@coral-xyz/anchor";
+import {{ Program }} from "@coral-xyz/anchor";
+import {{ ComputeBudgetProgram, PublicKey }} from "@solana/web3.js";
+import {{ {pascal} }} from "../target/types/{snake}";
+
+const TRANSACTIONS = Number(process.env.TRANSACTIONS ?? 100);
+const CONCURRENCY = Number(process.env.CONCURRENCY ?? 10);
+
+async function main() {{
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{pascal} as Program<{pascal}>;
+{setup}
+  // A distinct priority fee keeps otherwise identical transactions from being deduplicated
+  const send = (i: number) =>
+    {call}
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitPrice({{ microLamports: i + 1 }}),
+      ])
+      .rpc();
+
+  let next = 0;
+  let succeeded = 0;
+  let failed = 0;
+  const worker = async () => {{
+    while (next < TRANSACTIONS) {{
+      const i = next++;
+      try {{
+        await send(i);
+        succeeded++;
+      }} catch (err) {{
+        failed++;
+        console.error(`Transaction ${{i}} failed: ${{err}}`);
+      }}
+    }}
+  }};
+
+  const start = Date.now();
+  await Promise.all(Array.from({{ length: CONCURRENCY }}, worker));
+  const seconds = (Date.now() - start) / 1000;
+
+  console.log(`Sent ${{TRANSACTIONS}} transactions in ${{seconds.toFixed(2)}}s`);
+  console.log(`Throughput: ${{(succeeded / seconds).toFixed(2)}} TPS`);
+  console.log(`Succeeded: ${{succeeded}}, failed: ${{failed}}`);
+}}
+
+main().catch((err) => {{
+  console.error(err);
+  process.exit(1);
+}});
+"#,
+        pascal = name.to_pascal_case(),
+        snake = name.to_snake_case(),
+    )
+}
+
+pub fn makefile(template: ProgramTemplate, cluster: &str) -> String {
+    // Programs on a live cluster are deployed separately before testing
+    let test = match template {
+        ProgramTemplate::MintToken | ProgramTemplate::Nft => {
+            "anchor test --skip-deploy --provider.cluster $(CLUSTER)"
+        }
+        _ => "anchor test --provider.cluster $(CLUSTER)",
+    };
+
+    format!(
+        r#".PHONY: build test deploy localnet clean
+
+CLUSTER ?= {cluster}
+
+build:
+	anchor build
+
+test:
+	{test}
+
+deploy: build
+	anchor deploy --provider.cluster $(CLUSTER)
+
+localnet:
+	solana-test-validator --reset
+
+clean:
+	anchor clean
+"#
+    )
+}
+
+/// justfile of the workspace. `just test` and `just deploy` target the template's cluster unless
+/// overridden, e.g. `just cluster=devnet deploy`.
+pub fn justfile(
+    template: ProgramTemplate,
+    package_manager: PackageManager,
+    cluster: &str,
+) -> String {
+    // Programs on a live cluster are deployed separately before testing
+    let test = match template {
+        ProgramTemplate::MintToken | ProgramTemplate::Nft => {
+            "anchor test --skip-deploy --provider.cluster {{cluster}}"
+        }
+        _ => "anchor test --provider.cluster {{cluster}}",
+    };
+    let package_manager = package_manager.command();
+
+    format!(
+        r#"cluster := "{cluster}"
 
-    // Call the `mintTokens` function to mint tokens
-    const txHash = await program.methods
-      .mintTokens(new BN(mintAmount * 10 ** metadata.decimals))
-      .accounts(context)
-      .rpc();
-    await provider.connection.confirmTransaction(txHash);
-    console.log(`  https://explorer.solana.com/tx/${{txHash}}?cluster=devnet`);
+build:
+    anchor build
 
-    // check icy balance of payer
-    const postBalance = (
-      await provider.connection.getTokenAccountBalance(destination)
-    ).value.uiAmount;
-    assert.equal(
-      initialBalance + mintAmount,
-      postBalance,
-      "Post balance should equal initial plus mint amount"
-    );
-  }});
-}});
-"#,
-        name.to_pascal_case(),
-        name.to_snake_case(),
-        name,
-        name.to_pascal_case(),
-        name.to_pascal_case(),
+test:
+    {test}
+
+deploy: build
+    anchor deploy --provider.cluster {{{{cluster}}}}
+
+lint:
+    cargo fmt --all -- --check
+    {package_manager} run lint
+"#
     )
 }
 
-pub fn ts_config() -> &'static str {
-    r#"{
-  "compilerOptions": {
-    "types": ["mocha", "chai"],
-    "typeRoots": ["./node_modules/@types"],
-    "lib": ["es2015"],
-    "module": "commonjs",
-    "target": "es6",
-    "esModuleInterop": true
-  }
+/// .cargo/config.toml turning the warnings of every build into errors.
+pub fn cargo_config_deny_warnings() -> String {
+    to_toml(&CargoConfig {
+        build: BuildConfig {
+            rustflags: vec!["-D".into(), "warnings".into()],
+        },
+    })
 }
-"#
+
+/// Resolve a cluster moniker (e.g. `devnet`) to its RPC URL, passing custom URLs through.
+pub fn cluster_url(cluster: &str) -> String {
+    match cluster.to_lowercase().as_str() {
+        "localnet" | "l" => "http://127.0.0.1:8899".into(),
+        "devnet" | "d" => "https://api.devnet.solana.com".into(),
+        "testnet" | "t" => "https://api.testnet.solana.com".into(),
+        "mainnet" | "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com".into(),
+        _ => cluster.into(),
+    }
 }
 
-pub fn git_ignore() -> &'static str {
-    r#".anchor
-.DS_Store
-**/*.rs.bk
-node_modules
-test-ledger
-.yarn
-"#
+pub fn get_test_script(package_manager: PackageManager, javascript: bool) -> String {
+    format!(
+        "{} -t 1000000 tests/**/*.{}",
+        package_manager.runner(javascript),
+        test_extension(javascript)
+    )
 }
 
-pub fn prettier_ignore() -> &'static str {
-    r#".anchor
-.DS_Store
-target
-node_modules
-dist
-build
-test-ledger
-"#
+pub fn get_parallel_test_script(package_manager: PackageManager, javascript: bool) -> String {
+    format!(
+        "{} -t 1000000 --parallel tests/**/*.{}",
+        package_manager.runner(javascript),
+        test_extension(javascript)
+    )
 }
 
-pub fn get_test_script() -> &'static str {
-    "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
+/// Extension of the generated test files.
+pub fn test_extension(javascript: bool) -> &'static str {
+    if javascript {
+        "js"
+    } else {
+        "ts"
+    }
+}
+
+/// Mocha config of parallel runs, registering ts-node in the workers unless the tests are
+/// JavaScript.
+pub fn mocharc_parallel(javascript: bool) -> &'static str {
+    if javascript {
+        return r#"{
+  "parallel": true
+}
+"#;
+    }
+    r#"{
+  "parallel": true,
+  "require": ["ts-node/register"]
+}
+"#
 }
 
 pub fn readme(template: ProgramTemplate) -> String {
-    let template_files = match template {
+    match template {
         ProgramTemplate::Basic => readme_basic(),
         ProgramTemplate::Counter => readme_counter(),
         ProgramTemplate::MintToken => readme_mint_token(),
-    };
-
-    template_files
+        ProgramTemplate::Pausable => readme_pausable(),
+        ProgramTemplate::PdaSigner => readme_pda_signer(),
+        ProgramTemplate::Escrow => readme_escrow(),
+        ProgramTemplate::Vesting => readme_vesting(),
+        ProgramTemplate::Nft => readme_nft(),
+        ProgramTemplate::Staking => readme_staking(),
+        ProgramTemplate::Multisig => readme_multisig(),
+        ProgramTemplate::Token2022 => readme_token_2022(),
+        ProgramTemplate::TransferHook => readme_transfer_hook(),
+        ProgramTemplate::Crud => readme_crud(),
+    }
 }
 
 pub fn readme_basic() -> String {
@@ -925,24 +6655,47 @@ anchor test
 }
 
 pub fn readme_counter() -> String {
-    r#"**Build Program**
+    r#"### Counter Program
+
+`initialize` creates the `counter` account at a PDA derived from the `"counter"` seed and sets
+its count to 0. `increment` adds 1 to the count. Generated with `--pda-from user`, the PDA is also
+derived from the signer's public key, so each user gets their own counter.
+
+Derive the address of the counter in the client with
+```ts
+const [counter] = PublicKey.findProgramAddressSync(
+  [Buffer.from("counter")],
+  program.programId
+);
+```
+
+**Build Program**
 ```sh
 anchor build
 ```
 
 **Test Program**
 ```sh
-anchor test 
+anchor test
 ```
 "#
     .to_string()
 }
 
 pub fn readme_mint_token() -> String {
-    r#"### How to Test for Creating Token and Minting Token to Other Wallet
+    format!(
+        r#"### How to Test for Creating Token and Minting Token to Other Wallet
 
 Since the program utilizes the Metaplex program, deployment to the Devnet network is required.
+{}"#,
+        readme_devnet_steps("mint_token")
+    )
+}
 
+/// Steps deploying a program to devnet, funding the wallet with an airdrop, and testing it.
+fn readme_devnet_steps(program: &str) -> String {
+    format!(
+        r#"
 1. **Configure Solana URL to Devnet**
     ```sh
     solana config set --url https://api.devnet.solana.com
@@ -954,7 +6707,7 @@ Since the program utilizes the Metaplex program, deployment to the Devnet networ
     ```
 
 3. **Airdrop SOL to Address**
-    - To deploy the `mint_token` program, ensure you have 2-3 SOL in the wallet which stores the `wallet.json` file.
+    - To deploy the `{program}` program, ensure you have 2-3 SOL in the wallet which stores the `wallet.json` file.
     - Get the address of the wallet:
         ```shell
         solana address --keypair wallet.json
@@ -977,19 +6730,263 @@ Since the program utilizes the Metaplex program, deployment to the Devnet networ
     ```sh
     anchor test --skip-deploy
     ```
-"#.to_string()
+
+To test without devnet, build the program and start a local validator that loads it and clones
+the Metaplex program from devnet, then run the tests against it from another terminal:
+```sh
+anchor build
+df-sol localnet --reset
+anchor test --skip-deploy --skip-local-validator --provider.cluster localnet
+```
+"#
+    )
+}
+
+pub fn readme_pausable() -> String {
+    r#"### Pausable Program
+
+The `config` account stores the admin and a `paused` flag. The admin can call `pause` and
+`unpause`, and `increment` fails with `ProgramPaused` while the program is paused.
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
+}
+
+pub fn readme_pda_signer() -> String {
+    r#"### PDA Signer Program
+
+Each user has a `vault` PDA owned by the system program. `deposit` transfers SOL from the user
+into the vault, and `withdraw` transfers it back with the vault PDA signing the CPI through its
+seeds (`CpiContext::new_with_signer`).
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
+}
+
+pub fn readme_escrow() -> String {
+    r#"### Escrow Program
+
+The maker deposits tokens of mint A into a vault owned by an `escrow` PDA with `make`, asking for
+an amount of mint B in return. `take` swaps them, the taker paying the maker in mint B and receiving
+the vault, and `refund` gives the vault back to the maker. Both close the escrow and its vault.
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
+}
+
+pub fn readme_vesting() -> String {
+    r#"### Vesting Program
+
+`initialize_vesting` locks `total_amount` tokens in a vault owned by a `vesting` PDA of the
+beneficiary, with a `start_ts`, `cliff_ts` and `end_ts` schedule. Nothing unlocks before the cliff,
+then tokens unlock linearly until the end, and `claim` sends the unlocked amount not yet claimed
+to the beneficiary.
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
+}
+
+pub fn readme_nft() -> String {
+    format!(
+        r#"### How to Test Minting an NFT
+
+`mint_nft` creates a new mint with 0 decimals, mints its single token to the payer, then attaches
+the metadata and a master edition, which takes over the mint authority so the supply stays at 1.
+
+Since the program utilizes the Metaplex program, deployment to the Devnet network is required.
+{}"#,
+        readme_devnet_steps("nft")
+    )
+}
+
+pub fn readme_staking() -> String {
+    r#"### Staking Program
+
+`initialize_pool` creates a `pool` PDA for a stake mint, with a vault holding the staked tokens and
+a reward mint controlled by the pool. Users `stake` and `unstake` tokens, and every slot their stake
+accrues `reward_rate` millionths of a reward token per staked token, which `claim_rewards` mints to
+them.
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
+}
+
+pub fn readme_multisig() -> String {
+    r#"### Multisig Program
+
+`create_multisig` stores the owners and the number of approvals a transaction needs. An owner
+proposes an instruction with `propose_transaction`, which counts as their approval, the other
+owners `approve` it, and once the threshold is reached any owner can `execute` it. The multisig
+PDA signs the instruction, so it can own accounts and act as an upgrade or mint authority.
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
+}
+
+pub fn readme_token_2022() -> String {
+    r#"### Token-2022 Program
+
+`init_token` creates a mint owned by the Token-2022 program at a PDA of the program, which is
+its own mint authority. `mint_tokens` mints to the recipient's associated token account,
+creating it if needed. The accounts use the `token_interface` types, so the instructions can be
+extended with Token-2022 extensions like transfer fees or metadata.
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
+}
+
+pub fn readme_transfer_hook() -> String {
+    r#"### Transfer Hook Program
+
+Token-2022 calls the program on every transfer of a mint whose transfer hook extension points
+at it. `initialize_extra_account_meta_list` stores the extra accounts the hook needs, here a
+counter of the mint, which `transfer_hook` updates with the number and amount of transfers.
+Token-2022 calls the hook with the discriminator of the transfer hook interface, which the
+program's `fallback` routes to `transfer_hook`.
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
+}
+
+pub fn readme_crud() -> String {
+    r#"### CRUD Program
+
+Each user stores one entry at a PDA derived from their public key. `create_entry` creates it,
+`update_entry` changes its message and `delete_entry` closes it, refunding the rent to the
+owner. The `#[max_len]` attributes size the account for the longest title and message the
+program accepts.
+
+**Build Program**
+```sh
+anchor build
+```
+
+**Test Program**
+```sh
+anchor test
+```
+"#
+    .to_string()
 }
 
-pub fn create_test_files(project_name: &str, template: ProgramTemplate) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub fn create_test_files(
+    project_name: &str,
+    template: ProgramTemplate,
+    mint_recipient: Option<Pubkey>,
+    path_alias: Option<&str>,
+    pda_from: Option<PdaSeed>,
+    mints: Option<u8>,
+    mint_amount: Option<u64>,
+    commitment: Option<Commitment>,
+    javascript: bool,
+) -> Result<()> {
     fs::create_dir_all("tests")?;
 
-    let mut mocha = File::create(format!("tests/{}.ts", &project_name))?;
-    mocha.write_all(ts_mocha(project_name, template).as_bytes())?;
+    let test = if javascript {
+        js_mocha(
+            project_name,
+            template,
+            mint_recipient,
+            pda_from,
+            mints,
+            mint_amount,
+            commitment,
+        )
+    } else {
+        ts_mocha(
+            project_name,
+            template,
+            mint_recipient,
+            path_alias,
+            pda_from,
+            mints,
+            mint_amount,
+            commitment,
+        )
+    };
+    let mut mocha = File::create(format!(
+        "tests/{project_name}.{}",
+        test_extension(javascript)
+    ))?;
+    mocha.write_all(test.as_bytes())?;
 
     Ok(())
 }
 
-pub fn devbox_json() -> String {
+pub fn devbox_json(anchor_version: &str, solana_version: &str, node_version: &str) -> String {
     format!(
         r#"{{
   "packages": {{
@@ -997,7 +6994,7 @@ pub fn devbox_json() -> String {
       "version": "latest"
     }},
     "nodejs": {{
-      "version": "18"
+      "version": "{node_version}"
     }},
     "yarn": {{
       "version": "latest"
@@ -1022,14 +7019,928 @@ pub fn devbox_json() -> String {
     "init_hook": [
       "curl \"https://sh.rustup.rs\" -sfo rustup.sh && sh rustup.sh -y && rustup component add rustfmt clippy",
       "export PATH=\"${{HOME}}/.cargo/bin:${{PATH}}\"",
-      "sh -c \"$(curl -sSfL https://release.solana.com/v1.18.16/install)\"",
+      "sh -c \"$(curl -sSfL https://release.solana.com/v{solana_version}/install)\"",
       "export PATH=\"$HOME/.local/share/solana/install/active_release/bin:$PATH\"",
       "cargo install --git https://github.com/coral-xyz/anchor avm --locked --force",
-      "avm install {ANCHOR_VERSION}",
-      "avm use latest",
-      "cargo install df-sol"
+      "avm install {anchor_version}",
+      "avm use {anchor_version}",
+      "command -v df-sol >/dev/null || cargo install df-sol"
     ]
   }}
 }}"#
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_from_selection_is_one_based() {
+        assert_eq!(template_from_selection("1"), Some(ProgramTemplate::Basic));
+        assert_eq!(
+            template_from_selection(" 2\n"),
+            Some(ProgramTemplate::Counter)
+        );
+        assert_eq!(template_from_selection("0"), None);
+        assert_eq!(template_from_selection("counter"), None);
+        let past_last = ProgramTemplate::value_variants().len() + 1;
+        assert_eq!(template_from_selection(&past_last.to_string()), None);
+    }
+
+    #[test]
+    fn template_from_str_ignores_case_and_underscores() {
+        assert_eq!(
+            "mint_token".parse::<ProgramTemplate>().unwrap(),
+            ProgramTemplate::MintToken
+        );
+        assert_eq!(
+            " Token-2022 ".parse::<ProgramTemplate>().unwrap(),
+            ProgramTemplate::Token2022
+        );
+        for template in ProgramTemplate::value_variants() {
+            assert_eq!(
+                template.name().parse::<ProgramTemplate>().unwrap(),
+                *template
+            );
+        }
+
+        let err = "countr".parse::<ProgramTemplate>().unwrap_err().to_string();
+        assert!(err.starts_with("Invalid template `countr`. Possible values: basic, counter"));
+    }
+
+    #[test]
+    fn package_json_dependencies_per_template() {
+        for template in ProgramTemplate::value_variants() {
+            let package_json = ts_package_json(
+                "MIT".into(),
+                &[*template],
+                false,
+                "localnet",
+                "0.30.0",
+                false,
+            );
+            let package_json = serde_json::from_str::<serde_json::Value>(&package_json).unwrap();
+            let dependencies = package_json["dependencies"].as_object().unwrap();
+
+            let mut expected = vec![("@coral-xyz/anchor", "^0.30.0")];
+            expected.extend(test_dependencies(*template));
+            let actual = dependencies
+                .iter()
+                .map(|(package, version)| (package.as_str(), version.as_str().unwrap()))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected, "{}", template.name());
+        }
+    }
+
+    #[test]
+    fn package_json_of_several_programs() {
+        let templates = [
+            ProgramTemplate::Nft,
+            ProgramTemplate::Escrow,
+            ProgramTemplate::Counter,
+        ];
+        let package_json =
+            ts_package_json("MIT".into(), &templates, true, "devnet", "0.30.0", true);
+        let package_json = serde_json::from_str::<serde_json::Value>(&package_json).unwrap();
+
+        let keys = |value: &serde_json::Value| {
+            value
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keys(&package_json["dependencies"]),
+            ["@coral-xyz/anchor", "@solana/web3.js", "@solana/spl-token"]
+        );
+        assert_eq!(
+            keys(&package_json["scripts"]),
+            ["lint:fix", "lint", "build", "test", "deploy", "localnet"]
+        );
+        // The scripts are those of the workspace's own program
+        assert_eq!(
+            package_json["scripts"]["test"],
+            "anchor test --skip-deploy --provider.cluster devnet"
+        );
+        assert_eq!(
+            keys(&package_json["devDependencies"]),
+            ["chai", "mocha", "prettier"]
+        );
+    }
+
+    #[test]
+    fn cargo_toml_dependencies_per_template() {
+        for template in ProgramTemplate::value_variants() {
+            let expected = match template {
+                ProgramTemplate::Basic
+                | ProgramTemplate::Counter
+                | ProgramTemplate::Pausable
+                | ProgramTemplate::PdaSigner
+                | ProgramTemplate::Multisig
+                | ProgramTemplate::Crud => r#"anchor-lang = "0.30.0""#,
+                ProgramTemplate::MintToken => {
+                    r#"anchor-lang = { version = "0.30.0", features = ["init-if-needed"] }
+                    anchor-spl = { version = "0.30.0", features = ["metadata"] }"#
+                }
+                ProgramTemplate::Escrow | ProgramTemplate::Vesting | ProgramTemplate::Staking => {
+                    r#"anchor-lang = { version = "0.30.0", features = ["init-if-needed"] }
+                    anchor-spl = "0.30.0""#
+                }
+                ProgramTemplate::Nft => {
+                    r#"anchor-lang = "0.30.0"
+                    anchor-spl = { version = "0.30.0", features = ["metadata"] }"#
+                }
+                ProgramTemplate::Token2022 => {
+                    r#"anchor-lang = { version = "0.30.0", features = ["init-if-needed"] }
+                    anchor-spl = { version = "0.30.0", features = ["token_2022"] }"#
+                }
+                ProgramTemplate::TransferHook => {
+                    r#"anchor-lang = "0.30.0"
+                    anchor-spl = { version = "0.30.0", features = ["token_2022"] }
+                    spl-tlv-account-resolution = "0.6.3"
+                    spl-transfer-hook-interface = "0.6.3""#
+                }
+            };
+            let expected = expected.parse::<toml::Table>().unwrap();
+
+            let manifest = cargo_toml("my-program", *template, "0.30.0")
+                .parse::<toml::Table>()
+                .unwrap();
+            let dependencies = manifest["dependencies"].as_table().unwrap();
+            assert_eq!(dependencies, &expected, "{}", template.name());
+
+            // The IDL of programs using anchor-spl needs its types
+            let idl_build = manifest["features"]["idl-build"].as_array().unwrap();
+            assert_eq!(
+                idl_build.contains(&"anchor-spl/idl-build".into()),
+                expected.contains_key("anchor-spl"),
+                "{}",
+                template.name()
+            );
+        }
+    }
+
+    #[test]
+    fn fast_build_generates_a_lighter_release_profile() {
+        let release_profile = |fast_build| {
+            let files = program_files(
+                "my-program",
+                ProgramTemplate::Basic,
+                Pubkey::new_unique(),
+                ANCHOR_VERSION,
+                None,
+                None,
+                None,
+                fast_build,
+            );
+            let manifest = &files
+                .iter()
+                .find(|(path, _)| path == Path::new("Cargo.toml"))
+                .unwrap()
+                .1;
+            manifest.parse::<toml::Table>().unwrap()["profile"]["release"].clone()
+        };
+
+        let optimized = release_profile(false);
+        assert_eq!(optimized["lto"].as_str(), Some("fat"));
+        assert_eq!(optimized["codegen-units"].as_integer(), Some(1));
+        let fast = release_profile(true);
+        assert_eq!(fast["lto"].as_bool(), Some(false));
+        assert_eq!(fast["codegen-units"].as_integer(), Some(16));
+        assert_eq!(fast["overflow-checks"], optimized["overflow-checks"]);
+    }
+
+    #[test]
+    fn ci_workflows_share_their_setup() {
+        let setup = ci_setup_steps(PackageManager::Pnpm, "1.18.20", "0.29.0");
+        assert!(setup.contains("release.anza.xyz/v1.18.20/install"));
+        assert!(setup.contains("--tag v0.29.0 anchor-cli"));
+
+        let clusters = ["localnet".to_string(), "devnet".to_string()];
+        for workflow in [
+            ci_workflow(
+                &clusters,
+                WALLET_PATH,
+                PackageManager::Pnpm,
+                "1.18.20",
+                "0.29.0",
+            ),
+            github_ci(WALLET_PATH, PackageManager::Pnpm, "1.18.20", "0.29.0"),
+        ] {
+            assert!(workflow.contains(&setup));
+            assert!(workflow.contains("[ -f wallet.json ] || solana-keygen new"));
+        }
+    }
+
+    #[test]
+    fn js_from_ts_requires_the_imports_and_drops_the_types() {
+        let ts = r#"import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import BN from "bn.js";
+import {
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { MyProgram } from "../target/types/my_program";
+
+const program = anchor.workspace.MyProgram as Program<MyProgram>;
+const payer = (provider.wallet as anchor.Wallet).payer;
+let vault: PublicKey;
+let error: anchor.AnchorError | undefined;
+const ata = (mint: PublicKey, owner: PublicKey) => [mint, owner];
+error = err as anchor.AnchorError;
+"#;
+        let js = r#"const anchor = require("@coral-xyz/anchor");
+const BN = require("bn.js");
+const {
+  getAssociatedTokenAddressSync,
+} = require("@solana/spl-token");
+const { PublicKey } = require("@solana/web3.js");
+
+const program = anchor.workspace.MyProgram;
+const payer = provider.wallet.payer;
+let vault;
+let error;
+const ata = (mint, owner) => [mint, owner];
+error = err;
+"#;
+        assert_eq!(js_from_ts(ts), js);
+    }
+
+    #[test]
+    fn js_tests_have_no_typescript_syntax() {
+        for template in ProgramTemplate::value_variants() {
+            let js = js_mocha("my-program", *template, None, None, None, None, None);
+            for ts in ["import ", " as ", ": PublicKey", ": number", "Program<"] {
+                assert!(!js.contains(ts), "{}: {ts}", template.name());
+            }
+            assert!(js.starts_with(r#"const anchor = require("@coral-xyz/anchor");"#));
+        }
+    }
+
+    #[test]
+    fn built_in_tests_pass_their_pdas() {
+        for template in ProgramTemplate::value_variants() {
+            let files = program_files(
+                "my-program",
+                *template,
+                Pubkey::new_unique(),
+                ANCHOR_VERSION,
+                None,
+                None,
+                None,
+                false,
+            );
+            let program = &files
+                .iter()
+                .find(|(path, _)| path.ends_with("src/lib.rs"))
+                .unwrap()
+                .1;
+            let test = ts_mocha("my-program", *template, None, None, None, None, None, None);
+            assert_eq!(
+                resolved_pdas(program, &test),
+                Vec::<String>::new(),
+                "{}",
+                template.name()
+            );
+
+            if *template == ProgramTemplate::Counter {
+                assert_eq!(resolved_pdas(program, ""), ["counter"]);
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_feature_warning_flags_resolved_pdas() {
+        let program = r#"
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = user, space = 8, seeds = [b"user_stats", user.key().as_ref()], bump)]
+    pub user_stats: Account<'info, UserStats>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+"#;
+        let resolved = "await program.methods.initialize().accounts({ user }).rpc();";
+        let explicit = "await program.methods.initialize().accounts({ user, userStats }).rpc();";
+
+        let warning = seeds_feature_warning("[features]\nseeds = false\n", program, resolved);
+        assert!(warning
+            .unwrap()
+            .starts_with("The tests resolve `userStats` from their seeds"));
+        assert!(seeds_feature_warning("[features]\nseeds = true\n", program, resolved).is_none());
+        assert!(seeds_feature_warning("[features]\nseeds = false\n", program, explicit).is_none());
+    }
+
+    #[test]
+    fn anchor_toml_keys_the_program_by_its_snake_case_name() {
+        let id = Pubkey::new_unique().to_string();
+        for template in [ProgramTemplate::Counter, ProgramTemplate::MintToken] {
+            let toml = create_anchor_toml(
+                "foo-bar",
+                id.clone(),
+                "test".into(),
+                WALLET_PATH,
+                template,
+                None,
+                &[],
+            );
+            let table = toml.parse::<toml::Table>().unwrap();
+            for cluster in template.program_clusters() {
+                assert_eq!(
+                    table["programs"][cluster]["foo_bar"].as_str(),
+                    Some(id.as_str())
+                );
+            }
+            assert!(!toml.contains("counter ="), "{toml}");
+        }
+    }
+
+    #[test]
+    fn anchor_toml_lists_mainnet_beta_programs_under_mainnet() {
+        let id = Pubkey::new_unique().to_string();
+        let clusters = ["mainnet".to_string()];
+        for clusters in [&[][..], &clusters] {
+            let toml = create_anchor_toml(
+                "my-program",
+                id.clone(),
+                "test".into(),
+                WALLET_PATH,
+                ProgramTemplate::Counter,
+                Some("mainnet-beta"),
+                clusters,
+            );
+            let table = toml.parse::<toml::Table>().unwrap();
+            assert_eq!(table["provider"]["cluster"].as_str(), Some("mainnet-beta"));
+            assert_eq!(
+                table["programs"]["mainnet"]["my_program"].as_str(),
+                Some(id.as_str())
+            );
+            assert!(!table["programs"]
+                .as_table()
+                .unwrap()
+                .contains_key("mainnet-beta"));
+        }
+    }
+
+    #[test]
+    fn generated_toml_is_consistently_formatted() {
+        let id = Pubkey::new_unique().to_string();
+        let counter = create_anchor_toml(
+            "my-program",
+            id.clone(),
+            "test".into(),
+            WALLET_PATH,
+            ProgramTemplate::Counter,
+            None,
+            &[],
+        );
+        let mint_token = create_anchor_toml(
+            "my-program",
+            id,
+            "test".into(),
+            WALLET_PATH,
+            ProgramTemplate::MintToken,
+            None,
+            &[],
+        );
+
+        for toml in [
+            &counter,
+            &mint_token,
+            &workspace_manifest(false),
+            &cargo_toml("my-program", ProgramTemplate::Nft, ANCHOR_VERSION),
+        ] {
+            assert!(toml.parse::<toml::Table>().is_ok(), "{toml}");
+            // Tables are separated by exactly one blank line
+            for (i, line) in toml.lines().enumerate().skip(1) {
+                if line.starts_with('[') {
+                    let previous = toml.lines().nth(i - 1).unwrap();
+                    assert_eq!(previous, "", "No blank line before {line}:\n{toml}");
+                }
+            }
+            assert!(!toml.contains("\n\n\n"), "{toml}");
+        }
+        assert!(mint_token.contains("\n\n[programs.localnet]\n"));
+    }
+
+    #[test]
+    fn mint_tests_mint_the_given_amount_in_base_units() {
+        for mints in [None, Some(2)] {
+            let test = ts_mocha(
+                "my-program",
+                ProgramTemplate::MintToken,
+                None,
+                None,
+                None,
+                mints,
+                Some(MAX_MINT_AMOUNT),
+                None,
+            );
+            assert!(test.contains("const mintAmount = 18446744073;"), "{test}");
+            assert!(
+                test.contains("new BN(mintAmount).mul(new BN(10).pow("),
+                "{test}"
+            );
+            assert!(!test.contains("mintAmount * 10 **"), "{test}");
+        }
+    }
+
+    #[test]
+    fn concurrent_callers_converge_on_one_keypair() {
+        let dir = tempfile::tempdir().unwrap();
+        let keypair_path = dir.path().join("deploy").join("program-keypair.json");
+
+        let pubkeys = std::thread::scope(|scope| {
+            let handles = (0..8)
+                .map(|_| scope.spawn(|| get_or_create_keypair(&keypair_path)))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        let keypair = read_keypair_file(&keypair_path).unwrap();
+        assert!(pubkeys.iter().all(|pubkey| *pubkey == keypair.pubkey()));
+        let files = fs::read_dir(keypair_path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(files, ["program-keypair.json"]);
+    }
+
+    #[test]
+    fn mint_test_mints_to_the_associated_account_of_the_recipient() {
+        let recipient = Pubkey::new_unique();
+        let test = ts_mocha(
+            "my-program",
+            ProgramTemplate::MintToken,
+            Some(recipient),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(
+            test.contains(&format!(
+                r#"const recipient = new PublicKey("{recipient}");"#
+            )),
+            "{test}"
+        );
+        assert!(test.contains(
+            "const destination = anchor.utils.token.associatedAddress({\n      mint: mint,\n      owner: recipient,\n    });"
+        ));
+
+        let test = ts_mocha(
+            "my-program",
+            ProgramTemplate::MintToken,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(test.contains("const recipient = payer;"), "{test}");
+    }
+
+    #[test]
+    fn counter_test_derives_the_pda_from_the_seeds_of_the_program() {
+        // Seeds of the program with their equivalent in the test, whose wallet signs as `user`
+        let equivalents = [
+            (r#"b"counter""#, r#"Buffer.from("counter")"#),
+            (
+                "user.key().as_ref()",
+                "provider.wallet.publicKey.toBuffer()",
+            ),
+        ];
+        let rust_seeds = Regex::new(r"seeds = \[(.*?)\]").unwrap();
+        let ts_seeds = Regex::new(r"(?s)findProgramAddress\(\s*\[(.*?)\],").unwrap();
+        for pda_from in [None, Some(PdaSeed::User)] {
+            let files = program_files(
+                "my-program",
+                ProgramTemplate::Counter,
+                Pubkey::new_unique(),
+                ANCHOR_VERSION,
+                pda_from,
+                None,
+                None,
+                false,
+            );
+            let program = &files
+                .iter()
+                .find(|(path, _)| path.ends_with("src/lib.rs"))
+                .unwrap()
+                .1;
+            let test = ts_mocha(
+                "my-program",
+                ProgramTemplate::Counter,
+                None,
+                None,
+                pda_from,
+                None,
+                None,
+                None,
+            );
+
+            let seeds = rust_seeds
+                .captures_iter(program)
+                .map(|captures| captures[1].to_string())
+                .collect::<BTreeSet<_>>();
+            assert_eq!(seeds.len(), 1, "{pda_from:?}: {seeds:?}");
+            let expected = seeds
+                .first()
+                .unwrap()
+                .split(", ")
+                .map(|seed| {
+                    let (_, ts) = equivalents.iter().find(|(rust, _)| *rust == seed).unwrap();
+                    *ts
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            assert_eq!(
+                ts_seeds.captures(&test).unwrap()[1],
+                expected,
+                "{pda_from:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn counter_loadtest_calls_increment() {
+        let script = loadtest_script("my-program", ProgramTemplate::Counter);
+
+        assert!(
+            script.contains("program.methods\n      .increment()\n"),
+            "{script}"
+        );
+        assert!(script.starts_with("// Load test firing concurrent `increment` transactions"));
+    }
+
+    #[test]
+    fn mint_test_confirms_with_the_chosen_commitment() {
+        let test = ts_mocha(
+            "my-program",
+            ProgramTemplate::MintToken,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Commitment::Confirmed),
+        );
+
+        assert!(
+            test.contains(r#"{ commitment: "confirmed", preflightCommitment: "confirmed" }"#),
+            "{test}"
+        );
+        assert_eq!(
+            test.matches(r#"confirmTransaction(txHash, "confirmed")"#)
+                .count(),
+            2,
+            "{test}"
+        );
+        assert!(!test.contains("finalized"), "{test}");
+    }
+
+    #[test]
+    fn counter_client_exports_its_instructions() {
+        let client = ts_client("my-program", ProgramTemplate::Counter);
+
+        assert!(
+            client.contains("export async function initialize("),
+            "{client}"
+        );
+        assert!(
+            client.contains("export async function increment(program: Program<IDL>)"),
+            "{client}"
+        );
+    }
+
+    #[test]
+    fn fund_wallet_hook_airdrops_on_localnet_only() {
+        for hook in [ts_fund_wallet_hook().to_string(), js_fund_wallet_hook()] {
+            let guard = hook
+                .find(
+                    r#"if (!endpoint.includes("localhost") && !endpoint.includes("127.0.0.1")) {
+    return;
+  }"#,
+                )
+                .unwrap_or_else(|| panic!("No cluster check in {hook}"));
+            let airdrop = hook.find("requestAirdrop(").unwrap();
+            assert!(guard < airdrop, "{hook}");
+        }
+    }
+
+    #[test]
+    fn makefile_has_a_test_target() {
+        let counter = makefile(ProgramTemplate::Counter, "localnet");
+        assert!(
+            counter.contains("\ntest:\n\tanchor test --provider.cluster $(CLUSTER)\n"),
+            "{counter}"
+        );
+        assert!(counter.contains("\nCLUSTER ?= localnet\n"));
+
+        let mint_token = makefile(ProgramTemplate::MintToken, "devnet");
+        assert!(
+            mint_token
+                .contains("\ntest:\n\tanchor test --skip-deploy --provider.cluster $(CLUSTER)\n"),
+            "{mint_token}"
+        );
+    }
+
+    #[test]
+    fn metadata_program_is_constrained_to_the_metaplex_id() {
+        let programs = [
+            (ProgramTemplate::MintToken, None),
+            (ProgramTemplate::MintToken, Some(2)),
+            (ProgramTemplate::Nft, None),
+        ];
+        for (template, mints) in programs {
+            let files = program_files(
+                "my-program",
+                template,
+                Pubkey::new_unique(),
+                ANCHOR_VERSION,
+                None,
+                mints,
+                None,
+                false,
+            );
+            let program = &files
+                .iter()
+                .find(|(path, _)| path.ends_with("src/lib.rs"))
+                .unwrap()
+                .1;
+
+            let constrained = "#[account(address = mpl_token_metadata::ID)]\n    pub token_metadata_program: UncheckedAccount<'info>,";
+            assert_eq!(
+                program.matches("pub token_metadata_program:").count(),
+                program.matches(constrained).count(),
+                "{template:?} {mints:?}"
+            );
+            assert!(program.contains(constrained), "{template:?} {mints:?}");
+        }
+    }
+
+    #[test]
+    fn mints_derive_the_mint_from_its_index() {
+        let lib_rs = |mints| {
+            program_files(
+                "my-program",
+                ProgramTemplate::MintToken,
+                Pubkey::new_unique(),
+                ANCHOR_VERSION,
+                None,
+                mints,
+                None,
+                false,
+            )
+            .into_iter()
+            .find(|(path, _)| path.ends_with("src/lib.rs"))
+            .unwrap()
+            .1
+        };
+
+        let program = lib_rs(Some(2));
+        assert!(
+            program.contains(r#"seeds = [b"mint", index.to_le_bytes().as_ref()],"#),
+            "{program}"
+        );
+        assert!(!program.contains(r#"seeds = [b"mint"],"#));
+        assert!(program.contains("pub const MINT_COUNT: u8 = 2;"));
+
+        let program = lib_rs(None);
+        assert!(program.contains(r#"seeds = [b"mint"],"#), "{program}");
+        assert!(!program.contains("index"));
+    }
+
+    /// Code of the first variant of each `#[error_code]` enum of a program, Anchor numbering the
+    /// variants from the `offset` of the attribute, 6000 without one.
+    fn first_error_codes(program: &str) -> Vec<u32> {
+        let file = syn::parse_file(program).unwrap();
+        let mut items = file.items;
+        // Error enums may be declared in the `#[program]` module too
+        let mut enums = vec![];
+        while let Some(item) = items.pop() {
+            match item {
+                syn::Item::Mod(module) => {
+                    items.extend(module.content.map(|(_, items)| items).unwrap_or_default())
+                }
+                syn::Item::Enum(item) => enums.push(item),
+                _ => {}
+            }
+        }
+
+        enums
+            .iter()
+            .filter_map(|item| {
+                let attr = item
+                    .attrs
+                    .iter()
+                    .find(|attr| attr.path().is_ident("error_code"))?;
+                assert!(
+                    item.variants
+                        .iter()
+                        .all(|variant| variant.discriminant.is_none()),
+                    "{} sets its codes",
+                    item.ident
+                );
+                let offset = match &attr.meta {
+                    syn::Meta::List(list) => {
+                        let offset = list.parse_args::<syn::MetaNameValue>().unwrap();
+                        assert!(offset.path.is_ident("offset"));
+                        let syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Int(offset),
+                            ..
+                        }) = offset.value
+                        else {
+                            panic!("Invalid offset of {}", item.ident);
+                        };
+                        offset.base10_parse().unwrap()
+                    }
+                    _ => 6000,
+                };
+                Some(offset)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn error_codes_start_at_the_error_base() {
+        let templates = ProgramTemplate::value_variants()
+            .iter()
+            .map(|template| (*template, None))
+            .chain([(ProgramTemplate::MintToken, Some(2))]);
+        let mut with_errors = vec![];
+        for (template, mints) in templates {
+            for (error_base, first_code) in [(None, 6000), (Some(7000), 7000)] {
+                let files = program_files(
+                    "my-program",
+                    template,
+                    Pubkey::new_unique(),
+                    ANCHOR_VERSION,
+                    None,
+                    mints,
+                    error_base,
+                    false,
+                );
+                let program = &files
+                    .iter()
+                    .find(|(path, _)| path.ends_with("src/lib.rs"))
+                    .unwrap()
+                    .1;
+
+                let codes = first_error_codes(program);
+                assert!(
+                    codes.iter().all(|code| *code == first_code),
+                    "{template:?} {mints:?} {error_base:?}: {codes:?}"
+                );
+                if !codes.is_empty() && error_base.is_none() {
+                    with_errors.push((template, mints));
+                }
+            }
+        }
+
+        assert_eq!(
+            with_errors,
+            [
+                (ProgramTemplate::Pausable, None),
+                (ProgramTemplate::Vesting, None),
+                (ProgramTemplate::Staking, None),
+                (ProgramTemplate::Multisig, None),
+                (ProgramTemplate::Crud, None),
+                (ProgramTemplate::MintToken, Some(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn npm_scripts_wrap_the_anchor_commands() {
+        let scripts = |template, npm_scripts, cluster| {
+            let package_json = ts_package_json(
+                "MIT".into(),
+                &[template],
+                npm_scripts,
+                cluster,
+                "0.30.0",
+                false,
+            );
+            serde_json::from_str::<serde_json::Value>(&package_json).unwrap()["scripts"].clone()
+        };
+
+        let counter = scripts(ProgramTemplate::Counter, true, "localnet");
+        assert_eq!(counter["build"], "anchor build");
+        assert_eq!(counter["test"], "anchor test");
+        assert_eq!(
+            counter["deploy"],
+            "anchor deploy --provider.cluster localnet"
+        );
+        assert_eq!(counter["localnet"], "solana-test-validator --reset");
+
+        let mint_token = scripts(ProgramTemplate::MintToken, true, "devnet");
+        assert_eq!(mint_token["build"], "anchor build");
+        assert_eq!(
+            mint_token["deploy"],
+            "anchor deploy --provider.cluster devnet"
+        );
+
+        let counter = scripts(ProgramTemplate::Counter, false, "localnet");
+        assert!(counter.get("build").is_none(), "{counter}");
+    }
+
+    #[test]
+    fn justfile_of_each_template() {
+        assert_eq!(
+            justfile(ProgramTemplate::Counter, PackageManager::Pnpm, "localnet"),
+            r#"cluster := "localnet"
+
+build:
+    anchor build
+
+test:
+    anchor test --provider.cluster {{cluster}}
+
+deploy: build
+    anchor deploy --provider.cluster {{cluster}}
+
+lint:
+    cargo fmt --all -- --check
+    pnpm run lint
+"#
+        );
+
+        for template in ProgramTemplate::value_variants() {
+            let cluster = template.default_cluster();
+            let justfile = justfile(*template, PackageManager::Yarn, cluster);
+
+            assert!(
+                justfile.starts_with(&format!("cluster := \"{cluster}\"\n")),
+                "{template:?}: {justfile}"
+            );
+            for recipe in ["build:", "test:", "deploy: build", "lint:"] {
+                assert!(
+                    justfile.contains(&format!("\n{recipe}\n")),
+                    "{template:?}: {justfile}"
+                );
+            }
+            assert!(justfile.contains("\n    yarn run lint\n"), "{template:?}");
+            // Live cluster programs are deployed before testing, as their READMEs describe
+            let skip_deploy = matches!(template, ProgramTemplate::MintToken | ProgramTemplate::Nft);
+            assert_eq!(
+                justfile.contains("anchor test --skip-deploy --provider.cluster {{cluster}}"),
+                skip_deploy,
+                "{template:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn dockerfile_of_each_package_manager() {
+        for package_manager in PackageManager::value_variants() {
+            let dockerfile = dockerfile("1.18.16", "0.30.0", *package_manager);
+            let command = package_manager.command();
+
+            assert!(
+                dockerfile.contains("\nARG SOLANA_VERSION=1.18.16\nARG ANCHOR_VERSION=0.30.0\n")
+            );
+            assert!(
+                dockerfile.contains(&format!("\nRUN {command} install\n")),
+                "{package_manager:?}: {dockerfile}"
+            );
+            // npm is installed with node, the others globally with npm
+            assert_eq!(
+                dockerfile.contains("RUN npm install -g "),
+                *package_manager != PackageManager::Npm,
+                "{package_manager:?}"
+            );
+            if *package_manager != PackageManager::Npm {
+                assert!(dockerfile.contains(&format!("\nRUN npm install -g {command}\n")));
+            }
+            assert!(dockerfile.ends_with("ENTRYPOINT [\"anchor\"]\nCMD [\"test\"]\n"));
+        }
+    }
+
+    #[test]
+    fn readmes_differ_per_template() {
+        let basic = readme(ProgramTemplate::Basic);
+        let counter = readme(ProgramTemplate::Counter);
+        assert_ne!(basic, counter);
+        assert!(basic.contains("anchor build"), "{basic}");
+        assert!(!basic.contains("increment"), "{basic}");
+        for expected in ["`initialize`", "`increment`", r#"`"counter"` seed"#] {
+            assert!(counter.contains(expected), "{expected}: {counter}");
+        }
+
+        let readmes = ProgramTemplate::value_variants()
+            .iter()
+            .map(|template| readme(*template))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(readmes.len(), ProgramTemplate::value_variants().len());
+    }
+}
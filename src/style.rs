@@ -0,0 +1,60 @@
+//! ANSI styling of the CLI output, enabled or disabled once at startup by `--color`.
+
+use clap::ValueEnum;
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// When to color the output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable colors for the rest of the process.
+pub fn init(choice: ColorChoice) {
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            // https://no-color.org: any non-empty value disables colors
+            let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+            !no_color && std::io::stdout().is_terminal()
+        }
+    };
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+fn paint(code: &str, text: impl Display) -> String {
+    if ENABLED.load(Ordering::Relaxed) {
+        format!("\x1b[{code}m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Text reporting a completed action
+pub fn success(text: impl Display) -> String {
+    paint("32", text)
+}
+
+/// Text of a warning
+pub fn warning(text: impl Display) -> String {
+    paint("33", text)
+}
+
+/// Text reporting a failure
+pub fn error(text: impl Display) -> String {
+    paint("31", text)
+}
+
+/// Emphasized text, e.g. names and values
+pub fn bold(text: impl Display) -> String {
+    paint("1", text)
+}
@@ -0,0 +1,128 @@
+//! Workspaces rendered from a user-provided template directory, see `df-sol init --template-dir`.
+//!
+//! Every file of the directory is copied to the same relative path of the workspace, with these
+//! placeholders replaced in both its contents and its path:
+//!
+//! - `{{name}}`: workspace name, e.g. `my-program`
+//! - `{{snake_name}}`: workspace name in snake case, e.g. `my_program`
+//! - `{{pascal_name}}`: workspace name in pascal case, e.g. `MyProgram`
+//! - `{{program_id}}`: program ID of the generated program keypair
+//! - `{{anchor_version}}`: Anchor version of the workspace, e.g. `0.30.0`
+//!
+//! Files that aren't UTF-8 text, e.g. images, are copied as they are.
+
+use crate::rust_template::{self, ProgramTemplate};
+use crate::Files;
+use anyhow::{anyhow, Result};
+use heck::{ToPascalCase, ToSnakeCase};
+use solana_sdk::pubkey::Pubkey;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Workspace name the built-in templates are generated with before their placeholders are put
+/// back. Its cases are distinct from each other and don't appear in the templates otherwise.
+const TEMPLATE_NAME: &str = "df-sol-template-name";
+
+/// Anchor version the built-in templates are generated with before their placeholders are put back
+const TEMPLATE_ANCHOR_VERSION: &str = "0.0.0-df-sol-template";
+
+/// Program ID the built-in templates are generated with before their placeholders are put back
+const TEMPLATE_PROGRAM_ID: Pubkey = Pubkey::new_from_array([1; 32]);
+
+/// Files copied as they are, by their path relative to the workspace.
+pub type BinaryFiles = Vec<(PathBuf, Vec<u8>)>;
+
+/// Render the files of `dir`, replacing the placeholders in their contents and relative paths.
+/// Files that aren't UTF-8, e.g. images, are returned apart with only their paths rendered.
+pub fn render(
+    dir: &Path,
+    name: &str,
+    program_id: Pubkey,
+    anchor_version: &str,
+) -> Result<(Files, BinaryFiles)> {
+    let placeholders = [
+        ("name", name.to_string()),
+        ("snake_name", name.to_snake_case()),
+        ("pascal_name", name.to_pascal_case()),
+        ("program_id", program_id.to_string()),
+        ("anchor_version", anchor_version.to_string()),
+    ];
+    let render = |text: &str| {
+        placeholders
+            .iter()
+            .fold(text.to_string(), |text, (placeholder, value)| {
+                text.replace(&format!("{{{{{placeholder}}}}}"), value)
+            })
+    };
+
+    let mut files = vec![];
+    let mut binary_files = vec![];
+    let walker = walkdir::WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git");
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = PathBuf::from(render(&entry.path().strip_prefix(dir)?.to_string_lossy()));
+        let content = fs::read(entry.path())
+            .map_err(|e| anyhow!("Failed to read {}: {e}", entry.path().display()))?;
+        match String::from_utf8(content) {
+            Ok(content) => files.push((path, render(&content))),
+            Err(e) => binary_files.push((path, e.into_bytes())),
+        }
+    }
+
+    if files.is_empty() && binary_files.is_empty() {
+        return Err(anyhow!("Template directory {} has no files", dir.display()));
+    }
+    Ok((files, binary_files))
+}
+
+/// Program and test files of a built-in template with the placeholders in place of the name,
+/// program ID and Anchor version, the reverse of [`render`].
+pub fn from_built_in(template: ProgramTemplate) -> Files {
+    let placeholders = [
+        (TEMPLATE_NAME.to_pascal_case(), "{{pascal_name}}"),
+        (TEMPLATE_NAME.to_snake_case(), "{{snake_name}}"),
+        (TEMPLATE_NAME.to_string(), "{{name}}"),
+        (TEMPLATE_PROGRAM_ID.to_string(), "{{program_id}}"),
+        (TEMPLATE_ANCHOR_VERSION.to_string(), "{{anchor_version}}"),
+    ];
+    let templatize = |text: &str| {
+        placeholders
+            .iter()
+            .fold(text.to_string(), |text, (value, placeholder)| {
+                text.replace(value, placeholder)
+            })
+    };
+
+    // The workspace Cargo.toml is the same for every template
+    let mut files = rust_template::program_files(
+        TEMPLATE_NAME,
+        template,
+        TEMPLATE_PROGRAM_ID,
+        TEMPLATE_ANCHOR_VERSION,
+        None,
+        None,
+        None,
+        false,
+    );
+    files.retain(|(path, _)| path.starts_with("programs"));
+    files.push((
+        Path::new("tests").join(format!("{TEMPLATE_NAME}.ts")),
+        rust_template::ts_mocha(TEMPLATE_NAME, template, None, None, None, None, None, None),
+    ));
+
+    files
+        .into_iter()
+        .map(|(path, content)| {
+            (
+                PathBuf::from(templatize(&path.to_string_lossy())),
+                templatize(&content),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,85 @@
+//! `add` scaffolding another program into a workspace created with `init`.
+
+use solana_sdk::signature::read_keypair_file;
+use solana_sdk::signer::Signer;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn df_sol(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+fn init(dir: &Path, args: &[&str]) -> std::path::PathBuf {
+    let output = df_sol(
+        dir,
+        &[&["init", "my-program", "--no-install", "--no-git"], args].concat(),
+    );
+    assert!(output.status.success(), "init failed: {output:?}");
+    dir.join("my-program")
+}
+
+fn add(workspace: &Path, args: &[&str]) {
+    let output = df_sol(workspace, &[&["add"], args].concat());
+    assert!(output.status.success(), "add {args:?} failed: {output:?}");
+}
+
+#[test]
+fn added_programs_depend_on_the_anchor_version_of_the_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--anchor-version", "0.29.0"]);
+    add(&workspace, &["vault", "--template", "pda-signer"]);
+
+    let cargo_toml = fs::read_to_string(workspace.join("programs/vault/Cargo.toml")).unwrap();
+    let cargo_toml = cargo_toml.parse::<toml::Table>().unwrap();
+    let dependencies = &cargo_toml["dependencies"];
+    assert_eq!(dependencies["anchor-lang"].as_str(), Some("0.29.0"));
+}
+
+#[test]
+fn added_programs_are_listed_in_anchor_toml() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &[]);
+    add(&workspace, &["vault", "--template", "pda-signer"]);
+
+    assert!(workspace.join("programs/vault/src/lib.rs").is_file());
+    assert!(workspace.join("tests/vault.ts").is_file());
+    let keypair = workspace.join("target/deploy/vault-keypair.json");
+    let program_id = read_keypair_file(keypair).unwrap().pubkey().to_string();
+    let lib_rs = fs::read_to_string(workspace.join("programs/vault/src/lib.rs")).unwrap();
+    assert!(lib_rs.contains(&format!("declare_id!(\"{program_id}\");")));
+
+    let anchor_toml = fs::read_to_string(workspace.join("Anchor.toml")).unwrap();
+    let anchor_toml = anchor_toml.parse::<toml::Table>().unwrap();
+    let localnet = &anchor_toml["programs"]["localnet"];
+    assert_eq!(localnet["vault"].as_str(), Some(program_id.as_str()));
+    assert!(localnet.get("my_program").is_some());
+}
+
+#[test]
+fn adding_an_existing_program_needs_the_skip_flag() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &[]);
+    add(&workspace, &["vault"]);
+    let program = workspace.join("programs/vault");
+    fs::write(program.join("src/lib.rs"), "// Edited\n").unwrap();
+    fs::remove_file(program.join("Xargo.toml")).unwrap();
+
+    let output = df_sol(&workspace, &["add", "vault"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("already exists"), "{stderr}");
+    assert!(stderr.contains("--skip-existing-program-check"), "{stderr}");
+    assert!(!program.join("Xargo.toml").exists());
+
+    // Existing files are kept and the missing ones added
+    add(&workspace, &["vault", "--skip-existing-program-check"]);
+    let lib_rs = fs::read_to_string(program.join("src/lib.rs")).unwrap();
+    assert_eq!(lib_rs, "// Edited\n");
+    assert!(program.join("Xargo.toml").is_file());
+}
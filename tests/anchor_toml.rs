@@ -0,0 +1,111 @@
+//! Anchor.toml of workspaces created with `init`.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+fn init(dir: &Path, args: &[&str]) -> toml::Table {
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(["init", "my-program", "--no-install", "--no-git"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "df-sol init {args:?} failed");
+
+    let anchor_toml = fs::read_to_string(dir.join("my-program/Anchor.toml")).unwrap();
+    anchor_toml.parse().unwrap()
+}
+
+#[test]
+fn test_cluster_overrides_the_provider_cluster_for_tests_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml = init(
+        dir.path(),
+        &[
+            "--cluster",
+            "devnet",
+            "--test-cluster",
+            "http://127.0.0.1:8899",
+        ],
+    );
+
+    assert_eq!(toml["provider"]["cluster"].as_str(), Some("devnet"));
+    assert_eq!(
+        toml["test"]["provider"]["cluster"].as_str(),
+        Some("http://127.0.0.1:8899")
+    );
+    let test_script = toml["scripts"]["test"].as_str().unwrap();
+    assert!(
+        test_script.starts_with("ANCHOR_PROVIDER_URL=http://127.0.0.1:8899 "),
+        "{test_script}"
+    );
+}
+
+#[test]
+fn tests_run_on_the_provider_cluster_without_a_test_cluster() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml = init(dir.path(), &["--cluster", "devnet"]);
+
+    assert!(!toml.contains_key("test"));
+    assert!(!toml["scripts"]["test"]
+        .as_str()
+        .unwrap()
+        .contains("ANCHOR_PROVIDER_URL"));
+}
+
+#[test]
+fn preload_metaplex_clones_the_metadata_program() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml = init(
+        dir.path(),
+        &["--template", "mint-token", "--preload-metaplex"],
+    );
+
+    let validator = &toml["test"]["validator"];
+    assert_eq!(
+        validator["url"].as_str(),
+        Some("https://api.devnet.solana.com")
+    );
+    let clones = validator["clone"].as_array().unwrap();
+    assert!(clones
+        .iter()
+        .any(|clone| clone["address"].as_str()
+            == Some("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")));
+}
+
+#[test]
+fn cluster_sets_the_provider_cluster_and_lists_the_program_under_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml = init(dir.path(), &["--cluster", "testnet"]);
+    assert_eq!(toml["provider"]["cluster"].as_str(), Some("testnet"));
+    assert!(toml["programs"]["testnet"].get("my_program").is_some());
+
+    let dir = tempfile::tempdir().unwrap();
+    let toml = init(
+        dir.path(),
+        &["--template", "mint-token", "--cluster", "testnet"],
+    );
+    assert_eq!(toml["provider"]["cluster"].as_str(), Some("testnet"));
+    let programs = toml["programs"].as_table().unwrap();
+    let id = &programs["localnet"]["my_program"];
+    assert_eq!(&programs["testnet"]["my_program"], id);
+    assert_eq!(&programs["devnet"]["my_program"], id);
+}
+
+#[test]
+fn clusters_list_the_program_under_each_cluster() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml = init(
+        dir.path(),
+        &["--template", "counter", "--clusters", "localnet,devnet"],
+    );
+
+    let programs = toml["programs"].as_table().unwrap();
+    assert_eq!(programs.len(), 2);
+    let id = programs["localnet"]["my_program"].as_str().unwrap();
+    assert_eq!(programs["devnet"]["my_program"].as_str(), Some(id));
+    // The provider cluster stays the template's
+    assert_eq!(toml["provider"]["cluster"].as_str(), Some("Localnet"));
+}
@@ -0,0 +1,38 @@
+//! Coloring of the output with `--color`.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Output of `init` run with `--color <color>`, stdout then stderr.
+fn init_output(dir: &Path, color: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(["init", "my-program", "--no-install", "--no-git"])
+        .args(["--color", color])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    String::from_utf8(output.stdout).unwrap() + &String::from_utf8(output.stderr).unwrap()
+}
+
+#[test]
+fn color_never_writes_no_ansi_escapes() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = init_output(dir.path(), "never");
+
+    assert!(output.contains("my-program initialized"), "{output}");
+    assert!(!output.contains('\x1b'), "{output:?}");
+}
+
+#[test]
+fn color_always_colors_the_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = init_output(dir.path(), "always");
+
+    assert!(
+        output.contains("\x1b[32mmy-program initialized"),
+        "{output:?}"
+    );
+}
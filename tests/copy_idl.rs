@@ -0,0 +1,52 @@
+//! `copy-idl` of the IDL and types built by `anchor build`.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn copy_idl(dir: &Path, out_dir: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .args(["copy-idl", out_dir])
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn copy_idl_copies_the_idl_and_types() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("target");
+    let idl = r#"{"address":"11111111111111111111111111111111","instructions":[]}"#;
+    let types = "export type MyProgram = {};\n";
+    fs::create_dir_all(target.join("idl")).unwrap();
+    fs::create_dir_all(target.join("types")).unwrap();
+    fs::write(target.join("idl/my_program.json"), idl).unwrap();
+    fs::write(target.join("types/my_program.ts"), types).unwrap();
+    // Neither an IDL nor types
+    fs::write(target.join("idl/notes.txt"), "").unwrap();
+
+    let output = copy_idl(dir.path(), "app/idl");
+    assert!(output.status.success(), "{output:?}");
+
+    let out_dir = dir.path().join("app/idl");
+    assert_eq!(
+        fs::read_to_string(out_dir.join("my_program.json")).unwrap(),
+        idl
+    );
+    assert_eq!(
+        fs::read_to_string(out_dir.join("my_program.ts")).unwrap(),
+        types
+    );
+    assert!(!out_dir.join("notes.txt").exists());
+}
+
+#[test]
+fn copy_idl_needs_a_build() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = copy_idl(dir.path(), "app/idl");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("run `anchor build` first"), "{stderr}");
+    assert!(!dir.path().join("app/idl").exists());
+}
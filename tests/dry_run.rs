@@ -0,0 +1,47 @@
+//! `init --dry-run` failing where the actual run would, without writing anything.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn df_sol(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn dry_run_rejects_an_existing_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("my-program")).unwrap();
+    fs::write(dir.path().join("my-program/notes.txt"), "").unwrap();
+
+    let output = df_sol(dir.path(), &["init", "my-program", "--dry-run"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Pass `--force`"));
+
+    // `--force` only reinitializes Anchor workspaces
+    let output = df_sol(dir.path(), &["init", "my-program", "--dry-run", "--force"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is not an Anchor workspace"));
+    assert_eq!(
+        fs::read_dir(dir.path().join("my-program")).unwrap().count(),
+        1
+    );
+}
+
+#[test]
+fn dry_run_accepts_an_empty_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("my-program")).unwrap();
+
+    let output = df_sol(dir.path(), &["init", "my-program", "--dry-run"]);
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_dir(dir.path().join("my-program")).unwrap().count(),
+        0
+    );
+}
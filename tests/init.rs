@@ -0,0 +1,300 @@
+//! Files generated by `init` with its optional flags.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+/// Workspace created by `init` with `args`.
+fn init(dir: &Path, args: &[&str]) -> PathBuf {
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(["init", "my-program", "--no-install", "--no-git"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "df-sol init {args:?} failed");
+
+    dir.join("my-program")
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn with_vscode_recommends_rust_analyzer() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--with-vscode"]);
+
+    let settings = read_json(&workspace.join(".vscode/settings.json"));
+    assert_eq!(
+        settings["[rust]"]["editor.defaultFormatter"],
+        "rust-lang.rust-analyzer"
+    );
+    let extensions = read_json(&workspace.join(".vscode/extensions.json"));
+    let recommendations = extensions["recommendations"].as_array().unwrap();
+    assert!(recommendations.contains(&"rust-lang.rust-analyzer".into()));
+}
+
+#[test]
+fn rust_only_generates_no_typescript() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--rust-only"]);
+
+    for path in [
+        "package.json",
+        "tsconfig.json",
+        "tests",
+        "migrations",
+        ".prettierignore",
+    ] {
+        assert!(!workspace.join(path).exists(), "{path} was generated");
+    }
+    for path in [
+        "Anchor.toml",
+        "Cargo.toml",
+        "wallet.json",
+        ".gitignore",
+        "programs/my-program/src/lib.rs",
+    ] {
+        assert!(workspace.join(path).exists(), "{path} is missing");
+    }
+}
+
+#[test]
+fn show_program_id_prints_the_ids_of_anchor_toml() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .args(["init", "my-program", "--no-install", "--no-git"])
+        .args(["--template", "basic,counter", "--show-program-id"])
+        .args(["--color", "never"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let printed = |label: &str| {
+        stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(label))
+            .unwrap_or_else(|| panic!("No `{label}` in {stdout}"))
+            .to_string()
+    };
+
+    let anchor_toml = fs::read_to_string(dir.path().join("my-program/Anchor.toml"))
+        .unwrap()
+        .parse::<toml::Table>()
+        .unwrap();
+    let programs = &anchor_toml["programs"]["localnet"];
+    assert_eq!(
+        printed("Program ID: "),
+        programs["my_program"].as_str().unwrap()
+    );
+    assert_eq!(
+        printed("declare_id!: "),
+        programs["my_program"].as_str().unwrap()
+    );
+    assert_eq!(
+        printed("Program ID of counter: "),
+        programs["counter"].as_str().unwrap()
+    );
+}
+
+#[test]
+fn deploy_script_deploys_with_the_chosen_loader() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--with-deploy-script", "--loader", "final"]);
+    let script = fs::read_to_string(workspace.join("scripts/deploy.sh")).unwrap();
+    assert!(
+        script.contains(
+            r#"solana program deploy "$PROGRAM_SO" --program-id "$PROGRAM_KEYPAIR" --final "$@""#
+        ),
+        "{script}"
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--with-deploy-script"]);
+    let script = fs::read_to_string(workspace.join("scripts/deploy.sh")).unwrap();
+    assert!(
+        script.contains(
+            r#"solana program deploy "$PROGRAM_SO" --program-id "$PROGRAM_KEYPAIR" "$@""#
+        ),
+        "{script}"
+    );
+}
+
+#[test]
+fn with_anchorversion_writes_the_anchor_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(
+        dir.path(),
+        &["--with-anchorversion", "--anchor-version", "0.29.0"],
+    );
+
+    let version = fs::read_to_string(workspace.join(".anchorversion")).unwrap();
+    assert_eq!(version, "0.29.0\n");
+}
+
+#[test]
+fn with_security_txt_embeds_a_security_txt() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(
+        dir.path(),
+        &[
+            "--with-security-txt",
+            "--contact",
+            "security@my-program.dev",
+        ],
+    );
+    let program = workspace.join("programs/my-program");
+
+    let lib_rs = fs::read_to_string(program.join("src/lib.rs")).unwrap();
+    assert!(
+        lib_rs.contains("solana_security_txt::security_txt! {"),
+        "{lib_rs}"
+    );
+    assert!(lib_rs.contains(r#"contacts: "email:security@my-program.dev","#));
+    let manifest = fs::read_to_string(program.join("Cargo.toml"))
+        .unwrap()
+        .parse::<toml::Table>()
+        .unwrap();
+    assert!(manifest["dependencies"]
+        .as_table()
+        .unwrap()
+        .contains_key("solana-security-txt"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &[]);
+    let lib_rs = fs::read_to_string(workspace.join("programs/my-program/src/lib.rs")).unwrap();
+    assert!(!lib_rs.contains("security_txt!"));
+}
+
+fn test_script(workspace: &Path) -> String {
+    let anchor_toml = fs::read_to_string(workspace.join("Anchor.toml"))
+        .unwrap()
+        .parse::<toml::Table>()
+        .unwrap();
+    anchor_toml["scripts"]["test"].as_str().unwrap().into()
+}
+
+#[test]
+fn parallel_tests_run_mocha_in_parallel() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--parallel-tests"]);
+
+    let script = test_script(&workspace);
+    assert!(script.contains(" --parallel "), "{script}");
+    let mocharc = read_json(&workspace.join(".mocharc.json"));
+    assert_eq!(mocharc["parallel"], true);
+
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &[]);
+    assert!(!test_script(&workspace).contains("--parallel"));
+    assert!(!workspace.join(".mocharc.json").exists());
+}
+
+#[test]
+fn strict_warnings_denies_warnings_in_cargo_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--strict-warnings"]);
+
+    let config = fs::read_to_string(workspace.join(".cargo/config.toml"))
+        .unwrap()
+        .parse::<toml::Table>()
+        .unwrap();
+    assert_eq!(
+        config["build"]["rustflags"],
+        toml::Value::Array(vec!["-D".into(), "warnings".into()])
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &[]);
+    assert!(!workspace.join(".cargo/config.toml").exists());
+}
+
+#[test]
+fn exported_program_keypair_is_the_one_of_declare_id() {
+    let dir = tempfile::tempdir().unwrap();
+    let exported = dir.path().join("program-keypair.json");
+    let workspace = init(
+        dir.path(),
+        &["--export-program-keypair", exported.to_str().unwrap()],
+    );
+
+    let keypair = read_keypair_file(&exported).unwrap();
+    let lib_rs = fs::read_to_string(workspace.join("programs/my-program/src/lib.rs")).unwrap();
+    assert!(
+        lib_rs.contains(&format!("declare_id!(\"{}\");", keypair.pubkey())),
+        "{lib_rs}"
+    );
+}
+
+#[test]
+fn devbox_installs_the_requested_versions() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(
+        dir.path(),
+        &[
+            "--solana-version",
+            "1.18.20",
+            "--node-version",
+            "20",
+            "--anchor-version",
+            "0.29.0",
+        ],
+    );
+
+    let devbox = read_json(&workspace.join("devbox.json"));
+    assert_eq!(devbox["packages"]["nodejs"]["version"], "20");
+    let init_hook = devbox["shell"]["init_hook"].as_array().unwrap();
+    assert!(init_hook
+        .contains(&r#"sh -c "$(curl -sSfL https://release.solana.com/v1.18.20/install)""#.into()));
+    assert!(init_hook.contains(&"avm install 0.29.0".into()));
+}
+
+#[test]
+fn init_without_a_template_uses_basic_when_stdin_is_not_a_terminal() {
+    let dir = tempfile::tempdir().unwrap();
+    // Neither df-sol.toml nor a user config sets a template
+    let output = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .args(["init", "my-program", "--no-install", "--no-git"])
+        .current_dir(dir.path())
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("Available templates:"), "{stdout}");
+    let lib_rs =
+        fs::read_to_string(dir.path().join("my-program/programs/my-program/src/lib.rs")).unwrap();
+    assert!(lib_rs.contains("pub fn initialize("), "{lib_rs}");
+    assert!(!lib_rs.contains("pub fn increment("));
+}
+
+#[test]
+fn mint_token_env_example_points_at_devnet() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--template", "mint-token"]);
+
+    let env_example = fs::read_to_string(workspace.join(".env.example")).unwrap();
+    assert!(
+        env_example.contains("\nANCHOR_PROVIDER_URL=https://api.devnet.solana.com\n"),
+        "{env_example}"
+    );
+    assert!(env_example.contains("\nANCHOR_WALLET=wallet.json\n"));
+    let gitignore = fs::read_to_string(workspace.join(".gitignore")).unwrap();
+    assert!(gitignore.lines().any(|line| line == ".env"), "{gitignore}");
+
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--template", "counter"]);
+    let env_example = fs::read_to_string(workspace.join(".env.example")).unwrap();
+    assert!(
+        env_example.contains("\nANCHOR_PROVIDER_URL=http://127.0.0.1:8899\n"),
+        "{env_example}"
+    );
+}
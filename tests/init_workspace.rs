@@ -0,0 +1,33 @@
+//! `init_workspace` called as a library function.
+
+use df_sol::{init_workspace, InitOptions};
+
+// A single test since the current directory is shared by the whole test binary
+#[test]
+fn init_workspace_keeps_the_current_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    std::env::set_current_dir(dir.path()).unwrap();
+    let cwd = std::env::current_dir().unwrap();
+    let options = InitOptions {
+        template: vec!["counter".into()],
+        no_install: true,
+        no_git: true,
+        ..InitOptions::new("my-program")
+    };
+
+    init_workspace(options.clone()).unwrap();
+    assert_eq!(std::env::current_dir().unwrap(), cwd);
+    assert!(cwd
+        .join("my-program/programs/my-program/src/lib.rs")
+        .is_file());
+
+    // Failing within the workspace, when merging a conflicting provider into Anchor.toml
+    std::fs::write("extra.toml", "[provider]\ncluster = \"devnet\"\n").unwrap();
+    let options = InitOptions {
+        force: true,
+        anchor_toml_append: Some("extra.toml".into()),
+        ..options
+    };
+    assert!(init_workspace(options).is_err());
+    assert_eq!(std::env::current_dir().unwrap(), cwd);
+}
@@ -0,0 +1,38 @@
+//! Files of the initial commit of `init`.
+
+use std::process::{Command, Stdio};
+
+#[test]
+fn initial_commit_leaves_out_the_keypairs() {
+    let dir = tempfile::tempdir().unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(["init", "my-program", "--no-install"])
+        .current_dir(dir.path())
+        .env("GIT_AUTHOR_NAME", "df-sol")
+        .env("GIT_AUTHOR_EMAIL", "df-sol@example.com")
+        .env("GIT_COMMITTER_NAME", "df-sol")
+        .env("GIT_COMMITTER_EMAIL", "df-sol@example.com")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let workspace = dir.path().join("my-program");
+    assert!(workspace.join("wallet.json").is_file());
+    assert!(workspace
+        .join("target/deploy/my_program-keypair.json")
+        .is_file());
+    let output = Command::new("git")
+        .args(["ls-files"])
+        .current_dir(&workspace)
+        .output()
+        .unwrap();
+    let files = String::from_utf8(output.stdout).unwrap();
+    assert!(files.lines().any(|file| file == "Anchor.toml"));
+    assert!(
+        files
+            .lines()
+            .all(|file| file != "wallet.json" && !file.starts_with("target/")),
+        "{files}"
+    );
+}
@@ -0,0 +1,158 @@
+//! Installs of the node modules by `init`, run with a fake package manager on `PATH`.
+#![cfg(unix)]
+
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+/// Directory with fake commands running their scripts, to put first on `PATH`.
+fn fake_commands(dir: &Path, commands: &[(&str, &str)]) -> PathBuf {
+    let bin = dir.join("bin");
+    fs::create_dir(&bin).unwrap();
+    for (command, script) in commands {
+        let path = bin.join(command);
+        fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    bin
+}
+
+fn df_sol(dir: &Path, bin: &Path, args: &[&str]) -> Output {
+    let path = env::join_paths(
+        std::iter::once(bin.to_path_buf()).chain(env::split_paths(&env::var_os("PATH").unwrap())),
+    )
+    .unwrap();
+    Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(args)
+        .current_dir(dir)
+        .env("PATH", path)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn force_reinstall_removes_node_modules_and_lockfiles_before_installing() {
+    let dir = tempfile::tempdir().unwrap();
+    let bin = fake_commands(
+        dir.path(),
+        &[(
+            "yarn",
+            "ls -A > installed-over.txt\nmkdir node_modules && touch yarn.lock",
+        )],
+    );
+    let output = df_sol(
+        dir.path(),
+        &bin,
+        &["init", "my-program", "--no-install", "--no-git"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let workspace = dir.path().join("my-program");
+    fs::create_dir_all(workspace.join("node_modules/left-over")).unwrap();
+    for lockfile in [
+        "yarn.lock",
+        "package-lock.json",
+        "pnpm-lock.yaml",
+        "bun.lockb",
+        "bun.lock",
+    ] {
+        fs::write(workspace.join(lockfile), "").unwrap();
+    }
+
+    let output = df_sol(
+        dir.path(),
+        &bin,
+        &[
+            "init",
+            "my-program",
+            "--force",
+            "--force-reinstall",
+            "--no-git",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let installed_over = fs::read_to_string(workspace.join("installed-over.txt")).unwrap();
+    let installed_over = installed_over.lines().collect::<Vec<_>>();
+    for removed in [
+        "node_modules",
+        "yarn.lock",
+        "package-lock.json",
+        "pnpm-lock.yaml",
+        "bun.lockb",
+        "bun.lock",
+    ] {
+        assert!(!installed_over.contains(&removed), "{removed} was left");
+    }
+    assert!(installed_over.contains(&"package.json"));
+    assert!(workspace.join("yarn.lock").exists());
+}
+
+#[test]
+fn failed_installs_are_retried() {
+    let dir = tempfile::tempdir().unwrap();
+    let bin = fake_commands(
+        dir.path(),
+        &[
+            // Fails the first time only
+            (
+                "yarn",
+                "echo yarn >> installs.txt\n[ \"$(wc -l < installs.txt)\" -gt 1 ]",
+            ),
+            // Also asked for the license of package.json
+            (
+                "npm",
+                "case \"$1\" in install) echo npm >> installs.txt ;; *) echo ISC ;; esac",
+            ),
+        ],
+    );
+
+    let output = df_sol(
+        dir.path(),
+        &bin,
+        &["init", "my-program", "--install-retries", "2", "--no-git"],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let installs = fs::read_to_string(dir.path().join("my-program/installs.txt")).unwrap();
+    assert_eq!(installs, "yarn\nyarn\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("yarn install failed, retrying (1/2)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn pnpm_installs_and_runs_the_tests() {
+    let dir = tempfile::tempdir().unwrap();
+    let bin = fake_commands(
+        dir.path(),
+        &[
+            ("pnpm", "echo \"pnpm $*\" >> installs.txt"),
+            ("yarn", "echo \"yarn $*\" >> installs.txt"),
+        ],
+    );
+
+    let output = df_sol(
+        dir.path(),
+        &bin,
+        &["init", "demo", "--package-manager", "pnpm", "--no-git"],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let workspace = dir.path().join("demo");
+    let installs = fs::read_to_string(workspace.join("installs.txt")).unwrap();
+    assert_eq!(installs, "pnpm install\n");
+    let anchor_toml = fs::read_to_string(workspace.join("Anchor.toml"))
+        .unwrap()
+        .parse::<toml::Table>()
+        .unwrap();
+    let test_script = anchor_toml["scripts"]["test"].as_str().unwrap();
+    assert!(
+        test_script.starts_with("pnpm exec ts-mocha -p ./tsconfig.json "),
+        "{test_script}"
+    );
+}
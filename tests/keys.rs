@@ -0,0 +1,50 @@
+//! `keys sync` of the program IDs with the program keypairs.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use solana_sdk::signature::{write_keypair_file, Keypair, Signer};
+
+fn df_sol(dir: &Path, args: &[&str]) {
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "df-sol {args:?} failed");
+}
+
+#[test]
+fn keys_sync_declares_the_id_of_a_new_keypair() {
+    let dir = tempfile::tempdir().unwrap();
+    df_sol(
+        dir.path(),
+        &["init", "my-program", "--no-install", "--no-git"],
+    );
+    let workspace = dir.path().join("my-program");
+
+    let keypair = Keypair::new();
+    write_keypair_file(
+        &keypair,
+        workspace.join("target/deploy/my_program-keypair.json"),
+    )
+    .unwrap();
+    df_sol(&workspace, &["keys", "sync"]);
+
+    let id = keypair.pubkey().to_string();
+    let lib_rs = fs::read_to_string(workspace.join("programs/my-program/src/lib.rs")).unwrap();
+    assert!(
+        lib_rs.contains(&format!("declare_id!(\"{id}\");")),
+        "{lib_rs}"
+    );
+    let anchor_toml = fs::read_to_string(workspace.join("Anchor.toml"))
+        .unwrap()
+        .parse::<toml::Table>()
+        .unwrap();
+    assert_eq!(
+        anchor_toml["programs"]["localnet"]["my_program"].as_str(),
+        Some(id.as_str())
+    );
+}
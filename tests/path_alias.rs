@@ -0,0 +1,73 @@
+//! Imports of the generated tests in workspaces created with `init --with-path-alias`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn df_sol(dir: &Path, args: &[&str]) {
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "df-sol {args:?} failed");
+}
+
+fn init_with_path_alias(dir: &Path) {
+    df_sol(
+        dir,
+        &[
+            "init",
+            "my-program",
+            "--template",
+            "basic,counter",
+            "--with-path-alias",
+            "--no-install",
+            "--no-git",
+        ],
+    );
+}
+
+#[test]
+fn every_program_imports_its_own_types() {
+    let dir = tempfile::tempdir().unwrap();
+    init_with_path_alias(dir.path());
+    let workspace = dir.path().join("my-program");
+
+    let ts_config = fs::read_to_string(workspace.join("tsconfig.json")).unwrap();
+    assert!(ts_config.contains(r#""@program": ["./target/types/my_program"]"#));
+    assert!(ts_config.contains(r#""@program/*": ["./target/types/*"]"#));
+
+    let test = fs::read_to_string(workspace.join("tests/my-program.ts")).unwrap();
+    assert!(test.contains(r#"import { MyProgram } from "@program";"#));
+    let test = fs::read_to_string(workspace.join("tests/counter.ts")).unwrap();
+    assert!(test.contains(r#"import { Counter } from "@program/counter";"#));
+}
+
+#[test]
+fn added_programs_import_their_own_types() {
+    let dir = tempfile::tempdir().unwrap();
+    init_with_path_alias(dir.path());
+    let workspace = dir.path().join("my-program");
+    df_sol(&workspace, &["add", "vault", "--template", "pda-signer"]);
+
+    let test = fs::read_to_string(workspace.join("tests/vault.ts")).unwrap();
+    assert!(test.contains(r#"from "@program/vault";"#));
+}
+
+#[test]
+fn added_programs_import_their_types_relatively_without_a_program_alias() {
+    let dir = tempfile::tempdir().unwrap();
+    init_with_path_alias(dir.path());
+    let workspace = dir.path().join("my-program");
+    // tsconfig.json of a workspace generated before `@program/*` was mapped
+    let ts_config_path = workspace.join("tsconfig.json");
+    let ts_config = fs::read_to_string(&ts_config_path).unwrap();
+    let ts_config = ts_config.replace(",\n      \"@program/*\": [\"./target/types/*\"]", "");
+    assert!(!ts_config.contains("@program/*"));
+    fs::write(&ts_config_path, ts_config).unwrap();
+    df_sol(&workspace, &["add", "vault", "--template", "pda-signer"]);
+
+    let test = fs::read_to_string(workspace.join("tests/vault.ts")).unwrap();
+    assert!(test.contains(r#"from "../target/types/vault";"#));
+}
@@ -0,0 +1,153 @@
+//! `regenerate` reproducing a workspace from the manifest of `init --write-manifest`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use solana_sdk::signature::{write_keypair_file, Keypair};
+
+fn df_sol(dir: &Path, args: &[&str]) {
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "df-sol {args:?} failed");
+}
+
+/// Files of `dir` by their path relative to it.
+fn read_files(dir: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .map(Result::unwrap)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let path = entry.path().strip_prefix(dir).unwrap().to_path_buf();
+            (path, fs::read(entry.path()).unwrap())
+        })
+        .collect()
+}
+
+/// Manifest without the entry of the wallet, a new keypair of another size on each generation.
+fn manifest_without_wallet(manifest: &[u8]) -> serde_json::Value {
+    let mut manifest: serde_json::Value = serde_json::from_slice(manifest).unwrap();
+    manifest["files"]
+        .as_array_mut()
+        .unwrap()
+        .retain(|file| file["path"] != "wallet.json");
+    manifest
+}
+
+#[test]
+fn regenerate_applies_the_template_of_the_config() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("df-sol.toml"), "template = \"counter\"\n").unwrap();
+    df_sol(
+        dir.path(),
+        &[
+            "init",
+            "my-program",
+            "--write-manifest",
+            "--no-install",
+            "--no-git",
+        ],
+    );
+
+    let manifest = dir.path().join("my-program/.df-sol/manifest.json");
+    let manifest_json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest).unwrap()).unwrap();
+    assert_eq!(manifest_json["template"], "counter");
+    assert_eq!(
+        manifest_json["options"]["template"],
+        serde_json::json!(["counter"])
+    );
+    let files = manifest_json["files"].as_array().unwrap();
+    assert!(files.iter().any(|file| file["path"] == "Anchor.toml"));
+
+    // Regenerated twice, the second time from the manifest written by the first regeneration
+    df_sol(
+        dir.path(),
+        &[
+            "regenerate",
+            "--manifest",
+            manifest.to_str().unwrap(),
+            "first",
+        ],
+    );
+    let manifest = dir.path().join("first/my-program/.df-sol/manifest.json");
+    df_sol(
+        dir.path(),
+        &[
+            "regenerate",
+            "--manifest",
+            manifest.to_str().unwrap(),
+            "second",
+        ],
+    );
+
+    for out_dir in ["first", "second"] {
+        let lib_rs = dir
+            .path()
+            .join(out_dir)
+            .join("my-program/programs/my-program/src/lib.rs");
+        let program = fs::read_to_string(lib_rs).unwrap();
+        assert!(program.contains("pub fn increment("), "{out_dir}");
+    }
+}
+
+#[test]
+fn regenerate_reproduces_the_files_of_init() {
+    let dir = tempfile::tempdir().unwrap();
+    // Absolute, as relative paths of the manifest are resolved in the regenerated directory
+    let program_keypair = dir.path().join("program-keypair.json");
+    write_keypair_file(&Keypair::new(), &program_keypair).unwrap();
+    df_sol(
+        dir.path(),
+        &[
+            "init",
+            "my-program",
+            "--template",
+            "counter",
+            "--program-keypair",
+            program_keypair.to_str().unwrap(),
+            "--write-manifest",
+            "--no-install",
+            "--no-git",
+        ],
+    );
+    df_sol(
+        dir.path(),
+        &[
+            "regenerate",
+            "--manifest",
+            "my-program/.df-sol/manifest.json",
+            "regenerated",
+        ],
+    );
+
+    let original = read_files(&dir.path().join("my-program"));
+    let regenerated = read_files(&dir.path().join("regenerated/my-program"));
+    assert_eq!(
+        original.keys().collect::<Vec<_>>(),
+        regenerated.keys().collect::<Vec<_>>()
+    );
+    for (path, content) in &original {
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        if file_name == "wallet.json" || file_name.ends_with("-keypair.json") {
+            continue;
+        }
+        if path == Path::new(".df-sol/manifest.json") {
+            assert_eq!(
+                manifest_without_wallet(content),
+                manifest_without_wallet(&regenerated[path])
+            );
+            continue;
+        }
+        assert!(
+            *content == regenerated[path],
+            "{} differs after regenerate",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Golden-file tests of the generated templates. After an intended change to the templates,
+//! update the snapshots with `cargo run -- snapshot-templates tests/snapshots`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SNAPSHOTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+
+/// Contents of the files of `dir` by their path relative to it.
+fn read_files(dir: &Path) -> BTreeMap<PathBuf, String> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .map(Result::unwrap)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let path = entry.path().strip_prefix(dir).unwrap().to_path_buf();
+            let content = std::fs::read_to_string(entry.path()).unwrap();
+            (path, content)
+        })
+        .collect()
+}
+
+fn snapshot_templates() -> tempfile::TempDir {
+    let out_dir = tempfile::tempdir().unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .arg("snapshot-templates")
+        .arg(out_dir.path())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    out_dir
+}
+
+#[test]
+fn templates_match_snapshots() {
+    let out_dir = snapshot_templates();
+    let generated = read_files(out_dir.path());
+    let committed = read_files(Path::new(SNAPSHOTS_DIR));
+
+    let paths = |files: &BTreeMap<PathBuf, String>| files.keys().cloned().collect::<Vec<_>>();
+    assert_eq!(
+        paths(&generated),
+        paths(&committed),
+        "Generated files changed"
+    );
+    for (path, content) in &generated {
+        assert_eq!(
+            content,
+            &committed[path],
+            "{} changed, update the snapshots if intended",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn basic_snapshot_files() {
+    let files = read_files(&Path::new(SNAPSHOTS_DIR).join("basic"));
+    let paths = files
+        .keys()
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        paths,
+        [
+            ".env.example",
+            ".gitignore",
+            ".prettierignore",
+            "Anchor.toml",
+            "Cargo.toml",
+            "README.md",
+            "devbox.json",
+            "migrations/deploy.ts",
+            "package.json",
+            "programs/snapshot/Cargo.toml",
+            "programs/snapshot/Xargo.toml",
+            "programs/snapshot/src/lib.rs",
+            "tests/snapshot.ts",
+            "tsconfig.json",
+        ]
+    );
+    assert!(files[Path::new("programs/snapshot/src/lib.rs")].contains("pub fn initialize("));
+    assert!(files[Path::new("Anchor.toml")].contains("[programs.localnet]\nsnapshot = "));
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+declare_id!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+
+#[program]
+pub mod snapshot {
+    use super::*;
+
+    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize {}
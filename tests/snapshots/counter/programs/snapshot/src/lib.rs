@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+declare_id!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+
+#[program]
+pub mod snapshot {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let counter_account = &mut ctx.accounts.counter;
+        counter_account.count = 0;
+        Ok(())
+    }
+
+    pub fn increment(ctx: Context<Increment>) -> Result<()> {
+        let counter_account = &mut ctx.accounts.counter;
+        counter_account.count += 1;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        seeds = [b"counter"],
+        bump,
+        payer=user,
+        space = Counter::space()
+    )]
+    pub counter: Account<'info, Counter>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct Increment<'info> {
+    #[account(mut)]
+    pub counter: Account<'info, Counter>,
+
+    #[account(mut)]  // Remove leading space
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[account]
+pub struct Counter {
+    count: u64
+}
+
+impl Counter {
+    pub fn space() -> usize {
+        8 +  // discriminator
+        8 // counter
+    }
+}
@@ -0,0 +1,48 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {  PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  let counterAccount: PublicKey;
+  let counterBump: number;
+
+  before("Boilerplates", async () => {
+    [counterAccount, counterBump] = await PublicKey.findProgramAddress(
+      [Buffer.from("counter")],
+      program.programId
+    );
+  });
+
+  it("Initialize counter!", async () => {
+    await program.methods
+      .initialize()
+      .accounts({
+        counter: counterAccount,
+        user: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const counter = await program.account.counter.fetch(counterAccount);
+    expect(counter.count.toString()).eq("0")
+  });
+  it("Increment counter", async () => {
+    await program.methods
+      .increment()
+      .accounts({
+        counter: counterAccount,
+        user: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const counter = await program.account.counter.fetch(counterAccount);
+    expect(counter.count.toString()).eq("1")
+  });
+});
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+declare_id!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+
+/// Maximum length in bytes of the title of an entry
+pub const MAX_TITLE_LEN: usize = 50;
+/// Maximum length in bytes of the message of an entry
+pub const MAX_MESSAGE_LEN: usize = 280;
+
+#[program]
+pub mod snapshot {
+    use super::*;
+
+    pub fn create_entry(ctx: Context<CreateEntry>, title: String, message: String) -> Result<()> {
+        require!(title.len() <= MAX_TITLE_LEN, CrudError::TitleTooLong);
+        require!(message.len() <= MAX_MESSAGE_LEN, CrudError::MessageTooLong);
+
+        ctx.accounts.entry.set_inner(Entry {
+            owner: ctx.accounts.owner.key(),
+            title,
+            message,
+            bump: ctx.bumps.entry,
+        });
+        Ok(())
+    }
+
+    pub fn update_entry(ctx: Context<UpdateEntry>, message: String) -> Result<()> {
+        require!(message.len() <= MAX_MESSAGE_LEN, CrudError::MessageTooLong);
+
+        ctx.accounts.entry.message = message;
+        Ok(())
+    }
+
+    pub fn delete_entry(_ctx: Context<DeleteEntry>) -> Result<()> {
+        // The `close` constraint refunds the rent of the entry to its owner
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateEntry<'info> {
+    // One entry per user, derived from the owner's public key
+    #[account(
+        init,
+        seeds = [b"entry", owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + Entry::INIT_SPACE
+    )]
+    pub entry: Account<'info, Entry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEntry<'info> {
+    #[account(
+        mut,
+        seeds = [b"entry", owner.key().as_ref()],
+        bump = entry.bump,
+        has_one = owner
+    )]
+    pub entry: Account<'info, Entry>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteEntry<'info> {
+    #[account(
+        mut,
+        seeds = [b"entry", owner.key().as_ref()],
+        bump = entry.bump,
+        has_one = owner,
+        close = owner
+    )]
+    pub entry: Account<'info, Entry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Entry {
+    pub owner: Pubkey,
+    // `max_len` sizes the account for the longest strings accepted by the program
+    #[max_len(MAX_TITLE_LEN)]
+    pub title: String,
+    #[max_len(MAX_MESSAGE_LEN)]
+    pub message: String,
+    pub bump: u8,
+}
+
+// Anchor numbers custom errors from 6000, the first variant is 6000
+#[error_code]
+pub enum CrudError {
+    #[msg("The title is longer than 50 bytes")]
+    TitleTooLong,
+    #[msg("The message is longer than 280 bytes")]
+    MessageTooLong,
+}
@@ -0,0 +1,51 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const owner = provider.wallet.publicKey;
+
+  const [entry] = PublicKey.findProgramAddressSync(
+    [Buffer.from("entry"), owner.toBuffer()],
+    program.programId
+  );
+
+  it("Create an entry", async () => {
+    await program.methods
+      .createEntry("Favorites", "Blue, pizza and Solana")
+      .accounts({ entry, owner, systemProgram: SystemProgram.programId })
+      .rpc();
+  });
+
+  it("Read the entry", async () => {
+    const state = await program.account.entry.fetch(entry);
+    expect(state.owner.toBase58()).eq(owner.toBase58());
+    expect(state.title).eq("Favorites");
+    expect(state.message).eq("Blue, pizza and Solana");
+  });
+
+  it("Update the entry", async () => {
+    await program.methods
+      .updateEntry("Green, sushi and Solana")
+      .accounts({ entry, owner })
+      .rpc();
+
+    const state = await program.account.entry.fetch(entry);
+    expect(state.message).eq("Green, sushi and Solana");
+  });
+
+  it("Delete the entry", async () => {
+    await program.methods.deleteEntry().accounts({ entry, owner }).rpc();
+
+    // The entry is closed, so the tests can run again on the same validator
+    const state = await program.account.entry.fetchNullable(entry);
+    expect(state).eq(null);
+  });
+});
@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+declare_id!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+
+#[program]
+pub mod snapshot {
+    use super::*;
+
+    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
+        ctx.accounts.escrow.set_inner(Escrow {
+            seed,
+            maker: ctx.accounts.maker.key(),
+            mint_a: ctx.accounts.mint_a.key(),
+            mint_b: ctx.accounts.mint_b.key(),
+            receive,
+            bump: ctx.bumps.escrow,
+        });
+
+        // Move the maker's tokens into the vault owned by the escrow PDA
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.maker_ata_a.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.maker.to_account_info(),
+                },
+            ),
+            deposit,
+        )
+    }
+
+    pub fn take(ctx: Context<Take>) -> Result<()> {
+        // The taker pays the maker the amount of mint B they asked for
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.taker_ata_b.to_account_info(),
+                    to: ctx.accounts.maker_ata_b.to_account_info(),
+                    authority: ctx.accounts.taker.to_account_info(),
+                },
+            ),
+            ctx.accounts.escrow.receive,
+        )?;
+
+        // The escrow PDA signs the release of the vault to the taker
+        let escrow = &ctx.accounts.escrow;
+        let seed = escrow.seed.to_le_bytes();
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.maker.as_ref(),
+            seed.as_ref(),
+            &[escrow.bump],
+        ];
+        let signer = [&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.taker_ata_a.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                },
+                &signer,
+            ),
+            ctx.accounts.vault.amount,
+        )?;
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.maker.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            &signer,
+        ))
+    }
+
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        // The escrow PDA signs the return of the vault to the maker
+        let escrow = &ctx.accounts.escrow;
+        let seed = escrow.seed.to_le_bytes();
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.maker.as_ref(),
+            seed.as_ref(),
+            &[escrow.bump],
+        ];
+        let signer = [&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.maker_ata_a.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                },
+                &signer,
+            ),
+            ctx.accounts.vault.amount,
+        )?;
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.maker.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            &signer,
+        ))
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct Make<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub mint_a: Account<'info, Mint>,
+
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = maker)]
+    pub maker_ata_a: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Take<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    pub mint_a: Account<'info, Mint>,
+
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker
+    )]
+    pub taker_ata_a: Account<'info, TokenAccount>,
+
+    #[account(mut, associated_token::mint = mint_b, associated_token::authority = taker)]
+    pub taker_ata_b: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker
+    )]
+    pub maker_ata_b: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = escrow)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub mint_a: Account<'info, Mint>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = maker)]
+    pub maker_ata_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = escrow)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub bump: u8,
+}
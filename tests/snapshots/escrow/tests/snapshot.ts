@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const connection = provider.connection;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const maker = provider.wallet.publicKey;
+  const taker = Keypair.generate();
+  const deposit = 1_000;
+  const receive = 500;
+
+  let mintA: PublicKey;
+  let mintB: PublicKey;
+
+  const ata = (mint: PublicKey, owner: PublicKey) =>
+    getAssociatedTokenAddressSync(mint, owner, true);
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  // Each escrow of the maker is derived from a seed
+  const escrowAddress = (seed: anchor.BN) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("escrow"),
+        maker.toBuffer(),
+        seed.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  const make = async (seed: anchor.BN) => {
+    const escrow = escrowAddress(seed);
+    await program.methods
+      .make(seed, new anchor.BN(deposit), new anchor.BN(receive))
+      .accounts({
+        maker,
+        mintA,
+        mintB,
+        makerAtaA: ata(mintA, maker),
+        escrow,
+        vault: ata(mintA, escrow),
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return escrow;
+  };
+
+  before(async () => {
+    // The maker holds mint A and the taker holds mint B
+    await connection.confirmTransaction(
+      await connection.requestAirdrop(taker.publicKey, LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    mintA = await createMint(connection, payer, maker, null, 0);
+    mintB = await createMint(connection, payer, maker, null, 0);
+
+    const makerAtaA = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      mintA,
+      maker
+    );
+    const takerAtaB = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      mintB,
+      taker.publicKey
+    );
+    await mintTo(connection, payer, mintA, makerAtaA.address, payer, deposit * 2);
+    await mintTo(connection, payer, mintB, takerAtaB.address, payer, receive);
+  });
+
+  it("Make and take", async () => {
+    const escrow = await make(new anchor.BN(1));
+    const vault = ata(mintA, escrow);
+    expect(await balance(vault)).eq(deposit);
+
+    await program.methods
+      .take()
+      .accounts({
+        taker: taker.publicKey,
+        maker,
+        mintA,
+        mintB,
+        takerAtaA: ata(mintA, taker.publicKey),
+        takerAtaB: ata(mintB, taker.publicKey),
+        makerAtaB: ata(mintB, maker),
+        escrow,
+        vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([taker])
+      .rpc();
+
+    expect(await balance(ata(mintA, taker.publicKey))).eq(deposit);
+    expect(await balance(ata(mintB, maker))).eq(receive);
+    expect(await connection.getAccountInfo(escrow)).eq(null);
+  });
+
+  it("Make and refund", async () => {
+    const escrow = await make(new anchor.BN(2));
+
+    await program.methods
+      .refund()
+      .accounts({
+        maker,
+        mintA,
+        makerAtaA: ata(mintA, maker),
+        escrow,
+        vault: ata(mintA, escrow),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    // The first escrow was taken, so the maker is left with the refunded deposit
+    expect(await balance(ata(mintA, maker))).eq(deposit);
+    expect(await connection.getAccountInfo(escrow)).eq(null);
+  });
+});
@@ -0,0 +1,134 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { assert } from "chai";
+import BN from "bn.js";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+
+  // Metaplex Constants
+  const METADATA_SEED = "metadata";
+  const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+
+  // Constants from our program
+  const MINT_SEED = "mint";
+
+  // Data for our tests
+  const payer = provider.wallet.publicKey;
+  const recipient = payer;
+  const metadata = {
+    name: "Icy",
+    symbol: "ICY",
+    uri: "https://cdn.discordapp.com/emojis/1192768878183465062.png?size=240&quality=lossless",
+    decimals: 9,
+  };
+  const mintAmount = 10;
+
+  // Derive the public key for our mint account
+  const [mint] = PublicKey.findProgramAddressSync(
+    [Buffer.from(MINT_SEED)],
+    program.programId
+  );
+
+  // Derive the public key for our metadata account using the Metaplex program
+  const [metadataAddress] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(METADATA_SEED),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+
+  it("initialize", async () => {
+    // Check if the mint account already exists
+    const info = await provider.connection.getAccountInfo(mint);
+    if (info) {
+      return; // Do not attempt to initialize if already initialized
+    }
+    console.log("  Mint not found. Attempting to initialize.");
+
+    // Define the accounts and arguments for the `initToken` function call
+    const context = {
+      metadata: metadataAddress,
+      mint,
+      payer,
+      rent: SYSVAR_RENT_PUBKEY,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    };
+
+    // Call the `initToken` function to initialize the mint account
+    const txHash = await program.methods
+      .initToken(metadata)
+      .accounts(context)
+      .rpc();
+
+    // Wait for confirmation and log transaction details
+    await provider.connection.confirmTransaction(txHash, "finalized");
+    console.log(`  https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
+
+    // Verify that the mint account was initialized
+    const newInfo = await provider.connection.getAccountInfo(mint);
+    assert(newInfo, "  Mint should be initialized.");
+  });
+
+  it("mint tokens", async () => {
+    // Derive the associated token account address for the recipient
+    const destination = anchor.utils.token.associatedAddress({
+      mint: mint,
+      owner: recipient,
+    });
+
+    // Get initial token balance (0 if account not yet created)
+    let initialBalance: number;
+    try {
+      const balance = await provider.connection.getTokenAccountBalance(
+        destination
+      );
+      initialBalance = balance.value.uiAmount;
+    } catch {
+      // Token account not yet initiated has 0 balance
+      initialBalance = 0;
+    }
+
+    // Define the accounts and arguments for the `mintTokens` function call
+    const context = {
+      mint,
+      destination,
+      recipient,
+      payer,
+      rent: SYSVAR_RENT_PUBKEY,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+    };
+
+    // Call the `mintTokens` function to mint tokens
+    const txHash = await program.methods
+      .mintTokens(new BN(mintAmount).mul(new BN(10).pow(new BN(metadata.decimals))))
+      .accounts(context)
+      .rpc();
+    await provider.connection.confirmTransaction(txHash);
+    console.log(`  https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
+
+    // check icy balance of recipient
+    const postBalance = (
+      await provider.connection.getTokenAccountBalance(destination)
+    ).value.uiAmount;
+    assert.equal(
+      initialBalance + mintAmount,
+      postBalance,
+      "Post balance should equal initial plus mint amount"
+    );
+  });
+});
@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+declare_id!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+
+/// Maximum number of owners of a multisig
+pub const MAX_OWNERS: usize = 10;
+
+#[program]
+pub mod snapshot {
+    use super::*;
+
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            !owners.is_empty() && owners.len() <= MAX_OWNERS,
+            MultisigError::InvalidOwners
+        );
+        for (i, owner) in owners.iter().enumerate() {
+            require!(!owners[..i].contains(owner), MultisigError::DuplicateOwner);
+        }
+        require!(
+            threshold > 0 && threshold as usize <= owners.len(),
+            MultisigError::InvalidThreshold
+        );
+
+        ctx.accounts.multisig.set_inner(Multisig {
+            create_key: ctx.accounts.create_key.key(),
+            owners,
+            threshold,
+            transaction_count: 0,
+            bump: ctx.bumps.multisig,
+        });
+        Ok(())
+    }
+
+    pub fn propose_transaction(
+        ctx: Context<ProposeTransaction>,
+        program_id: Pubkey,
+        accounts: Vec<TransactionAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        let owner_index = multisig.owner_index(&ctx.accounts.proposer.key())?;
+
+        // Proposing a transaction approves it
+        let mut approvals = vec![false; multisig.owners.len()];
+        approvals[owner_index] = true;
+        ctx.accounts.transaction.set_inner(Transaction {
+            multisig: multisig.key(),
+            program_id,
+            accounts,
+            data,
+            approvals,
+            executed: false,
+        });
+        multisig.transaction_count += 1;
+        Ok(())
+    }
+
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        let owner_index = ctx
+            .accounts
+            .multisig
+            .owner_index(&ctx.accounts.owner.key())?;
+        ctx.accounts.transaction.approvals[owner_index] = true;
+        Ok(())
+    }
+
+    pub fn execute(ctx: Context<Execute>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        multisig.owner_index(&ctx.accounts.owner.key())?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        let approvals = transaction
+            .approvals
+            .iter()
+            .filter(|approved| **approved)
+            .count();
+        require!(
+            approvals >= multisig.threshold as usize,
+            MultisigError::NotEnoughApprovals
+        );
+        transaction.executed = true;
+
+        // The multisig PDA signs for the accounts of the instruction marked as signers, the
+        // accounts and the called program are passed as remaining accounts
+        let instruction = Instruction {
+            program_id: transaction.program_id,
+            accounts: transaction.accounts.iter().map(Into::into).collect(),
+            data: transaction.data.clone(),
+        };
+        let seeds = &[
+            b"multisig".as_ref(),
+            multisig.create_key.as_ref(),
+            &[multisig.bump],
+        ];
+        invoke_signed(&instruction, ctx.remaining_accounts, &[&seeds[..]])?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(owners: Vec<Pubkey>)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Multisig::space(owners.len()),
+        seeds = [b"multisig", create_key.key().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: Any public key, it only makes the multisig address unique
+    pub create_key: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<TransactionAccount>, data: Vec<u8>)]
+pub struct ProposeTransaction<'info> {
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Transaction::space(multisig.owners.len(), accounts.len(), data.len()),
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &multisig.transaction_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        has_one = multisig,
+        constraint = !transaction.executed @ MultisigError::AlreadyExecuted
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        has_one = multisig,
+        constraint = !transaction.executed @ MultisigError::AlreadyExecuted
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub owner: Signer<'info>,
+}
+
+#[account]
+pub struct Multisig {
+    pub create_key: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub transaction_count: u64,
+    pub bump: u8,
+}
+
+impl Multisig {
+    /// Size of a multisig account with `owners` owners
+    pub fn space(owners: usize) -> usize {
+        8 + 32 + (4 + 32 * owners) + 1 + 8 + 1
+    }
+
+    fn owner_index(&self, key: &Pubkey) -> Result<usize> {
+        self.owners
+            .iter()
+            .position(|owner| owner == key)
+            .ok_or_else(|| error!(MultisigError::NotAnOwner))
+    }
+}
+
+#[account]
+pub struct Transaction {
+    pub multisig: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<TransactionAccount>,
+    pub data: Vec<u8>,
+    /// Whether each owner approved the transaction, in the order of `Multisig::owners`
+    pub approvals: Vec<bool>,
+    pub executed: bool,
+}
+
+impl Transaction {
+    /// Size of a transaction account of a multisig with `owners` owners, calling an
+    /// instruction with `accounts` accounts and `data_len` bytes of data
+    pub fn space(owners: usize, accounts: usize, data_len: usize) -> usize {
+        let accounts = 4 + TransactionAccount::SPACE * accounts;
+        8 + 32 + 32 + accounts + (4 + data_len) + (4 + owners) + 1
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransactionAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl TransactionAccount {
+    pub const SPACE: usize = 32 + 1 + 1;
+}
+
+impl From<&TransactionAccount> for AccountMeta {
+    fn from(account: &TransactionAccount) -> Self {
+        AccountMeta {
+            pubkey: account.pubkey,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        }
+    }
+}
+
+// Anchor numbers custom errors from 6000, the first variant is 6000
+#[error_code]
+pub enum MultisigError {
+    #[msg("A multisig needs between 1 and 10 owners")]
+    InvalidOwners,
+    #[msg("The owners must be distinct")]
+    DuplicateOwner,
+    #[msg("The threshold must be between 1 and the number of owners")]
+    InvalidThreshold,
+    #[msg("The signer is not an owner of the multisig")]
+    NotAnOwner,
+    #[msg("The transaction doesn't have enough approvals")]
+    NotEnoughApprovals,
+    #[msg("The transaction was already executed")]
+    AlreadyExecuted,
+}
@@ -0,0 +1,103 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+// The memo program is loaded by the local validator, the multisig signs a memo with its PDA
+const MEMO_PROGRAM_ID = new PublicKey(
+  "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+);
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const owner = provider.wallet.publicKey;
+  const otherOwners = [Keypair.generate(), Keypair.generate()];
+
+  // The create key makes the multisig address unique, so the tests can run again
+  const createKey = Keypair.generate().publicKey;
+  const [multisig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("multisig"), createKey.toBuffer()],
+    program.programId
+  );
+  const [transaction] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("transaction"),
+      multisig.toBuffer(),
+      new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+
+  // The accounts of the proposed instruction and its program are passed to `execute`
+  const execute = () =>
+    program.methods
+      .execute()
+      .accounts({ multisig, transaction, owner })
+      .remainingAccounts([
+        { pubkey: multisig, isSigner: false, isWritable: false },
+        { pubkey: MEMO_PROGRAM_ID, isSigner: false, isWritable: false },
+      ])
+      .rpc();
+
+  it("Create a 2-of-3 multisig", async () => {
+    await program.methods
+      .createMultisig([owner, ...otherOwners.map((o) => o.publicKey)], 2)
+      .accounts({
+        multisig,
+        createKey,
+        payer: owner,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const state = await program.account.multisig.fetch(multisig);
+    expect(state.owners.length).eq(3);
+    expect(state.threshold).eq(2);
+  });
+
+  it("Propose a memo signed by the multisig", async () => {
+    await program.methods
+      .proposeTransaction(
+        MEMO_PROGRAM_ID,
+        [{ pubkey: multisig, isSigner: true, isWritable: false }],
+        Buffer.from("Approved by the multisig")
+      )
+      .accounts({
+        multisig,
+        transaction,
+        proposer: owner,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const state = await program.account.transaction.fetch(transaction);
+    expect(state.approvals).deep.eq([true, false, false]);
+  });
+
+  it("Rejects execution below the threshold", async () => {
+    let error: anchor.AnchorError | undefined;
+    try {
+      await execute();
+    } catch (err) {
+      error = err as anchor.AnchorError;
+    }
+    expect(error?.error.errorCode.code).eq("NotEnoughApprovals");
+  });
+
+  it("Execute after a second approval", async () => {
+    await program.methods
+      .approve()
+      .accounts({ multisig, transaction, owner: otherOwners[0].publicKey })
+      .signers([otherOwners[0]])
+      .rpc();
+    await execute();
+
+    const state = await program.account.transaction.fetch(transaction);
+    expect(state.executed).eq(true);
+  });
+});
@@ -0,0 +1,91 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { assert } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+
+  // Metaplex Constants
+  const METADATA_SEED = "metadata";
+  const EDITION_SEED = "edition";
+  const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+
+  // Data for our tests
+  const payer = provider.wallet.publicKey;
+  const metadata = {
+    name: "Icy",
+    symbol: "ICY",
+    uri: "https://cdn.discordapp.com/emojis/1192768878183465062.png?size=240&quality=lossless",
+  };
+
+  // Every run mints a new NFT
+  const mint = Keypair.generate();
+
+  // Derive the metadata and master edition accounts using the Metaplex program
+  const [metadataAddress] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(METADATA_SEED),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.publicKey.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  const [masterEdition] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(METADATA_SEED),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.publicKey.toBuffer(),
+      Buffer.from(EDITION_SEED),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  const tokenAccount = anchor.utils.token.associatedAddress({
+    mint: mint.publicKey,
+    owner: payer,
+  });
+
+  it("mint nft", async () => {
+    const txHash = await program.methods
+      .mintNft(metadata)
+      .accounts({
+        payer,
+        mint: mint.publicKey,
+        tokenAccount,
+        metadata: metadataAddress,
+        masterEdition,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([mint])
+      .rpc();
+    console.log(`  https://explorer.solana.com/tx/${txHash}?cluster=devnet`);
+
+    // The payer holds the single token of the NFT
+    const balance = (
+      await provider.connection.getTokenAccountBalance(tokenAccount)
+    ).value;
+    assert.equal(balance.amount, "1");
+    assert.equal(balance.decimals, 0);
+
+    // The master edition was created by the Metaplex program
+    const edition = await provider.connection.getAccountInfo(masterEdition);
+    assert(edition, "  Master edition should exist.");
+    assert(edition.owner.equals(TOKEN_METADATA_PROGRAM_ID));
+  });
+});
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+declare_id!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+
+#[program]
+pub mod snapshot {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.paused = false;
+        ctx.accounts.counter.count = 0;
+        Ok(())
+    }
+
+    pub fn pause(ctx: Context<SetPaused>) -> Result<()> {
+        ctx.accounts.config.paused = true;
+        Ok(())
+    }
+
+    pub fn unpause(ctx: Context<SetPaused>) -> Result<()> {
+        ctx.accounts.config.paused = false;
+        Ok(())
+    }
+
+    pub fn increment(ctx: Context<Increment>) -> Result<()> {
+        // Reject the instruction while the program is paused
+        require!(!ctx.accounts.config.paused, PausableError::ProgramPaused);
+
+        ctx.accounts.counter.count += 1;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        seeds = [b"config"],
+        bump,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        seeds = [b"counter"],
+        bump,
+        payer = admin,
+        space = 8 + Counter::INIT_SPACE
+    )]
+    pub counter: Account<'info, Counter>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ PausableError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Increment<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"counter"], bump)]
+    pub counter: Account<'info, Counter>,
+
+    pub user: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub paused: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Counter {
+    pub count: u64,
+}
+
+// Anchor numbers custom errors from 6000, the first variant is 6000
+#[error_code]
+pub enum PausableError {
+    #[msg("The program is paused")]
+    ProgramPaused,
+    #[msg("Only the admin can pause or unpause the program")]
+    Unauthorized,
+}
@@ -0,0 +1,59 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const admin = provider.wallet.publicKey;
+
+  const [config] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const [counter] = PublicKey.findProgramAddressSync(
+    [Buffer.from("counter")],
+    program.programId
+  );
+
+  it("Initialize", async () => {
+    await program.methods
+      .initialize()
+      .accounts({ config, counter, admin })
+      .rpc();
+
+    const state = await program.account.config.fetch(config);
+    expect(state.paused).eq(false);
+  });
+
+  it("Rejects increment while paused", async () => {
+    await program.methods.pause().accounts({ config, admin }).rpc();
+
+    let error: anchor.AnchorError | undefined;
+    try {
+      await program.methods
+        .increment()
+        .accounts({ config, counter, user: admin })
+        .rpc();
+    } catch (err) {
+      error = err as anchor.AnchorError;
+    }
+    expect(error?.error.errorCode.code).eq("ProgramPaused");
+  });
+
+  it("Increments after unpause", async () => {
+    await program.methods.unpause().accounts({ config, admin }).rpc();
+    await program.methods
+      .increment()
+      .accounts({ config, counter, user: admin })
+      .rpc();
+
+    const state = await program.account.counter.fetch(counter);
+    expect(state.count.toString()).eq("1");
+  });
+});
@@ -0,0 +1,40 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const user = provider.wallet.publicKey;
+
+  // The vault is a PDA owned by the system program, one per user
+  const [vault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), user.toBuffer()],
+    program.programId
+  );
+
+  it("Deposit into the vault", async () => {
+    await program.methods
+      .deposit(new anchor.BN(LAMPORTS_PER_SOL))
+      .accounts({ vault, user })
+      .rpc();
+
+    const balance = await provider.connection.getBalance(vault);
+    expect(balance).eq(LAMPORTS_PER_SOL);
+  });
+
+  it("Withdraw from the vault", async () => {
+    await program.methods
+      .withdraw(new anchor.BN(LAMPORTS_PER_SOL / 2))
+      .accounts({ vault, user })
+      .rpc();
+
+    const balance = await provider.connection.getBalance(vault);
+    expect(balance).eq(LAMPORTS_PER_SOL / 2);
+  });
+});
@@ -0,0 +1,320 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+
+declare_id!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+
+// `reward_rate` is the reward per staked token and slot, in millionths
+pub const RATE_PRECISION: u128 = 1_000_000;
+
+#[program]
+pub mod snapshot {
+    use super::*;
+
+    pub fn initialize_pool(ctx: Context<InitializePool>, reward_rate: u64) -> Result<()> {
+        ctx.accounts.pool.set_inner(StakePool {
+            authority: ctx.accounts.authority.key(),
+            stake_mint: ctx.accounts.stake_mint.key(),
+            reward_mint: ctx.accounts.reward_mint.key(),
+            reward_rate,
+            total_staked: 0,
+            bump: ctx.bumps.pool,
+        });
+        Ok(())
+    }
+
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::ZeroAmount);
+
+        let slot = Clock::get()?.slot;
+        let reward_rate = ctx.accounts.pool.reward_rate;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.owner = ctx.accounts.user.key();
+        stake_account.pool = ctx.accounts.pool.key();
+        stake_account.bump = ctx.bumps.stake_account;
+        stake_account.accrue(reward_rate, slot);
+        stake_account.amount += amount;
+        ctx.accounts.pool.total_staked += amount;
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_ata.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::ZeroAmount);
+        require!(
+            amount <= ctx.accounts.stake_account.amount,
+            StakingError::InsufficientStake
+        );
+
+        let slot = Clock::get()?.slot;
+        let reward_rate = ctx.accounts.pool.reward_rate;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.accrue(reward_rate, slot);
+        stake_account.amount -= amount;
+        ctx.accounts.pool.total_staked -= amount;
+
+        // The pool PDA signs the release of the staked tokens
+        let stake_mint = ctx.accounts.stake_mint.key();
+        let seeds = &[
+            b"pool".as_ref(),
+            stake_mint.as_ref(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let signer = [&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &signer,
+            ),
+            amount,
+        )
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let reward_rate = ctx.accounts.pool.reward_rate;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.accrue(reward_rate, slot);
+        let rewards = stake_account.pending_rewards;
+        require!(rewards > 0, StakingError::NothingToClaim);
+        stake_account.pending_rewards = 0;
+
+        // The pool PDA is the authority of the reward mint
+        let stake_mint = ctx.accounts.pool.stake_mint;
+        let seeds = &[
+            b"pool".as_ref(),
+            stake_mint.as_ref(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let signer = [&seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.user_reward_ata.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &signer,
+            ),
+            rewards,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = StakePool::space(),
+        seeds = [b"pool", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward", pool.key().as_ref()],
+        bump,
+        mint::decimals = stake_mint.decimals,
+        mint::authority = pool
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = stake_mint,
+        associated_token::authority = pool
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = stake_mint,
+        seeds = [b"pool", stake_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = StakeAccount::space(),
+        seeds = [b"stake", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut, associated_token::mint = stake_mint, associated_token::authority = user)]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    #[account(mut, associated_token::mint = stake_mint, associated_token::authority = pool)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = stake_mint,
+        seeds = [b"pool", stake_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut, associated_token::mint = stake_mint, associated_token::authority = user)]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    #[account(mut, associated_token::mint = stake_mint, associated_token::authority = pool)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(has_one = reward_mint, seeds = [b"pool", pool.stake_mint.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(mut)]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user
+    )]
+    pub user_reward_ata: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct StakePool {
+    pub authority: Pubkey,
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_rate: u64,
+    pub total_staked: u64,
+    pub bump: u8,
+}
+
+impl StakePool {
+    pub fn space() -> usize {
+        8 +  // discriminator
+        32 + // authority
+        32 + // stake_mint
+        32 + // reward_mint
+        8 +  // reward_rate
+        8 +  // total_staked
+        1 // bump
+    }
+}
+
+#[account]
+pub struct StakeAccount {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub pending_rewards: u64,
+    pub last_update_slot: u64,
+    pub bump: u8,
+}
+
+impl StakeAccount {
+    pub fn space() -> usize {
+        8 +  // discriminator
+        32 + // owner
+        32 + // pool
+        8 +  // amount
+        8 +  // pending_rewards
+        8 +  // last_update_slot
+        1 // bump
+    }
+
+    /// Add the rewards of the slots elapsed since the last update to the pending rewards.
+    pub fn accrue(&mut self, reward_rate: u64, slot: u64) {
+        let elapsed = slot.saturating_sub(self.last_update_slot) as u128;
+        let rewards = self.amount as u128 * reward_rate as u128 * elapsed / RATE_PRECISION;
+        self.pending_rewards += rewards as u64;
+        self.last_update_slot = slot;
+    }
+}
+
+// Anchor numbers custom errors from 6000, the first variant is 6000
+#[error_code]
+pub enum StakingError {
+    #[msg("The amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("The amount is greater than the staked amount")]
+    InsufficientStake,
+    #[msg("No rewards have accrued yet")]
+    NothingToClaim,
+}
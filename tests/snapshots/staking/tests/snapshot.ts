@@ -0,0 +1,144 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const connection = provider.connection;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const user = provider.wallet.publicKey;
+
+  const stakeAmount = 1_000;
+  // One reward token per slot for the whole stake, in millionths per staked token
+  const rewardRate = 1_000;
+
+  let stakeMint: PublicKey;
+  let pool: PublicKey;
+  let rewardMint: PublicKey;
+  let stakeAccount: PublicKey;
+  let vault: PublicKey;
+  let userAta: PublicKey;
+
+  const ata = (mint: PublicKey, owner: PublicKey) =>
+    getAssociatedTokenAddressSync(mint, owner, true);
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  before(async () => {
+    stakeMint = await createMint(connection, payer, user, null, 0);
+    userAta = (
+      await getOrCreateAssociatedTokenAccount(connection, payer, stakeMint, user)
+    ).address;
+    await mintTo(connection, payer, stakeMint, userAta, payer, stakeAmount);
+
+    [pool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), stakeMint.toBuffer()],
+      program.programId
+    );
+    [rewardMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward"), pool.toBuffer()],
+      program.programId
+    );
+    [stakeAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake"), pool.toBuffer(), user.toBuffer()],
+      program.programId
+    );
+    vault = ata(stakeMint, pool);
+  });
+
+  it("Initialize the pool", async () => {
+    await program.methods
+      .initializePool(new anchor.BN(rewardRate))
+      .accounts({
+        authority: user,
+        stakeMint,
+        pool,
+        rewardMint,
+        vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const account = await program.account.stakePool.fetch(pool);
+    expect(account.rewardRate.toNumber()).eq(rewardRate);
+  });
+
+  it("Stake", async () => {
+    await program.methods
+      .stake(new anchor.BN(stakeAmount))
+      .accounts({
+        user,
+        stakeMint,
+        pool,
+        stakeAccount,
+        userAta,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    expect(await balance(vault)).eq(stakeAmount);
+  });
+
+  it("Claim rewards after some slots", async () => {
+    // Let a few slots pass so that rewards accrue
+    const { lastUpdateSlot } = await program.account.stakeAccount.fetch(
+      stakeAccount
+    );
+    while ((await connection.getSlot()) <= lastUpdateSlot.toNumber() + 2) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    await program.methods
+      .claimRewards()
+      .accounts({
+        user,
+        pool,
+        rewardMint,
+        stakeAccount,
+        userRewardAta: ata(rewardMint, user),
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    expect(await balance(ata(rewardMint, user))).greaterThan(0);
+  });
+
+  it("Unstake", async () => {
+    await program.methods
+      .unstake(new anchor.BN(stakeAmount))
+      .accounts({
+        user,
+        stakeMint,
+        pool,
+        stakeAccount,
+        userAta,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect(await balance(vault)).eq(0);
+    expect(await balance(userAta)).eq(stakeAmount);
+  });
+});
@@ -0,0 +1,91 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const payer = provider.wallet.publicKey;
+  const decimals = 9;
+
+  const [mint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("mint")],
+    program.programId
+  );
+  // Associated token accounts of Token-2022 mints are derived from the Token-2022 program
+  const destination = getAssociatedTokenAddressSync(
+    mint,
+    payer,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  it("Initialize the mint", async () => {
+    // The mint PDA survives between runs on a persistent validator
+    if (await provider.connection.getAccountInfo(mint)) {
+      return;
+    }
+
+    await program.methods
+      .initToken(decimals)
+      .accounts({
+        mint,
+        payer,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    const info = await getMint(
+      provider.connection,
+      mint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(info.decimals).eq(decimals);
+  });
+
+  it("Mint tokens", async () => {
+    const balance = async () => {
+      const info = await provider.connection.getAccountInfo(destination);
+      if (!info) {
+        return BigInt(0);
+      }
+      const account = await getAccount(
+        provider.connection,
+        destination,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      return account.amount;
+    };
+    const before = await balance();
+
+    await program.methods
+      .mintTokens(new anchor.BN(1_000))
+      .accounts({
+        mint,
+        destination,
+        recipient: payer,
+        payer,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect((await balance()) - before).eq(BigInt(1_000));
+  });
+});
@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferHookInstruction,
+  createMintToInstruction,
+  createTransferCheckedWithTransferHookInstruction,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const connection = provider.connection;
+  const owner = provider.wallet.publicKey;
+  const recipient = Keypair.generate().publicKey;
+  const decimals = 9;
+
+  // A new mint each run, its transfer hook extension points at the program
+  const mint = Keypair.generate();
+  const source = getAssociatedTokenAddressSync(
+    mint.publicKey,
+    owner,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const destination = getAssociatedTokenAddressSync(
+    mint.publicKey,
+    recipient,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const [counter] = PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), mint.publicKey.toBuffer()],
+    program.programId
+  );
+
+  it("Create a mint with the transfer hook extension", async () => {
+    const space = getMintLen([ExtensionType.TransferHook]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(space);
+    const transaction = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: owner,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferHookInstruction(
+        mint.publicKey,
+        owner,
+        program.programId,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mint.publicKey,
+        decimals,
+        owner,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createAssociatedTokenAccountInstruction(
+        owner,
+        source,
+        owner,
+        mint.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createAssociatedTokenAccountInstruction(
+        owner,
+        destination,
+        recipient,
+        mint.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createMintToInstruction(
+        mint.publicKey,
+        source,
+        owner,
+        1_000,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await provider.sendAndConfirm(transaction, [mint]);
+  });
+
+  it("Initialize the extra account meta list", async () => {
+    const [extraAccountMetaList] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extra-account-metas"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeExtraAccountMetaList()
+      .accounts({
+        payer: owner,
+        extraAccountMetaList,
+        mint: mint.publicKey,
+        counter,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Transfer calling the hook", async () => {
+    // Resolves the accounts of the hook from the extra account meta list
+    const instruction = await createTransferCheckedWithTransferHookInstruction(
+      connection,
+      source,
+      mint.publicKey,
+      destination,
+      owner,
+      BigInt(100),
+      decimals,
+      [],
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    await provider.sendAndConfirm(new Transaction().add(instruction));
+
+    const state = await program.account.counter.fetch(counter);
+    expect(state.transfers.toNumber()).eq(1);
+    expect(state.amount.toNumber()).eq(100);
+  });
+});
@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+declare_id!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+
+#[program]
+pub mod snapshot {
+    use super::*;
+
+    pub fn initialize_vesting(
+        ctx: Context<InitializeVesting>,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        total_amount: u64,
+    ) -> Result<()> {
+        require!(
+            start_ts <= cliff_ts && cliff_ts <= end_ts && start_ts < end_ts,
+            VestingError::InvalidSchedule
+        );
+
+        ctx.accounts.vesting.set_inner(Vesting {
+            beneficiary: ctx.accounts.beneficiary.key(),
+            mint: ctx.accounts.mint.key(),
+            start_ts,
+            cliff_ts,
+            end_ts,
+            total_amount,
+            claimed: 0,
+            bump: ctx.bumps.vesting,
+        });
+
+        // Lock the vested tokens in a vault owned by the vesting PDA
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder_ata.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            total_amount,
+        )
+    }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vesting = &ctx.accounts.vesting;
+        let amount = vesting.unlocked(now) - vesting.claimed;
+        require!(amount > 0, VestingError::NothingToClaim);
+
+        // The vesting PDA signs the release of the unlocked tokens
+        let beneficiary = vesting.beneficiary;
+        let mint = vesting.mint;
+        let seeds = &[
+            b"vesting".as_ref(),
+            beneficiary.as_ref(),
+            mint.as_ref(),
+            &[vesting.bump],
+        ];
+        let signer = [&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.beneficiary_ata.to_account_info(),
+                    authority: ctx.accounts.vesting.to_account_info(),
+                },
+                &signer,
+            ),
+            amount,
+        )?;
+
+        ctx.accounts.vesting.claimed += amount;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeVesting<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub beneficiary: SystemAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, associated_token::mint = mint, associated_token::authority = funder)]
+    pub funder_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = Vesting::space(),
+        seeds = [b"vesting", beneficiary.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    #[account(
+        init,
+        payer = funder,
+        associated_token::mint = mint,
+        associated_token::authority = vesting
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary
+    )]
+    pub beneficiary_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = beneficiary,
+        has_one = mint,
+        seeds = [b"vesting", beneficiary.key().as_ref(), mint.key().as_ref()],
+        bump = vesting.bump
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    #[account(mut, associated_token::mint = mint, associated_token::authority = vesting)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct Vesting {
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub total_amount: u64,
+    pub claimed: u64,
+    pub bump: u8,
+}
+
+impl Vesting {
+    pub fn space() -> usize {
+        8 +  // discriminator
+        32 + // beneficiary
+        32 + // mint
+        8 +  // start_ts
+        8 +  // cliff_ts
+        8 +  // end_ts
+        8 +  // total_amount
+        8 +  // claimed
+        1 // bump
+    }
+
+    /// Amount unlocked at `now`, nothing before the cliff and then linearly until the end.
+    pub fn unlocked(&self, now: i64) -> u64 {
+        if now < self.cliff_ts {
+            return 0;
+        }
+        if now >= self.end_ts {
+            return self.total_amount;
+        }
+
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+}
+
+// Anchor numbers custom errors from 6000, the first variant is 6000
+#[error_code]
+pub enum VestingError {
+    #[msg("The schedule must satisfy start <= cliff <= end with start < end")]
+    InvalidSchedule,
+    #[msg("No tokens are unlocked yet")]
+    NothingToClaim,
+}
@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Snapshot } from "../target/types/snapshot";
+
+describe("snapshot", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Snapshot as Program<Snapshot>;
+  const connection = provider.connection;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  // The provider wallet funds the vesting and is also its beneficiary
+  const beneficiary = provider.wallet.publicKey;
+  const totalAmount = 1_000_000;
+
+  // The cliff is a few seconds away and the end far in the future, so only part unlocks
+  const now = Math.floor(Date.now() / 1000);
+  const startTs = now - 10;
+  const cliffTs = now + 3;
+  const endTs = now + 1000;
+
+  let mint: PublicKey;
+  let vesting: PublicKey;
+  let vault: PublicKey;
+
+  const ata = (owner: PublicKey) =>
+    getAssociatedTokenAddressSync(mint, owner, true);
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  const claim = () =>
+    program.methods
+      .claim()
+      .accounts({
+        beneficiary,
+        mint,
+        beneficiaryAta: ata(beneficiary),
+        vesting,
+        vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  before(async () => {
+    mint = await createMint(connection, payer, beneficiary, null, 0);
+    const funderAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      mint,
+      beneficiary
+    );
+    await mintTo(connection, payer, mint, funderAta.address, payer, totalAmount);
+
+    [vesting] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting"), beneficiary.toBuffer(), mint.toBuffer()],
+      program.programId
+    );
+    vault = ata(vesting);
+  });
+
+  it("Initialize vesting", async () => {
+    await program.methods
+      .initializeVesting(
+        new anchor.BN(startTs),
+        new anchor.BN(cliffTs),
+        new anchor.BN(endTs),
+        new anchor.BN(totalAmount)
+      )
+      .accounts({
+        funder: beneficiary,
+        beneficiary,
+        mint,
+        funderAta: ata(beneficiary),
+        vesting,
+        vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    expect(await balance(vault)).eq(totalAmount);
+  });
+
+  it("Rejects claims before the cliff", async () => {
+    let error: anchor.AnchorError | undefined;
+    try {
+      await claim();
+    } catch (err) {
+      error = err as anchor.AnchorError;
+    }
+    expect(error?.error.errorCode.code).eq("NothingToClaim");
+  });
+
+  it("Claims part of the tokens after the cliff", async () => {
+    // Wait until the validator clock is past the cliff
+    const wait = cliffTs + 3 - Math.floor(Date.now() / 1000);
+    await new Promise((resolve) => setTimeout(resolve, Math.max(wait, 0) * 1000));
+
+    await claim();
+
+    const claimed = await balance(ata(beneficiary));
+    expect(claimed).greaterThan(0);
+    expect(claimed).lessThan(totalAmount);
+    expect(await balance(vault)).eq(totalAmount - claimed);
+  });
+});
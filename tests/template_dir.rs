@@ -0,0 +1,125 @@
+//! Workspaces initialized from a template directory with `init --template-dir`.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+fn df_sol(dir: &Path, args: &[&str]) {
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "df-sol {args:?} failed");
+}
+
+#[test]
+fn template_dir_files_without_an_extension_or_utf_8() {
+    let dir = tempfile::tempdir().unwrap();
+    let template = dir.path().join("template");
+    fs::create_dir_all(template.join("programs/{{name}}/src")).unwrap();
+    fs::create_dir_all(template.join("assets")).unwrap();
+    fs::write(
+        template.join("programs/{{name}}/src/lib.rs"),
+        "use anchor_lang::prelude::*;\n\ndeclare_id!(\"{{program_id}}\");\n",
+    )
+    .unwrap();
+    fs::write(
+        template.join("Makefile"),
+        "test:\n\tanchor test # {{name}}\n",
+    )
+    .unwrap();
+    fs::write(template.join(".npmrc"), "save-exact=true\n").unwrap();
+    let logo = [0x89, b'P', b'N', b'G', 0xff, 0x00, b'{', b'{'];
+    fs::write(template.join("assets/logo.png"), logo).unwrap();
+
+    df_sol(
+        dir.path(),
+        &[
+            "init",
+            "my-program",
+            "--template-dir",
+            template.to_str().unwrap(),
+            "--no-install",
+            "--no-git",
+        ],
+    );
+
+    let workspace = dir.path().join("my-program");
+    assert_eq!(
+        fs::read_to_string(workspace.join("Makefile")).unwrap(),
+        "test:\n\tanchor test # my-program\n"
+    );
+    assert_eq!(
+        fs::read_to_string(workspace.join(".npmrc")).unwrap(),
+        "save-exact=true\n"
+    );
+    assert_eq!(fs::read(workspace.join("assets/logo.png")).unwrap(), logo);
+    assert!(workspace.join(".gitignore").is_file());
+}
+
+/// Program and test files of the workspace by their path, with the program ID replaced.
+fn program_files(workspace: &Path, program_id: &str) -> Vec<(String, String)> {
+    let mut files = walkdir::WalkDir::new(workspace)
+        .into_iter()
+        .map(Result::unwrap)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let path = entry.path().strip_prefix(workspace).unwrap();
+            let content = fs::read_to_string(entry.path()).unwrap_or_default();
+            (
+                path.to_string_lossy().replace('\\', "/"),
+                content.replace(program_id, "<program id>"),
+            )
+        })
+        .filter(|(path, _)| path.starts_with("programs/") || path.starts_with("tests/"))
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn program_id(workspace: &Path) -> String {
+    let anchor_toml = fs::read_to_string(workspace.join("Anchor.toml")).unwrap();
+    let anchor_toml = anchor_toml.parse::<toml::Table>().unwrap();
+    anchor_toml["programs"]["localnet"]["my_program"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn new_template_round_trips_through_template_dir() {
+    for template in ["counter", "escrow", "transfer-hook"] {
+        let dir = tempfile::tempdir().unwrap();
+        let args = ["my-program", "--no-install", "--no-git", "--no-wallet"];
+        df_sol(dir.path(), &["new-template", template, "template"]);
+        let built_in = dir.path().join("built-in");
+        fs::create_dir(&built_in).unwrap();
+        df_sol(
+            &built_in,
+            &[&["init", "--template", template][..], &args].concat(),
+        );
+        let rendered = dir.path().join("rendered");
+        fs::create_dir(&rendered).unwrap();
+        let template_dir = dir.path().join("template");
+        df_sol(
+            &rendered,
+            &[
+                &["init", "--template-dir", template_dir.to_str().unwrap()][..],
+                &args,
+            ]
+            .concat(),
+        );
+
+        let built_in = built_in.join("my-program");
+        let rendered = rendered.join("my-program");
+        let files = program_files(&built_in, &program_id(&built_in));
+        assert!(files.iter().any(|(path, _)| path == "tests/my-program.ts"));
+        assert_eq!(
+            program_files(&rendered, &program_id(&rendered)),
+            files,
+            "{template}"
+        );
+    }
+}
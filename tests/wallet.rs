@@ -0,0 +1,84 @@
+//! Wallet of workspaces created with `init`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use solana_sdk::signature::{read_keypair_file, write_keypair_file, Keypair, Signer};
+
+/// Workspace created by `init` with `args`.
+fn init(dir: &Path, args: &[&str]) -> PathBuf {
+    let status = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(["init", "my-program", "--no-install", "--no-git"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "df-sol init {args:?} failed");
+
+    dir.join("my-program")
+}
+
+fn provider_wallet(workspace: &Path) -> String {
+    let anchor_toml = fs::read_to_string(workspace.join("Anchor.toml"))
+        .unwrap()
+        .parse::<toml::Table>()
+        .unwrap();
+    anchor_toml["provider"]["wallet"].as_str().unwrap().into()
+}
+
+#[test]
+fn no_wallet_tests_with_the_default_keypair() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = init(dir.path(), &["--no-wallet"]);
+
+    assert!(!workspace.join("wallet.json").exists());
+    assert_eq!(provider_wallet(&workspace), "~/.config/solana/id.json");
+}
+
+#[test]
+fn write_pubkey_writes_the_pubkey_of_the_wallet() {
+    let dir = tempfile::tempdir().unwrap();
+    let pubkey_path = dir.path().join("wallet-pubkey.txt");
+    let workspace = init(
+        dir.path(),
+        &["--write-pubkey", pubkey_path.to_str().unwrap()],
+    );
+
+    let wallet = read_keypair_file(workspace.join("wallet.json")).unwrap();
+    let pubkey = fs::read_to_string(&pubkey_path).unwrap();
+    assert_eq!(pubkey.trim_end(), wallet.pubkey().to_string());
+}
+
+#[test]
+fn wallet_is_copied_into_the_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    let keypair = Keypair::new();
+    let wallet_path = dir.path().join("id.json");
+    write_keypair_file(&keypair, &wallet_path).unwrap();
+    let workspace = init(dir.path(), &["--wallet", wallet_path.to_str().unwrap()]);
+
+    let wallet = read_keypair_file(workspace.join("wallet.json")).unwrap();
+    assert_eq!(wallet.pubkey(), keypair.pubkey());
+    assert_eq!(provider_wallet(&workspace), "wallet.json");
+}
+
+#[test]
+fn wallet_must_be_a_keypair() {
+    let dir = tempfile::tempdir().unwrap();
+    let wallet_path = dir.path().join("id.json");
+    fs::write(&wallet_path, "[1, 2, 3]").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_df-sol"))
+        .stdin(Stdio::null())
+        .args(["init", "my-program", "--no-install", "--no-git", "--wallet"])
+        .arg(&wallet_path)
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("Error: Invalid wallet "), "{stderr}");
+    assert!(!dir.path().join("my-program/wallet.json").exists());
+}